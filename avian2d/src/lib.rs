@@ -15,6 +15,7 @@ use bevy::prelude::*;
 use bevy_tnua_physics_integration_layer::data_for_backends::{
     TnuaGhostPlatform, TnuaGhostSensor, TnuaGravity, TnuaMotor, TnuaNotPlatform,
     TnuaProximitySensor, TnuaProximitySensorOutput, TnuaRigidBodyTracker, TnuaToggle,
+    TnuaWaterSensor, TnuaWaterSensorOutput, TnuaWaterVolume,
 };
 use bevy_tnua_physics_integration_layer::math::*;
 use bevy_tnua_physics_integration_layer::subservient_sensors::TnuaSubservientSensor;
@@ -67,6 +68,7 @@ impl Plugin for TnuaAvian2dPlugin {
             (
                 update_rigid_body_trackers_system,
                 update_proximity_sensors_system,
+                update_water_sensors_system,
                 update_obstacle_radars_system,
             )
                 .in_set(TnuaPipelineStages::Sensors),
@@ -135,8 +137,10 @@ fn update_proximity_sensors_system(
         Option<&TnuaAvian2dSensorShape>,
         Option<&mut TnuaGhostSensor>,
         Option<&TnuaSubservientSensor>,
+        Option<&Transform>,
         Option<&TnuaToggle>,
     )>,
+    owners_query: Query<(&Position, &Rotation)>,
     collision_layers_query: Query<&CollisionLayers>,
     other_object_query: Query<(
         Option<(&Position, &LinearVelocity, &AngularVelocity)>,
@@ -145,6 +149,7 @@ fn update_proximity_sensors_system(
         Has<TnuaGhostPlatform>,
         Has<Sensor>,
         Has<TnuaNotPlatform>,
+        Has<TnuaWaterVolume>,
     )>,
 ) {
     query.par_iter_mut().for_each(
@@ -157,6 +162,7 @@ fn update_proximity_sensors_system(
             shape,
             mut ghost_sensor,
             subservient,
+            local_transform,
             tnua_toggle,
         )| {
             match tnua_toggle.copied().unwrap_or_default() {
@@ -164,12 +170,28 @@ fn update_proximity_sensors_system(
                 TnuaToggle::SenseOnly => {}
                 TnuaToggle::Enabled => {}
             }
-            let transform = Transform {
-                translation: position.f32().extend(0.0),
-                rotation: Quaternion::from(*rotation).f32(),
-                scale: collider
-                    .map(|collider| collider.scale().f32().extend(1.0))
-                    .unwrap_or(Vec3::ONE),
+            let transform = if let Some(subservient) = subservient {
+                // Avian does not sync the `Position` of entities without colliders inside a rigid
+                // body's hierarchy, so calculate it from the owner instead.
+                let Ok((owner_position, owner_rotation)) =
+                    owners_query.get(subservient.owner_entity)
+                else {
+                    return;
+                };
+                Transform {
+                    translation: owner_position.f32().extend(0.0),
+                    rotation: Quaternion::from(*owner_rotation).f32(),
+                    scale: Vec3::ONE,
+                }
+                .mul_transform(local_transform.copied().unwrap_or_default())
+            } else {
+                Transform {
+                    translation: position.f32().extend(0.0),
+                    rotation: Quaternion::from(*rotation).f32(),
+                    scale: collider
+                        .map(|collider| collider.scale().f32().extend(1.0))
+                        .unwrap_or(Vec3::ONE),
+                }
             };
             let cast_origin = transform.transform_point(sensor.cast_origin.f32());
             let cast_direction = sensor.cast_direction;
@@ -212,6 +234,7 @@ fn update_proximity_sensors_system(
                     mut entity_is_ghost,
                     mut entity_is_sensor,
                     mut entity_is_not_platform,
+                    mut entity_is_water,
                 )) = other_object_query.get(entity)
                 else {
                     return false;
@@ -232,6 +255,7 @@ fn update_proximity_sensors_system(
                         parent_is_ghost,
                         parent_is_sensor,
                         parent_is_not_platform,
+                        parent_is_water,
                     )) = other_object_query.get(parent_entity)
                     {
                         if entity_kinematic_data.is_none() {
@@ -243,10 +267,11 @@ fn update_proximity_sensors_system(
                         entity_is_ghost = entity_is_ghost || parent_is_ghost;
                         entity_is_sensor = entity_is_sensor || parent_is_sensor;
                         entity_is_not_platform = entity_is_not_platform || parent_is_not_platform;
+                        entity_is_water = entity_is_water || parent_is_water;
                     }
                 }
 
-                if entity_is_not_platform {
+                if entity_is_not_platform || entity_is_water {
                     return true;
                 }
 
@@ -300,6 +325,7 @@ fn update_proximity_sensors_system(
             };
 
             let query_filter = SpatialQueryFilter::from_excluded_entities([owner_entity]);
+            let mut cast_results = Vec::new();
             if let Some(TnuaAvian2dSensorShape(shape)) = shape {
                 spatial_query_pipeline.shape_hits_callback(
                     shape,
@@ -314,13 +340,14 @@ fn update_proximity_sensors_system(
                     &query_filter,
                     #[allow(clippy::useless_conversion)]
                     |shape_hit_data| {
-                        apply_cast(CastResult {
+                        cast_results.push(CastResult {
                             entity: shape_hit_data.entity,
                             proximity: shape_hit_data.distance,
                             intersection_point: shape_hit_data.point1,
                             normal: Dir3::new(shape_hit_data.normal1.extend(0.0).f32())
                                 .unwrap_or_else(|_| -cast_direction),
-                        })
+                        });
+                        true
                     },
                 );
             } else {
@@ -331,7 +358,7 @@ fn update_proximity_sensors_system(
                     true,
                     &query_filter,
                     |ray_hit_data| {
-                        apply_cast(CastResult {
+                        cast_results.push(CastResult {
                             entity: ray_hit_data.entity,
                             proximity: ray_hit_data.distance,
                             intersection_point: cast_origin.truncate().adjust_precision()
@@ -339,15 +366,92 @@ fn update_proximity_sensors_system(
                                     * cast_direction_2d.adjust_precision(),
                             normal: Dir3::new(ray_hit_data.normal.extend(0.0).f32())
                                 .unwrap_or_else(|_| -cast_direction),
-                        })
+                        });
+                        true
                     },
                 );
             }
+            // Avian does not report the hits ordered by distance, so they must be sorted before
+            // looking for the first tangible one.
+            cast_results.sort_by(|a, b| a.proximity.total_cmp(&b.proximity));
+            for cast_result in cast_results {
+                if !apply_cast(cast_result) {
+                    break;
+                }
+            }
             sensor.output = final_sensor_output;
         },
     );
 }
 
+#[allow(clippy::type_complexity)]
+fn update_water_sensors_system(
+    spatial_query_pipeline: Res<SpatialQueryPipeline>,
+    mut query: Query<(
+        Entity,
+        &Position,
+        &Rotation,
+        &mut TnuaWaterSensor,
+        Option<&TnuaToggle>,
+    )>,
+    water_volumes_query: Query<(Has<TnuaWaterVolume>, Option<&ColliderOf>)>,
+    velocities_query: Query<&LinearVelocity>,
+) {
+    for (owner_entity, position, rotation, mut sensor, tnua_toggle) in query.iter_mut() {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled => continue,
+            TnuaToggle::SenseOnly => {}
+            TnuaToggle::Enabled => {}
+        }
+        let cast_origin = position.0 + *rotation * sensor.cast_origin.truncate();
+        let cast_direction = Dir2::new(sensor.cast_direction.truncate().f32())
+            .expect("cast direction must be on the XY plane");
+        // Cast from above the origin, so that the ray will enter the water volume through its
+        // surface.
+        let cast_start = cast_origin - sensor.cast_range * cast_direction.adjust_precision();
+
+        let mut closest_hit: Option<(Entity, Float)> = None;
+        spatial_query_pipeline.ray_hits_callback(
+            cast_start,
+            cast_direction,
+            2.0 * sensor.cast_range,
+            true,
+            &SpatialQueryFilter::from_excluded_entities([owner_entity]),
+            |ray_hit_data| {
+                if closest_hit.is_some_and(|(_, distance)| distance <= ray_hit_data.distance) {
+                    return true;
+                }
+                let Ok((is_water, collider_of)) = water_volumes_query.get(ray_hit_data.entity)
+                else {
+                    return true;
+                };
+                let water_entity = if is_water {
+                    ray_hit_data.entity
+                } else if let Some(collider_of) = collider_of.filter(|collider_of| {
+                    water_volumes_query
+                        .get(collider_of.body)
+                        .is_ok_and(|(is_water, _)| is_water)
+                }) {
+                    collider_of.body
+                } else {
+                    return true;
+                };
+                closest_hit = Some((water_entity, ray_hit_data.distance));
+                true
+            },
+        );
+
+        sensor.output = closest_hit.map(|(entity, distance)| TnuaWaterSensorOutput {
+            entity,
+            depth: sensor.cast_range - distance,
+            entity_linvel: velocities_query
+                .get(entity)
+                .map(|linvel| linvel.0.adjust_precision().extend(0.0))
+                .unwrap_or(Vector3::ZERO),
+        });
+    }
+}
+
 fn update_obstacle_radars_system(
     spatial_query_pipeline: Res<SpatialQueryPipeline>,
     gravity: Res<Gravity>,
@@ -360,7 +464,7 @@ fn update_obstacle_radars_system(
         radar.pre_marking_update(
             radar_owner_entity,
             radar_position.0.extend(0.0),
-            Dir3::new(gravity.0.f32().extend(0.0)).unwrap_or(Dir3::Y),
+            Dir3::new(-gravity.0.f32().extend(0.0)).unwrap_or(Dir3::Y),
         );
         spatial_query_pipeline.shape_intersections_callback(
             &Collider::rectangle(2.0 * radar.radius, radar.height),
@@ -407,7 +511,7 @@ fn apply_motors_system(
         match tnua_toggle.copied().unwrap_or_default() {
             TnuaToggle::Disabled | TnuaToggle::SenseOnly => {
                 *external_force = Default::default();
-                return;
+                continue;
             }
             TnuaToggle::Enabled => {}
         }
@@ -27,6 +27,9 @@ use bevy_tnua_physics_integration_layer::data_for_backends::{TnuaGhostPlatform,
 use bevy_tnua_physics_integration_layer::data_for_backends::{
     TnuaMotor, TnuaProximitySensor, TnuaProximitySensorOutput, TnuaRigidBodyTracker,
 };
+use bevy_tnua_physics_integration_layer::data_for_backends::{
+    TnuaWaterSensor, TnuaWaterSensorOutput, TnuaWaterVolume,
+};
 use bevy_tnua_physics_integration_layer::obstacle_radar::TnuaObstacleRadar;
 use bevy_tnua_physics_integration_layer::subservient_sensors::TnuaSubservientSensor;
 use bevy_tnua_physics_integration_layer::TnuaPipelineStages;
@@ -76,6 +79,7 @@ impl Plugin for TnuaAvian3dPlugin {
             (
                 update_rigid_body_trackers_system,
                 update_proximity_sensors_system,
+                update_water_sensors_system,
                 update_obstacle_radars_system,
             )
                 .in_set(TnuaPipelineStages::Sensors),
@@ -144,8 +148,10 @@ fn update_proximity_sensors_system(
         Option<&TnuaAvian3dSensorShape>,
         Option<&mut TnuaGhostSensor>,
         Option<&TnuaSubservientSensor>,
+        Option<&Transform>,
         Option<&TnuaToggle>,
     )>,
+    owners_query: Query<(&Position, &Rotation)>,
     collision_layers_query: Query<&CollisionLayers>,
     other_object_query: Query<(
         Option<(&Position, &LinearVelocity, &AngularVelocity)>,
@@ -154,6 +160,7 @@ fn update_proximity_sensors_system(
         Has<TnuaGhostPlatform>,
         Has<Sensor>,
         Has<TnuaNotPlatform>,
+        Has<TnuaWaterVolume>,
     )>,
 ) {
     query.par_iter_mut().for_each(
@@ -166,6 +173,7 @@ fn update_proximity_sensors_system(
             shape,
             mut ghost_sensor,
             subservient,
+            local_transform,
             tnua_toggle,
         )| {
             match tnua_toggle.copied().unwrap_or_default() {
@@ -173,12 +181,28 @@ fn update_proximity_sensors_system(
                 TnuaToggle::SenseOnly => {}
                 TnuaToggle::Enabled => {}
             }
-            let transform = Transform {
-                translation: position.0.f32(),
-                rotation: rotation.0.f32(),
-                scale: collider
-                    .map(|collider| collider.scale().f32())
-                    .unwrap_or(Vec3::ONE),
+            let transform = if let Some(subservient) = subservient {
+                // Avian does not sync the `Position` of entities without colliders inside a rigid
+                // body's hierarchy, so calculate it from the owner instead.
+                let Ok((owner_position, owner_rotation)) =
+                    owners_query.get(subservient.owner_entity)
+                else {
+                    return;
+                };
+                Transform {
+                    translation: owner_position.0.f32(),
+                    rotation: owner_rotation.0.f32(),
+                    scale: Vec3::ONE,
+                }
+                .mul_transform(local_transform.copied().unwrap_or_default())
+            } else {
+                Transform {
+                    translation: position.0.f32(),
+                    rotation: rotation.0.f32(),
+                    scale: collider
+                        .map(|collider| collider.scale().f32())
+                        .unwrap_or(Vec3::ONE),
+                }
             };
 
             // TODO: is there any point in doing these transformations as f64 when that feature
@@ -222,6 +246,7 @@ fn update_proximity_sensors_system(
                     mut entity_is_ghost,
                     mut entity_is_sensor,
                     mut entity_is_not_platform,
+                    mut entity_is_water,
                 )) = other_object_query.get(entity)
                 else {
                     return false;
@@ -242,6 +267,7 @@ fn update_proximity_sensors_system(
                         parent_is_ghost,
                         parent_is_sensor,
                         parent_is_not_platform,
+                        parent_is_water,
                     )) = other_object_query.get(parent_entity)
                     {
                         if entity_kinematic_data.is_none() {
@@ -253,10 +279,11 @@ fn update_proximity_sensors_system(
                         entity_is_ghost = entity_is_ghost || parent_is_ghost;
                         entity_is_sensor = entity_is_sensor || parent_is_sensor;
                         entity_is_not_platform = entity_is_not_platform || parent_is_not_platform;
+                        entity_is_water = entity_is_water || parent_is_water;
                     }
                 }
 
-                if entity_is_not_platform {
+                if entity_is_not_platform || entity_is_water {
                     return true;
                 }
 
@@ -310,6 +337,7 @@ fn update_proximity_sensors_system(
             };
 
             let query_filter = SpatialQueryFilter::from_excluded_entities([owner_entity]);
+            let mut cast_results = Vec::new();
             if let Some(TnuaAvian3dSensorShape(shape)) = shape {
                 let owner_rotation = Quaternion::from_axis_angle(
                     cast_direction.adjust_precision(),
@@ -329,13 +357,14 @@ fn update_proximity_sensors_system(
                     },
                     &query_filter,
                     |shape_hit_data| {
-                        apply_cast(CastResult {
+                        cast_results.push(CastResult {
                             entity: shape_hit_data.entity,
                             proximity: shape_hit_data.distance,
                             intersection_point: shape_hit_data.point1,
                             normal: Dir3::new(shape_hit_data.normal1.f32())
                                 .unwrap_or_else(|_| -cast_direction),
-                        })
+                        });
+                        true
                     },
                 );
             } else {
@@ -346,22 +375,98 @@ fn update_proximity_sensors_system(
                     true,
                     &query_filter,
                     |ray_hit_data| {
-                        apply_cast(CastResult {
+                        cast_results.push(CastResult {
                             entity: ray_hit_data.entity,
                             proximity: ray_hit_data.distance,
                             intersection_point: cast_origin
                                 + ray_hit_data.distance * cast_direction.adjust_precision(),
                             normal: Dir3::new(ray_hit_data.normal.f32())
                                 .unwrap_or_else(|_| -cast_direction),
-                        })
+                        });
+                        true
                     },
                 );
             }
+            // Avian does not report the hits ordered by distance, so they must be sorted before
+            // looking for the first tangible one.
+            cast_results.sort_by(|a, b| a.proximity.total_cmp(&b.proximity));
+            for cast_result in cast_results {
+                if !apply_cast(cast_result) {
+                    break;
+                }
+            }
             sensor.output = final_sensor_output;
         },
     );
 }
 
+#[allow(clippy::type_complexity)]
+fn update_water_sensors_system(
+    spatial_query_pipeline: Res<SpatialQueryPipeline>,
+    mut query: Query<(
+        Entity,
+        &Position,
+        &Rotation,
+        &mut TnuaWaterSensor,
+        Option<&TnuaToggle>,
+    )>,
+    water_volumes_query: Query<(Has<TnuaWaterVolume>, Option<&ColliderOf>)>,
+    velocities_query: Query<&LinearVelocity>,
+) {
+    for (owner_entity, position, rotation, mut sensor, tnua_toggle) in query.iter_mut() {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled => continue,
+            TnuaToggle::SenseOnly => {}
+            TnuaToggle::Enabled => {}
+        }
+        let cast_origin = position.0 + rotation.0 * sensor.cast_origin;
+        let cast_direction = sensor.cast_direction;
+        // Cast from above the origin, so that the ray will enter the water volume through its
+        // surface.
+        let cast_start = cast_origin - sensor.cast_range * cast_direction.adjust_precision();
+
+        let mut closest_hit: Option<(Entity, Float)> = None;
+        spatial_query_pipeline.ray_hits_callback(
+            cast_start,
+            cast_direction,
+            2.0 * sensor.cast_range,
+            true,
+            &SpatialQueryFilter::from_excluded_entities([owner_entity]),
+            |ray_hit_data| {
+                if closest_hit.is_some_and(|(_, distance)| distance <= ray_hit_data.distance) {
+                    return true;
+                }
+                let Ok((is_water, collider_of)) = water_volumes_query.get(ray_hit_data.entity)
+                else {
+                    return true;
+                };
+                let water_entity = if is_water {
+                    ray_hit_data.entity
+                } else if let Some(collider_of) = collider_of.filter(|collider_of| {
+                    water_volumes_query
+                        .get(collider_of.body)
+                        .is_ok_and(|(is_water, _)| is_water)
+                }) {
+                    collider_of.body
+                } else {
+                    return true;
+                };
+                closest_hit = Some((water_entity, ray_hit_data.distance));
+                true
+            },
+        );
+
+        sensor.output = closest_hit.map(|(entity, distance)| TnuaWaterSensorOutput {
+            entity,
+            depth: sensor.cast_range - distance,
+            entity_linvel: velocities_query
+                .get(entity)
+                .map(|linvel| linvel.0.adjust_precision())
+                .unwrap_or(Vector3::ZERO),
+        });
+    }
+}
+
 fn update_obstacle_radars_system(
     spatial_query_pipeline: Res<SpatialQueryPipeline>,
     gravity: Res<Gravity>,
@@ -374,7 +479,7 @@ fn update_obstacle_radars_system(
         radar.pre_marking_update(
             radar_owner_entity,
             radar_position.0,
-            Dir3::new(gravity.0.f32()).unwrap_or(Dir3::Y),
+            Dir3::new(-gravity.0.f32()).unwrap_or(Dir3::Y),
         );
         spatial_query_pipeline.shape_intersections_callback(
             &Collider::cylinder(radar.radius, radar.height),
@@ -421,7 +526,7 @@ fn apply_motors_system(
         match tnua_toggle.copied().unwrap_or_default() {
             TnuaToggle::Disabled | TnuaToggle::SenseOnly => {
                 *external_force = Default::default();
-                return;
+                continue;
             }
             TnuaToggle::Enabled => {}
         }
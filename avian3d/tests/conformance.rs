@@ -0,0 +1,52 @@
+use avian3d::prelude::*;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy_tnua_avian3d::TnuaAvian3dPlugin;
+use bevy_tnua_physics_integration_layer::conformance::{TnuaConformanceHarness, CHARACTER_RADIUS};
+use bevy_tnua_physics_integration_layer::data_for_backends::TnuaWaterVolume;
+use bevy_tnua_physics_integration_layer::math::{AsF32, Vector3};
+use bevy_tnua_physics_integration_layer::tnua_conformance_tests;
+
+struct Harness;
+
+impl TnuaConformanceHarness for Harness {
+    fn schedule() -> InternedScheduleLabel {
+        PhysicsSchedule.intern()
+    }
+
+    fn add_backend(app: &mut App) {
+        app.add_plugins((
+            PhysicsPlugins::default(),
+            TnuaAvian3dPlugin::new(PhysicsSchedule),
+        ));
+    }
+
+    fn spawn_static_cuboid(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        let size = 2.0 * half_extents;
+        world
+            .spawn((
+                Transform::from_translation(position.f32()),
+                RigidBody::Static,
+                Collider::cuboid(size.x, size.y, size.z),
+            ))
+            .id()
+    }
+
+    fn spawn_water_volume(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        let entity = Self::spawn_static_cuboid(world, position, half_extents);
+        world.entity_mut(entity).insert((TnuaWaterVolume, Sensor));
+        entity
+    }
+
+    fn spawn_character(world: &mut World, position: Vector3) -> Entity {
+        world
+            .spawn((
+                Transform::from_translation(position.f32()),
+                RigidBody::Dynamic,
+                Collider::sphere(CHARACTER_RADIUS),
+            ))
+            .id()
+    }
+}
+
+tnua_conformance_tests!(Harness);
@@ -216,6 +216,7 @@ fn setup_player(mut commands: Commands) {
         falling_through: FallingThroughControlScheme::SingleFall,
         knockback: Default::default(),
         wall_slide: Default::default(),
+        wall_jump: Default::default(),
         climb: Default::default(),
         climb_speed: 10.0,
     });
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_tnua::builtins::{
     TnuaBuiltinClimb, TnuaBuiltinClimbState, TnuaBuiltinCrouch, TnuaBuiltinDash,
-    TnuaBuiltinJumpState, TnuaBuiltinKnockback, TnuaBuiltinWallSlide,
+    TnuaBuiltinJumpState, TnuaBuiltinKnockback, TnuaBuiltinWallJump, TnuaBuiltinWallSlide,
 };
 use bevy_tnua::math::{Float, Vector3};
 use bevy_tnua::prelude::*;
@@ -95,7 +95,7 @@ pub fn animate_platformer_character(
                 Some(TnuaBuiltinDash::NAME) => AnimationState::Dashing,
                 Some(TnuaBuiltinKnockback::NAME) => AnimationState::KnockedBack,
                 Some(TnuaBuiltinWallSlide::NAME) => AnimationState::WallSliding,
-                Some("walljump") => AnimationState::WallJumping,
+                Some(TnuaBuiltinWallJump::NAME) => AnimationState::WallJumping,
                 Some(TnuaBuiltinClimb::NAME) => {
                     let Some((_, action_state)) = controller.concrete_action::<TnuaBuiltinClimb>()
                     else {
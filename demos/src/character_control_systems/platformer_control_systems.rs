@@ -14,7 +14,7 @@ use bevy_tnua::radar_lens::{TnuaBlipSpatialRelation, TnuaRadarLens};
 use bevy_tnua::{
     builtins::{
         TnuaBuiltinClimb, TnuaBuiltinCrouch, TnuaBuiltinCrouchState, TnuaBuiltinDash,
-        TnuaBuiltinKnockback, TnuaBuiltinWallSlide,
+        TnuaBuiltinKnockback, TnuaBuiltinWallJump, TnuaBuiltinWallSlide,
     },
     control_helpers::TnuaBlipReuseAvoidance,
 };
@@ -469,17 +469,26 @@ pub fn apply_platformer_controls(
                     if controller.is_airborne().unwrap() {
                         let dot_direction = direction.dot(blip_direction.adjust_precision());
                         if dot_direction <= -0.7 {
-                            if let Some((best_entity, best_dot, best_direction)) =
-                                walljump_candidate.as_mut()
+                            if let Some((
+                                best_entity,
+                                best_dot,
+                                best_direction,
+                                best_contact_point,
+                            )) = walljump_candidate.as_mut()
                             {
                                 if *best_dot < dot_direction {
                                     *best_entity = blip.entity();
                                     *best_dot = dot_direction;
                                     *best_direction = blip_direction;
+                                    *best_contact_point = blip.closest_point().get();
                                 }
                             } else {
-                                walljump_candidate =
-                                    Some((blip.entity(), dot_direction, blip_direction));
+                                walljump_candidate = Some((
+                                    blip.entity(),
+                                    dot_direction,
+                                    blip_direction,
+                                    blip.closest_point().get(),
+                                ));
                             }
                         }
                         if dot_threshold < dot_direction
@@ -503,7 +512,9 @@ pub fn apply_platformer_controls(
             }
         }
         let walljump_candidate =
-            walljump_candidate.map(|(entity, _, blip_direction)| (entity, -blip_direction));
+            walljump_candidate.map(|(entity, _, blip_direction, contact_point)| {
+                (entity, -blip_direction, contact_point)
+            });
 
         if crouch {
             // Crouching is an action. We either feed it or we don't - other than that there is
@@ -517,21 +528,19 @@ pub fn apply_platformer_controls(
             let action_flow_status = controller.action_flow_status().clone();
             if matches!(
                 action_flow_status.ongoing(),
-                Some(TnuaBuiltinJump::NAME | "walljump")
+                Some(TnuaBuiltinJump::NAME | TnuaBuiltinWallJump::NAME)
             ) {
                 controller.prolong_action();
-            } else if let Some((_, walljump_direction)) = walljump_candidate {
-                controller.named_action(
-                    "walljump",
-                    TnuaBuiltinJump {
-                        vertical_displacement: Some(2.0 * walljump_direction.adjust_precision()),
-                        allow_in_air: true,
-                        takeoff_extra_gravity: 3.0 * config.jump.takeoff_extra_gravity,
-                        takeoff_above_velocity: 0.0,
-                        force_forward: Some(-walljump_direction),
-                        ..config.jump.clone()
-                    },
-                );
+            } else if let Some((wall_entity, walljump_direction, contact_point)) =
+                walljump_candidate
+            {
+                controller.action(TnuaBuiltinWallJump {
+                    wall_entity: Some(wall_entity),
+                    contact_point,
+                    normal: walljump_direction,
+                    force_forward: Some(-walljump_direction),
+                    ..config.wall_jump.clone()
+                });
             } else {
                 let current_action_name = controller.action_name();
                 controller.action(TnuaBuiltinJump {
@@ -602,6 +611,7 @@ pub struct CharacterMotionConfigForPlatformerDemo {
     pub falling_through: FallingThroughControlScheme,
     pub knockback: TnuaBuiltinKnockback,
     pub wall_slide: TnuaBuiltinWallSlide,
+    pub wall_jump: TnuaBuiltinWallJump,
     pub climb_speed: Float,
     pub climb: TnuaBuiltinClimb,
 }
@@ -637,6 +647,9 @@ impl UiTunable for CharacterMotionConfigForPlatformerDemo {
         ui.collapsing("Wall Slide:", |ui| {
             self.wall_slide.tune(ui);
         });
+        ui.collapsing("Wall Jump:", |ui| {
+            self.wall_jump.tune(ui);
+        });
         ui.collapsing("Climb", |ui| {
             ui.add(egui::Slider::new(&mut self.climb_speed, 0.0..=30.0).text("Climb Speed"));
             self.climb.tune(ui);
@@ -3,7 +3,7 @@ use std::ops::RangeInclusive;
 
 use bevy_tnua::builtins::{
     TnuaBuiltinClimb, TnuaBuiltinCrouch, TnuaBuiltinDash, TnuaBuiltinKnockback,
-    TnuaBuiltinWallSlide,
+    TnuaBuiltinWallJump, TnuaBuiltinWallSlide,
 };
 #[allow(unused_imports)]
 use bevy_tnua::math::{float_consts, Float};
@@ -282,6 +282,16 @@ impl UiTunable for TnuaBuiltinWallSlide {
     }
 }
 
+impl UiTunable for TnuaBuiltinWallJump {
+    #[cfg(feature = "egui")]
+    fn tune(&mut self, ui: &mut egui::Ui) {
+        ui.add(egui::Slider::new(&mut self.horizontal_speed, 0.0..=30.0).text("Horizontal Speed"));
+        ui.add(egui::Slider::new(&mut self.vertical_speed, 0.0..=30.0).text("Vertical Speed"));
+        ui.add(egui::Slider::new(&mut self.lockout_duration, 0.0..=2.0).text("Lockout Duration"));
+        ui.checkbox(&mut self.refresh_air_actions, "Refresh Air Actions");
+    }
+}
+
 impl UiTunable for TnuaBuiltinClimb {
     #[cfg(feature = "egui")]
     fn tune(&mut self, ui: &mut egui::Ui) {
@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+use bevy_tnua_physics_integration_layer::math::{AdjustPrecision, Float, Quaternion, Vector3};
+use bevy_tnua_physics_integration_layer::spatial_ext::TnuaPointProjectionResult;
+
+/// An analytic collider that the headless backend can cast against.
+///
+/// The collider is positioned and rotated by the entity's [`Transform`] (scale is ignored). Only
+/// the entity's own `Transform` is used - colliders inside hierarchies are not supported.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[require(Transform)]
+pub enum TnuaHeadlessCollider {
+    /// An infinite solid that fills everything behind a plane that goes through the entity's
+    /// translation.
+    ///
+    /// The `normal` is in the entity's local coordinates, and points away from the solid side.
+    HalfSpace { normal: Dir3 },
+    /// A box centered on the entity's translation.
+    Cuboid { half_extents: Vector3 },
+    /// A ball centered on the entity's translation.
+    Sphere { radius: Float },
+}
+
+impl TnuaHeadlessCollider {
+    /// Cast a ray on the collider, returning the time-of-impact and the normal.
+    ///
+    /// Like the physics engines, the time-of-impact is measured in multiples of `direction`, and a
+    /// ray that starts inside the collider hits it immediately, with a zero normal.
+    pub fn cast_ray(
+        &self,
+        transform: &Transform,
+        origin: Vector3,
+        direction: Vector3,
+        max_time_of_impact: Float,
+    ) -> Option<(Float, Vector3)> {
+        let (position, rotation) = position_and_rotation(transform);
+        let origin = rotation.inverse() * (origin - position);
+        let direction = rotation.inverse() * direction;
+
+        let (time_of_impact, normal) = match self {
+            TnuaHeadlessCollider::HalfSpace { normal } => {
+                let normal = normal.adjust_precision();
+                let distance = origin.dot(normal);
+                if distance <= 0.0 {
+                    return Some((0.0, Vector3::ZERO));
+                }
+                let approach_speed = direction.dot(normal);
+                if 0.0 <= approach_speed {
+                    return None;
+                }
+                (-distance / approach_speed, normal)
+            }
+            TnuaHeadlessCollider::Cuboid { half_extents } => {
+                if origin.abs().cmple(*half_extents).all() {
+                    return Some((0.0, Vector3::ZERO));
+                }
+                let mut entry = Float::NEG_INFINITY;
+                let mut exit = Float::INFINITY;
+                let mut normal = Vector3::ZERO;
+                for axis in 0..3 {
+                    if direction[axis] == 0.0 {
+                        if half_extents[axis] < origin[axis].abs() {
+                            return None;
+                        }
+                        continue;
+                    }
+                    let to_min = (-half_extents[axis] - origin[axis]) / direction[axis];
+                    let to_max = (half_extents[axis] - origin[axis]) / direction[axis];
+                    let (near, far) = if to_min < to_max {
+                        (to_min, to_max)
+                    } else {
+                        (to_max, to_min)
+                    };
+                    if entry < near {
+                        entry = near;
+                        normal = Vector3::ZERO;
+                        normal[axis] = -direction[axis].signum();
+                    }
+                    exit = exit.min(far);
+                }
+                if exit < entry || entry < 0.0 {
+                    return None;
+                }
+                (entry, normal)
+            }
+            TnuaHeadlessCollider::Sphere { radius } => {
+                let offset_from_surface = origin.length_squared() - radius.powi(2);
+                if offset_from_surface <= 0.0 {
+                    return Some((0.0, Vector3::ZERO));
+                }
+                let a = direction.length_squared();
+                let half_b = origin.dot(direction);
+                let discriminant = half_b.powi(2) - a * offset_from_surface;
+                if a == 0.0 || discriminant < 0.0 {
+                    return None;
+                }
+                let time_of_impact = (-half_b - discriminant.sqrt()) / a;
+                if time_of_impact < 0.0 {
+                    return None;
+                }
+                (
+                    time_of_impact,
+                    (origin + time_of_impact * direction).normalize_or_zero(),
+                )
+            }
+        };
+        if max_time_of_impact < time_of_impact {
+            return None;
+        }
+        Some((time_of_impact, rotation * normal))
+    }
+
+    /// Return the point on the collider that's closest to some external point.
+    ///
+    /// If the point is inside the collider and `solid` is `true`, the point itself is returned.
+    pub fn project_point(
+        &self,
+        transform: &Transform,
+        point: Vector3,
+        solid: bool,
+    ) -> TnuaPointProjectionResult {
+        let (position, rotation) = position_and_rotation(transform);
+        let local_point = rotation.inverse() * (point - position);
+
+        let (projected, is_inside) = match self {
+            TnuaHeadlessCollider::HalfSpace { normal } => {
+                let normal = normal.adjust_precision();
+                let distance = local_point.dot(normal);
+                (local_point - distance * normal, distance <= 0.0)
+            }
+            TnuaHeadlessCollider::Cuboid { half_extents } => {
+                let clamped = local_point.clamp(-*half_extents, *half_extents);
+                if clamped != local_point {
+                    (clamped, false)
+                } else {
+                    let depths = *half_extents - local_point.abs();
+                    let axis = if depths.x <= depths.y && depths.x <= depths.z {
+                        0
+                    } else if depths.y <= depths.z {
+                        1
+                    } else {
+                        2
+                    };
+                    let mut projected = local_point;
+                    projected[axis] = half_extents[axis] * local_point[axis].signum();
+                    (projected, true)
+                }
+            }
+            TnuaHeadlessCollider::Sphere { radius } => (
+                local_point.try_normalize().unwrap_or(Vector3::Y) * *radius,
+                local_point.length() <= *radius,
+            ),
+        };
+
+        if is_inside {
+            if solid {
+                TnuaPointProjectionResult::Inside(point)
+            } else {
+                TnuaPointProjectionResult::Inside(position + rotation * projected)
+            }
+        } else {
+            TnuaPointProjectionResult::Outside(position + rotation * projected)
+        }
+    }
+}
+
+pub(crate) fn position_and_rotation(transform: &Transform) -> (Vector3, Quaternion) {
+    (
+        transform.translation.adjust_precision(),
+        transform.rotation.adjust_precision(),
+    )
+}
@@ -0,0 +1,403 @@
+//! # Headless Mock Physics Backend for bevy-tnua
+//!
+//! A physics backend that does not use any physics engine. It is meant for testing Tnua
+//! controlled characters (and custom [bases](https://docs.rs/bevy-tnua/latest/bevy_tnua/trait.TnuaBasis.html)
+//! and [actions](https://docs.rs/bevy-tnua/latest/bevy_tnua/trait.TnuaAction.html)) quickly and
+//! deterministically, without having to set up a full physics world.
+//!
+//! In addition to the instruction in bevy-tnua's documentation:
+//!
+//! * Add [`TnuaHeadlessPlugin`] to the Bevy app.
+//! * Add [`TnuaHeadlessRigidBody`] to the character entity (and to any other entity that should
+//!   move - like moving platforms).
+//! * Add [`TnuaHeadlessCollider`] to the entities that the character should be able to stand on
+//!   (or detect with [`TnuaObstacleRadar`]).
+//!
+//! This backend is not a physics engine:
+//!
+//! * Bodies are integrated with simple Euler steps, using [`Time`]'s delta. For deterministic
+//!   tests, use [`TimeUpdateStrategy::ManualDuration`](bevy::time::TimeUpdateStrategy).
+//! * There is no collision response - bodies pass through colliders. A Tnua character floats
+//!   above the ground, so it does not need one.
+//! * The proximity sensor always casts a ray. There are no sensor shapes.
+//! * Positions and rotations are taken from the [`Transform`] of the entities, ignoring
+//!   hierarchies (except for [`TnuaSubservientSensor`], which is positioned relative to its
+//!   owner).
+mod colliders;
+mod spatial_ext;
+
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy_tnua_physics_integration_layer::math::{AdjustPrecision, AsF32, Float, Vector3};
+pub use colliders::TnuaHeadlessCollider;
+pub use spatial_ext::TnuaSpatialExtHeadless;
+
+use bevy_tnua_physics_integration_layer::data_for_backends::TnuaGhostSensor;
+use bevy_tnua_physics_integration_layer::data_for_backends::TnuaGravity;
+use bevy_tnua_physics_integration_layer::data_for_backends::TnuaToggle;
+use bevy_tnua_physics_integration_layer::data_for_backends::{TnuaGhostPlatform, TnuaNotPlatform};
+use bevy_tnua_physics_integration_layer::data_for_backends::{
+    TnuaMotor, TnuaProximitySensor, TnuaProximitySensorOutput, TnuaRigidBodyTracker,
+};
+use bevy_tnua_physics_integration_layer::data_for_backends::{
+    TnuaWaterSensor, TnuaWaterSensorOutput, TnuaWaterVolume,
+};
+use bevy_tnua_physics_integration_layer::obstacle_radar::TnuaObstacleRadar;
+use bevy_tnua_physics_integration_layer::subservient_sensors::TnuaSubservientSensor;
+use bevy_tnua_physics_integration_layer::TnuaPipelineStages;
+
+use self::colliders::position_and_rotation;
+
+/// Add this plugin to use the headless mock backend.
+///
+/// This plugin should be used in addition to `TnuaControllerPlugin`, and in the same schedule.
+pub struct TnuaHeadlessPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl TnuaHeadlessPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for TnuaHeadlessPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for TnuaHeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TnuaHeadlessGravity>();
+        app.register_type::<TnuaHeadlessGravity>();
+        app.register_type::<TnuaHeadlessRigidBody>();
+        app.register_type::<TnuaHeadlessCollider>();
+        app.add_systems(
+            self.schedule,
+            (
+                update_rigid_body_trackers_system,
+                update_proximity_sensors_system,
+                update_water_sensors_system,
+                update_obstacle_radars_system,
+            )
+                .in_set(TnuaPipelineStages::Sensors),
+        );
+        app.add_systems(
+            self.schedule,
+            (apply_motors_system, integrate_rigid_bodies_system)
+                .chain()
+                .in_set(TnuaPipelineStages::Motors),
+        );
+    }
+}
+
+/// The gravity applied to all the [`TnuaHeadlessRigidBody`] entities that don't have
+/// [`TnuaGravity`].
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct TnuaHeadlessGravity(pub Vector3);
+
+impl Default for TnuaHeadlessGravity {
+    fn default() -> Self {
+        Self(Vector3::NEG_Y * 9.81)
+    }
+}
+
+/// A body that moves according to its velocity, gravity, and the [`TnuaMotor`] (if it has one).
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct TnuaHeadlessRigidBody {
+    pub linvel: Vector3,
+    /// Angular velocity as the rotation axis multiplied by the rotation speed in radians per
+    /// second.
+    pub angvel: Vector3,
+    /// Multiplier for the gravity. Set to 0 for kinematic bodies, like moving platforms.
+    pub gravity_scale: Float,
+}
+
+impl Default for TnuaHeadlessRigidBody {
+    fn default() -> Self {
+        Self {
+            linvel: Vector3::ZERO,
+            angvel: Vector3::ZERO,
+            gravity_scale: 1.0,
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_rigid_body_trackers_system(
+    gravity: Res<TnuaHeadlessGravity>,
+    mut query: Query<(
+        &Transform,
+        &TnuaHeadlessRigidBody,
+        &mut TnuaRigidBodyTracker,
+        Option<&TnuaToggle>,
+        Option<&TnuaGravity>,
+    )>,
+) {
+    for (transform, rigid_body, mut tracker, tnua_toggle, tnua_gravity) in query.iter_mut() {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled => continue,
+            TnuaToggle::SenseOnly => {}
+            TnuaToggle::Enabled => {}
+        }
+        let (translation, rotation) = position_and_rotation(transform);
+        *tracker = TnuaRigidBodyTracker {
+            translation,
+            rotation,
+            velocity: rigid_body.linvel,
+            angvel: rigid_body.angvel,
+            // The gravity the body is actually integrated with.
+            gravity: rigid_body.gravity_scale * tnua_gravity.map(|g| g.0).unwrap_or(gravity.0),
+        };
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_proximity_sensors_system(
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &mut TnuaProximitySensor,
+        Option<&mut TnuaGhostSensor>,
+        Option<&TnuaSubservientSensor>,
+        Option<&TnuaToggle>,
+    )>,
+    owners_query: Query<&Transform>,
+    colliders_query: Query<(
+        Entity,
+        &TnuaHeadlessCollider,
+        &Transform,
+        Option<&TnuaHeadlessRigidBody>,
+        Has<TnuaGhostPlatform>,
+        Has<TnuaNotPlatform>,
+        Has<TnuaWaterVolume>,
+    )>,
+) {
+    for (owner_entity, transform, mut sensor, mut ghost_sensor, subservient, tnua_toggle) in
+        query.iter_mut()
+    {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled => continue,
+            TnuaToggle::SenseOnly => {}
+            TnuaToggle::Enabled => {}
+        }
+
+        let (owner_entity, transform) = if let Some(subservient) = subservient {
+            let Ok(owner_transform) = owners_query.get(subservient.owner_entity) else {
+                continue;
+            };
+            (
+                subservient.owner_entity,
+                owner_transform.mul_transform(*transform),
+            )
+        } else {
+            (owner_entity, *transform)
+        };
+
+        let cast_origin = transform
+            .transform_point(sensor.cast_origin.f32())
+            .adjust_precision();
+        let cast_direction = sensor.cast_direction;
+
+        let mut hits = colliders_query
+            .iter()
+            .filter(|(entity, .., is_water)| *entity != owner_entity && !is_water)
+            .filter_map(
+                |(
+                    entity,
+                    collider,
+                    collider_transform,
+                    rigid_body,
+                    is_ghost,
+                    is_not_platform,
+                    _,
+                )| {
+                    let (proximity, normal) = collider.cast_ray(
+                        collider_transform,
+                        cast_origin,
+                        cast_direction.adjust_precision(),
+                        sensor.cast_range,
+                    )?;
+                    let (entity_linvel, entity_angvel) = if let Some(rigid_body) = rigid_body {
+                        let relative_point = cast_origin
+                            + proximity * cast_direction.adjust_precision()
+                            - collider_transform.translation.adjust_precision();
+                        (
+                            rigid_body.linvel + rigid_body.angvel.cross(relative_point),
+                            rigid_body.angvel,
+                        )
+                    } else {
+                        (Vector3::ZERO, Vector3::ZERO)
+                    };
+                    let sensor_output = TnuaProximitySensorOutput {
+                        entity,
+                        proximity,
+                        normal: Dir3::new(normal.f32()).unwrap_or(-cast_direction),
+                        entity_linvel,
+                        entity_angvel,
+                    };
+                    Some((sensor_output, is_ghost, is_not_platform))
+                },
+            )
+            .collect::<Vec<_>>();
+        hits.sort_by(|(a, ..), (b, ..)| a.proximity.total_cmp(&b.proximity));
+
+        if let Some(ghost_sensor) = ghost_sensor.as_mut() {
+            ghost_sensor.0.clear();
+        }
+        let mut final_sensor_output = None;
+        for (sensor_output, is_ghost, is_not_platform) in hits {
+            if is_not_platform {
+                continue;
+            }
+            if is_ghost {
+                if let Some(ghost_sensor) = ghost_sensor.as_mut() {
+                    ghost_sensor.0.push(sensor_output);
+                }
+                continue;
+            }
+            final_sensor_output = Some(sensor_output);
+            break;
+        }
+        sensor.output = final_sensor_output;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_water_sensors_system(
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &mut TnuaWaterSensor,
+        Option<&TnuaToggle>,
+    )>,
+    water_volumes_query: Query<
+        (
+            Entity,
+            &TnuaHeadlessCollider,
+            &Transform,
+            Option<&TnuaHeadlessRigidBody>,
+        ),
+        With<TnuaWaterVolume>,
+    >,
+) {
+    for (owner_entity, transform, mut sensor, tnua_toggle) in query.iter_mut() {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled => continue,
+            TnuaToggle::SenseOnly => {}
+            TnuaToggle::Enabled => {}
+        }
+        let cast_origin = transform
+            .transform_point(sensor.cast_origin.f32())
+            .adjust_precision();
+        let cast_direction = sensor.cast_direction.adjust_precision();
+        // Cast from above the origin, so that the ray will enter the water volume through its
+        // surface.
+        let cast_start = cast_origin - sensor.cast_range * cast_direction;
+
+        sensor.output = water_volumes_query
+            .iter()
+            .filter(|(entity, ..)| *entity != owner_entity)
+            .filter_map(|(entity, collider, collider_transform, rigid_body)| {
+                let (time_of_impact, _) = collider.cast_ray(
+                    collider_transform,
+                    cast_start,
+                    cast_direction,
+                    2.0 * sensor.cast_range,
+                )?;
+                Some(TnuaWaterSensorOutput {
+                    entity,
+                    depth: sensor.cast_range - time_of_impact,
+                    entity_linvel: rigid_body.map_or(Vector3::ZERO, |rigid_body| rigid_body.linvel),
+                })
+            })
+            .max_by(|a, b| a.depth.total_cmp(&b.depth));
+    }
+}
+
+fn update_obstacle_radars_system(
+    gravity: Res<TnuaHeadlessGravity>,
+    mut radars_query: Query<(
+        Entity,
+        &mut TnuaObstacleRadar,
+        &Transform,
+        Option<&TnuaGravity>,
+    )>,
+    colliders_query: Query<(Entity, &TnuaHeadlessCollider, &Transform)>,
+) {
+    for (radar_owner_entity, mut radar, radar_transform, tnua_gravity) in radars_query.iter_mut() {
+        let radar_position = radar_transform.translation.adjust_precision();
+        let up_direction =
+            Dir3::new(-tnua_gravity.map(|g| g.0).unwrap_or(gravity.0).f32()).unwrap_or(Dir3::Y);
+        radar.pre_marking_update(radar_owner_entity, radar_position, up_direction);
+        let up = up_direction.adjust_precision();
+        for (obstacle_entity, collider, collider_transform) in colliders_query.iter() {
+            if obstacle_entity == radar_owner_entity {
+                continue;
+            }
+            // Only an approximation of a cylinder intersection check - but it is exact for half
+            // spaces and good enough for the rest of the primitives, which are convex.
+            let closest_point = collider.project_point(collider_transform, radar_position, true);
+            let offset = closest_point.get() - radar_position;
+            let vertical_offset = offset.dot(up);
+            if closest_point.inside().is_some()
+                || (vertical_offset.abs() <= 0.5 * radar.height
+                    && offset.reject_from(up).length() <= radar.radius)
+            {
+                radar.mark_seen(obstacle_entity);
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_motors_system(
+    time: Res<Time>,
+    mut query: Query<(&TnuaMotor, &mut TnuaHeadlessRigidBody, Option<&TnuaToggle>)>,
+) {
+    let frame_duration = time.delta().as_secs_f64() as Float;
+    for (motor, mut rigid_body, tnua_toggle) in query.iter_mut() {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled | TnuaToggle::SenseOnly => continue,
+            TnuaToggle::Enabled => {}
+        }
+        if motor.lin.boost.is_finite() {
+            rigid_body.linvel += motor.lin.boost;
+        }
+        if motor.lin.acceleration.is_finite() {
+            rigid_body.linvel += frame_duration * motor.lin.acceleration;
+        }
+        if motor.ang.boost.is_finite() {
+            rigid_body.angvel += motor.ang.boost;
+        }
+        if motor.ang.acceleration.is_finite() {
+            rigid_body.angvel += frame_duration * motor.ang.acceleration;
+        }
+    }
+}
+
+fn integrate_rigid_bodies_system(
+    time: Res<Time>,
+    gravity: Res<TnuaHeadlessGravity>,
+    mut query: Query<(
+        &mut TnuaHeadlessRigidBody,
+        &mut Transform,
+        Option<&TnuaGravity>,
+    )>,
+) {
+    let frame_duration = time.delta().as_secs_f64() as Float;
+    for (mut rigid_body, mut transform, tnua_gravity) in query.iter_mut() {
+        let gravity = tnua_gravity.map(|g| g.0).unwrap_or(gravity.0);
+        let gravity_scale = rigid_body.gravity_scale;
+        rigid_body.linvel += frame_duration * gravity_scale * gravity;
+        transform.translation += (frame_duration * rigid_body.linvel).f32();
+        let rotation_change = Quat::from_scaled_axis((frame_duration * rigid_body.angvel).f32());
+        transform.rotation = (rotation_change * transform.rotation).normalize();
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_tnua_physics_integration_layer::{
+    math::{Float, Vector3},
+    spatial_ext::{TnuaPointProjectionResult, TnuaSpatialExt},
+};
+
+use crate::TnuaHeadlessCollider;
+
+#[derive(SystemParam)]
+pub struct TnuaSpatialExtHeadless<'w, 's> {
+    colliders_query: Query<'w, 's, (&'static TnuaHeadlessCollider, &'static Transform)>,
+}
+
+impl TnuaSpatialExt for TnuaSpatialExtHeadless<'_, '_> {
+    type ColliderData<'a>
+        = (&'a TnuaHeadlessCollider, &'a Transform)
+    where
+        Self: 'a;
+
+    fn fetch_collider_data(&self, entity: Entity) -> Option<Self::ColliderData<'_>> {
+        self.colliders_query.get(entity).ok()
+    }
+
+    fn project_point<'a>(
+        &'a self,
+        point: Vector3,
+        solid: bool,
+        collider_data: &Self::ColliderData<'a>,
+    ) -> TnuaPointProjectionResult {
+        let (collider, transform) = collider_data;
+        collider.project_point(transform, point, solid)
+    }
+
+    fn cast_ray<'a>(
+        &'a self,
+        origin: Vector3,
+        direction: Vector3,
+        max_time_of_impact: Float,
+        collider_data: &Self::ColliderData<'a>,
+    ) -> Option<(Float, Vector3)> {
+        let (collider, transform) = collider_data;
+        collider.cast_ray(transform, origin, direction, max_time_of_impact)
+    }
+
+    fn can_interact(&self, _entity1: Entity, _entity2: Entity) -> bool {
+        // There are no collision groups or sensors in the headless backend, so everything can
+        // interact with everything.
+        true
+    }
+}
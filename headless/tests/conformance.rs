@@ -0,0 +1,41 @@
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy_tnua_headless::{TnuaHeadlessCollider, TnuaHeadlessPlugin, TnuaHeadlessRigidBody};
+use bevy_tnua_physics_integration_layer::conformance::{TnuaConformanceHarness, CHARACTER_RADIUS};
+use bevy_tnua_physics_integration_layer::math::{AsF32, Vector3};
+use bevy_tnua_physics_integration_layer::tnua_conformance_tests;
+
+struct Harness;
+
+impl TnuaConformanceHarness for Harness {
+    fn schedule() -> InternedScheduleLabel {
+        Update.intern()
+    }
+
+    fn add_backend(app: &mut App) {
+        app.add_plugins(TnuaHeadlessPlugin::default());
+    }
+
+    fn spawn_static_cuboid(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        world
+            .spawn((
+                Transform::from_translation(position.f32()),
+                TnuaHeadlessCollider::Cuboid { half_extents },
+            ))
+            .id()
+    }
+
+    fn spawn_character(world: &mut World, position: Vector3) -> Entity {
+        world
+            .spawn((
+                Transform::from_translation(position.f32()),
+                TnuaHeadlessRigidBody::default(),
+                TnuaHeadlessCollider::Sphere {
+                    radius: CHARACTER_RADIUS,
+                },
+            ))
+            .id()
+    }
+}
+
+tnua_conformance_tests!(Harness);
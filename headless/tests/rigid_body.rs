@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_tnua_headless::{TnuaHeadlessGravity, TnuaHeadlessPlugin, TnuaHeadlessRigidBody};
+use bevy_tnua_physics_integration_layer::data_for_backends::{TnuaGravity, TnuaRigidBodyTracker};
+use bevy_tnua_physics_integration_layer::math::{Float, Vector3};
+
+const FRAMES_PER_SECOND: u32 = 60;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(
+        Duration::from_secs(1) / FRAMES_PER_SECOND,
+    ));
+    app.add_plugins(TnuaHeadlessPlugin::default());
+    app
+}
+
+/// Spawn a free falling body, and return the gravity its tracker reports together with the
+/// gravity it was actually integrated with.
+fn fall(gravity_scale: Float, tnua_gravity: Option<TnuaGravity>) -> (Vector3, Vector3) {
+    let mut app = app();
+    let mut entity = app.world_mut().spawn((
+        Transform::default(),
+        TnuaHeadlessRigidBody {
+            gravity_scale,
+            ..Default::default()
+        },
+        TnuaRigidBodyTracker::default(),
+    ));
+    if let Some(tnua_gravity) = tnua_gravity {
+        entity.insert(tnua_gravity);
+    }
+    let entity = entity.id();
+    // The first update has no time delta.
+    app.update();
+    app.update();
+    let velocity_before = app
+        .world()
+        .get::<TnuaHeadlessRigidBody>(entity)
+        .unwrap()
+        .linvel;
+    app.update();
+    let velocity_after = app
+        .world()
+        .get::<TnuaHeadlessRigidBody>(entity)
+        .unwrap()
+        .linvel;
+    let tracker = app.world().get::<TnuaRigidBodyTracker>(entity).unwrap();
+    (
+        tracker.gravity,
+        (velocity_after - velocity_before) * FRAMES_PER_SECOND as Float,
+    )
+}
+
+fn assert_close(actual: Vector3, expected: Vector3) {
+    assert!(
+        actual.distance(expected) < 1e-3,
+        "{actual} instead of {expected}"
+    );
+}
+
+#[test]
+fn tracker_reports_scaled_gravity() {
+    let gravity = TnuaHeadlessGravity::default().0;
+    for gravity_scale in [1.0, 0.5, 2.0, 0.0] {
+        let (tracked, integrated) = fall(gravity_scale, None);
+        assert_close(tracked, gravity_scale * gravity);
+        assert_close(integrated, tracked);
+    }
+}
+
+#[test]
+fn tracker_reports_scaled_tnua_gravity() {
+    let tnua_gravity = Vector3::new(3.0, 0.0, -4.0);
+    let (tracked, integrated) = fall(0.5, Some(TnuaGravity(tnua_gravity)));
+    assert_close(tracked, 0.5 * tnua_gravity);
+    assert_close(integrated, tracked);
+}
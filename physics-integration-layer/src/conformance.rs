@@ -0,0 +1,725 @@
+//! A conformance test kit for physics integration crates.
+//!
+//! The rules listed in the [crate documentation](crate) are checked by the functions of this
+//! module, each of which builds an app with the backend under test, sets up a small scene, and
+//! panics if the backend does not follow the rule.
+//!
+//! To run the kit, implement [`TnuaConformanceHarness`] for the backend (in the backend crate's
+//! integration tests) and invoke [`tnua_conformance_tests!`](crate::tnua_conformance_tests) to
+//! generate a `#[test]` for each rule:
+//!
+//! ```ignore
+//! struct Harness;
+//!
+//! impl TnuaConformanceHarness for Harness {
+//!     // ...
+//! }
+//!
+//! tnua_conformance_tests!(Harness);
+//! ```
+//!
+//! The scenes are built around the origin, with gravity pointing down the Y axis and all the
+//! interesting offsets on the X and Y axes - so that 2D backends can just drop the Z coordinate.
+//!
+//! The kit does not use `TnuaControllerPlugin` - it only adds the components the backend is
+//! supposed to update, and sets the [`TnuaMotor`] directly.
+use bevy::ecs::schedule::InternedScheduleLabel;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+use crate::data_for_backends::{
+    TnuaGhostPlatform, TnuaGhostSensor, TnuaGravity, TnuaMotor, TnuaNotPlatform,
+    TnuaProximitySensor, TnuaRigidBodyTracker, TnuaToggle, TnuaWaterSensor, TnuaWaterVolume,
+};
+use crate::math::{AdjustPrecision, Float, Vector3};
+use crate::obstacle_radar::TnuaObstacleRadar;
+use crate::subservient_sensors::TnuaSubservientSensor;
+use crate::{TnuaPipelineStages, TnuaSystemSet};
+
+/// The number of frames the conformance tests run per (simulated) second.
+pub const FRAMES_PER_SECOND: u32 = 60;
+
+/// The fixed duration of each frame in the conformance tests.
+pub const FRAME_DURATION: Float = 1.0 / FRAMES_PER_SECOND as Float;
+
+/// The radius of the characters spawned by [`TnuaConformanceHarness::spawn_character`].
+pub const CHARACTER_RADIUS: Float = 0.5;
+
+const POSITION_TOLERANCE: Float = 0.1;
+const VELOCITY_TOLERANCE: Float = 0.05;
+
+/// Connects the conformance kit to a physics backend.
+pub trait TnuaConformanceHarness {
+    /// The schedule in which the integration plugin runs its systems.
+    fn schedule() -> InternedScheduleLabel;
+
+    /// Add the physics engine and the integration plugin to the app.
+    ///
+    /// The app already has [`MinimalPlugins`] and [`TransformPlugin`], and its time is advanced
+    /// by [`FRAME_DURATION`] on each update (for both the [`Virtual`] and the [`Fixed`] clocks).
+    fn add_backend(app: &mut App);
+
+    /// Spawn a static, axis-aligned box.
+    fn spawn_static_cuboid(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity;
+
+    /// Spawn a static, axis-aligned box that acts as a ghost platform.
+    ///
+    /// The default implementation adds [`TnuaGhostPlatform`] to a regular static cuboid. Backends
+    /// that require more than that for ghost platforms (e.g. solver groups) should override it.
+    fn spawn_ghost_platform(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        let entity = Self::spawn_static_cuboid(world, position, half_extents);
+        world.entity_mut(entity).insert(TnuaGhostPlatform);
+        entity
+    }
+
+    /// Spawn a static, axis-aligned box that acts as a water volume.
+    ///
+    /// The default implementation adds [`TnuaWaterVolume`] to a regular static cuboid. Backends
+    /// where that would make the box solid (e.g. ones that need it to be a sensor collider) should
+    /// override it.
+    fn spawn_water_volume(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        let entity = Self::spawn_static_cuboid(world, position, half_extents);
+        world.entity_mut(entity).insert(TnuaWaterVolume);
+        entity
+    }
+
+    /// Spawn a dynamic rigid body with a ball collider of [`CHARACTER_RADIUS`].
+    ///
+    /// The kit will add the Tnua components to it.
+    fn spawn_character(world: &mut World, position: Vector3) -> Entity;
+}
+
+/// Generate a `#[test]` function for each rule of the conformance kit.
+///
+/// Invoke with the type that implements [`TnuaConformanceHarness`](crate::conformance::TnuaConformanceHarness)
+/// for the backend. To only generate some of the tests, list them after a colon:
+///
+/// ```ignore
+/// tnua_conformance_tests!(Harness: sensor_detects_ground, sensor_ignores_owner_collider);
+/// ```
+#[macro_export]
+macro_rules! tnua_conformance_tests {
+    ($harness:ty: $($rule:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $rule() {
+                $crate::conformance::$rule::<$harness>();
+            }
+        )*
+    };
+    ($harness:ty) => {
+        $crate::tnua_conformance_tests!(
+            $harness:
+            tracker_reports_kinematic_state,
+            tnua_gravity_replaces_global_gravity,
+            sensor_detects_ground,
+            sensor_ignores_owner_collider,
+            sensor_reports_nothing_out_of_range,
+            sensor_skips_not_platform,
+            ghost_hits_collected_before_tangible_hit,
+            subservient_sensor_ignores_owner_collider,
+            water_sensor_measures_depth,
+            radar_marks_nearby_obstacles,
+            radar_forgets_removed_obstacles,
+            motor_applies_boost,
+            motor_applies_acceleration,
+            toggle_disabled_skips_sensors_and_motors,
+            toggle_sense_only_skips_motors,
+        );
+    };
+}
+
+fn create_app<H: TnuaConformanceHarness>() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin));
+    let frame_duration = Duration::from_secs(1) / FRAMES_PER_SECOND;
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(frame_duration));
+    app.insert_resource(Time::<Fixed>::from_duration(frame_duration));
+    H::add_backend(&mut app);
+    app.configure_sets(
+        H::schedule(),
+        (
+            TnuaPipelineStages::Sensors,
+            TnuaPipelineStages::SubservientSensors,
+            TnuaPipelineStages::Logic,
+            TnuaPipelineStages::Motors,
+        )
+            .chain()
+            .in_set(TnuaSystemSet),
+    );
+    // Normally done by `App::run`, which the kit does not use.
+    app.finish();
+    app.cleanup();
+    app
+}
+
+fn spawn_floor<H: TnuaConformanceHarness>(app: &mut App) -> Entity {
+    // Top surface is at Y=0
+    H::spawn_static_cuboid(
+        app.world_mut(),
+        Vector3::new(0.0, -0.5, 0.0),
+        Vector3::new(20.0, 0.5, 20.0),
+    )
+}
+
+fn spawn_character<H: TnuaConformanceHarness>(app: &mut App, position: Vector3) -> Entity {
+    let entity = H::spawn_character(app.world_mut(), position);
+    app.world_mut().entity_mut(entity).insert((
+        TnuaRigidBodyTracker::default(),
+        TnuaProximitySensor {
+            cast_origin: Vector3::ZERO,
+            cast_direction: Dir3::NEG_Y,
+            cast_range: 5.0,
+            output: None,
+        },
+        TnuaGhostSensor::default(),
+        TnuaMotor::default(),
+    ));
+    entity
+}
+
+fn run_frames(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+fn get<C: Component>(app: &App, entity: Entity) -> &C {
+    app.world()
+        .get::<C>(entity)
+        .unwrap_or_else(|| panic!("Entity {entity} has no {}", std::any::type_name::<C>()))
+}
+
+fn get_mut<C: Component<Mutability = bevy::ecs::component::Mutable>>(
+    app: &mut App,
+    entity: Entity,
+) -> Mut<'_, C> {
+    app.world_mut()
+        .get_mut::<C>(entity)
+        .unwrap_or_else(|| panic!("Entity {entity} has no {}", std::any::type_name::<C>()))
+}
+
+fn assert_close(what: &str, actual: Float, expected: Float, tolerance: Float) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{what} should be {expected} (+-{tolerance}), but it is {actual}"
+    );
+}
+
+/// The [`TnuaRigidBodyTracker`] must be updated with the position, velocity and gravity of the
+/// rigid body.
+pub fn tracker_reports_kinematic_state<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let character = spawn_character::<H>(&mut app, Vector3::new(1.0, 2.0, 0.0));
+    run_frames(&mut app, 5);
+
+    let tracker = get::<TnuaRigidBodyTracker>(&app, character);
+    assert_close("Tracked X", tracker.translation.x, 1.0, POSITION_TOLERANCE);
+    assert_close("Tracked Y", tracker.translation.y, 2.0, POSITION_TOLERANCE);
+    assert!(
+        tracker.velocity.y < 0.0,
+        "The character should be falling, but its tracked velocity is {}",
+        tracker.velocity
+    );
+    assert_close(
+        "Tracked gravity X",
+        tracker.gravity.x,
+        0.0,
+        VELOCITY_TOLERANCE,
+    );
+    assert!(
+        tracker.gravity.y < 0.0,
+        "The tracked gravity should point down, but it is {}",
+        tracker.gravity
+    );
+}
+
+/// [`TnuaGravity`] must be reported by the [`TnuaRigidBodyTracker`] and must be applied instead of
+/// the global gravity.
+pub fn tnua_gravity_replaces_global_gravity<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    let gravity = Vector3::new(5.0, 0.0, 0.0);
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaGravity(gravity));
+    run_frames(&mut app, 10);
+
+    let tracker = get::<TnuaRigidBodyTracker>(&app, character);
+    assert_close("Tracked gravity X", tracker.gravity.x, gravity.x, 0.0);
+    assert_close("Tracked gravity Y", tracker.gravity.y, gravity.y, 0.0);
+    assert!(
+        0.0 < tracker.velocity.x,
+        "The character should be pulled by `TnuaGravity`, but its tracked velocity is {}",
+        tracker.velocity
+    );
+    assert_close(
+        "Tracked velocity Y (global gravity should not apply)",
+        tracker.velocity.y,
+        0.0,
+        VELOCITY_TOLERANCE,
+    );
+}
+
+/// The [`TnuaProximitySensor`] must detect the collider below the character, with the distance
+/// to it and its normal.
+pub fn sensor_detects_ground<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    run_frames(&mut app, 5);
+
+    let sensor = get::<TnuaProximitySensor>(&app, character);
+    let output = sensor
+        .output
+        .as_ref()
+        .expect("The sensor should detect the floor");
+    assert_eq!(output.entity, floor, "The sensor should detect the floor");
+    assert_close("Proximity", output.proximity, 2.0, POSITION_TOLERANCE);
+    assert_close(
+        "Normal Y",
+        output.normal.adjust_precision().y,
+        1.0,
+        VELOCITY_TOLERANCE,
+    );
+}
+
+/// The [`TnuaProximitySensor`] must not detect the collider of the character it is attached to,
+/// even though its ray starts inside it.
+pub fn sensor_ignores_owner_collider<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    run_frames(&mut app, 5);
+
+    let sensor = get::<TnuaProximitySensor>(&app, character);
+    let output = sensor
+        .output
+        .as_ref()
+        .expect("The sensor should detect the floor");
+    assert_ne!(
+        output.entity, character,
+        "The sensor should not detect the character's own collider"
+    );
+    assert_eq!(output.entity, floor, "The sensor should detect the floor");
+}
+
+/// The [`TnuaProximitySensor`] must not report anything when there is nothing within its range.
+pub fn sensor_reports_nothing_out_of_range<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    spawn_floor::<H>(&mut app);
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 10.0, 0.0));
+    run_frames(&mut app, 5);
+
+    let sensor = get::<TnuaProximitySensor>(&app, character);
+    assert!(
+        sensor.output.is_none(),
+        "The floor is out of range, but the sensor detects {:?}",
+        sensor.output
+    );
+}
+
+/// The [`TnuaProximitySensor`] must skip colliders marked with [`TnuaNotPlatform`].
+pub fn sensor_skips_not_platform<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    let not_platform = H::spawn_static_cuboid(
+        app.world_mut(),
+        Vector3::new(0.0, 1.0, 0.0),
+        Vector3::new(1.0, 0.1, 1.0),
+    );
+    app.world_mut()
+        .entity_mut(not_platform)
+        .insert(TnuaNotPlatform);
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    run_frames(&mut app, 5);
+
+    let sensor = get::<TnuaProximitySensor>(&app, character);
+    let output = sensor
+        .output
+        .as_ref()
+        .expect("The sensor should detect the floor");
+    assert_ne!(
+        output.entity, not_platform,
+        "The sensor should skip the `TnuaNotPlatform` collider"
+    );
+    assert_eq!(output.entity, floor, "The sensor should detect the floor");
+}
+
+/// The [`TnuaGhostSensor`] must collect all the ghost platforms before the first tangible hit -
+/// and only them - while the [`TnuaProximitySensor`] reports that tangible hit.
+pub fn ghost_hits_collected_before_tangible_hit<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    let ghost_above_floor = H::spawn_ghost_platform(
+        app.world_mut(),
+        Vector3::new(0.0, 1.0, 0.0),
+        Vector3::new(1.0, 0.1, 1.0),
+    );
+    let ghost_below_floor = H::spawn_ghost_platform(
+        app.world_mut(),
+        Vector3::new(0.0, -2.0, 0.0),
+        Vector3::new(1.0, 0.1, 1.0),
+    );
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    run_frames(&mut app, 5);
+
+    let sensor = get::<TnuaProximitySensor>(&app, character);
+    let output = sensor
+        .output
+        .as_ref()
+        .expect("The sensor should detect the floor");
+    assert_eq!(
+        output.entity, floor,
+        "The sensor should skip the ghost platform and detect the floor"
+    );
+
+    let ghost_sensor = get::<TnuaGhostSensor>(&app, character);
+    let ghost_entities = ghost_sensor
+        .iter()
+        .map(|ghost| ghost.entity)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ghost_entities,
+        [ghost_above_floor],
+        "Only the ghost platform above the floor should be collected (the one below it is {ghost_below_floor})"
+    );
+    let ghost_output = &ghost_sensor.0[0];
+    assert_close(
+        "Ghost proximity",
+        ghost_output.proximity,
+        0.9,
+        POSITION_TOLERANCE,
+    );
+}
+
+/// A [`TnuaProximitySensor`] with [`TnuaSubservientSensor`] must ignore the collider of the
+/// `owner_entity` instead of its own.
+pub fn subservient_sensor_ignores_owner_collider<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    let subservient = app
+        .world_mut()
+        .spawn((
+            ChildOf(character),
+            Transform::default(),
+            TnuaSubservientSensor {
+                owner_entity: character,
+            },
+            TnuaProximitySensor {
+                cast_origin: Vector3::ZERO,
+                cast_direction: Dir3::NEG_Y,
+                cast_range: 5.0,
+                output: None,
+            },
+        ))
+        .id();
+    run_frames(&mut app, 5);
+
+    let sensor = get::<TnuaProximitySensor>(&app, subservient);
+    let output = sensor
+        .output
+        .as_ref()
+        .expect("The subservient sensor should detect the floor");
+    assert_ne!(
+        output.entity, character,
+        "The subservient sensor should not detect its owner's collider"
+    );
+    assert_eq!(
+        output.entity, floor,
+        "The subservient sensor should detect the floor"
+    );
+    assert_close("Proximity", output.proximity, 2.0, POSITION_TOLERANCE);
+}
+
+/// The [`TnuaWaterSensor`] must detect the [`TnuaWaterVolume`] around (or below) the character and
+/// measure how deep the character is in it, while the [`TnuaProximitySensor`] must ignore the
+/// water volume.
+pub fn water_sensor_measures_depth<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    // Water surface is at Y=3
+    let water = H::spawn_water_volume(
+        app.world_mut(),
+        Vector3::new(0.0, 1.5, 0.0),
+        Vector3::new(20.0, 1.5, 20.0),
+    );
+    let water_sensor = || TnuaWaterSensor {
+        cast_origin: Vector3::ZERO,
+        cast_direction: Dir3::NEG_Y,
+        cast_range: 2.0,
+        output: None,
+    };
+    let submerged = spawn_character::<H>(&mut app, Vector3::new(-4.0, 2.0, 0.0));
+    let above_water = spawn_character::<H>(&mut app, Vector3::new(0.0, 4.0, 0.0));
+    let out_of_range = spawn_character::<H>(&mut app, Vector3::new(4.0, 10.0, 0.0));
+    for character in [submerged, above_water, out_of_range] {
+        app.world_mut().entity_mut(character).insert(water_sensor());
+    }
+    run_frames(&mut app, 5);
+
+    let output = get::<TnuaWaterSensor>(&app, submerged)
+        .output
+        .as_ref()
+        .expect("The water sensor should detect the water");
+    assert_eq!(
+        output.entity, water,
+        "The water sensor should detect the water"
+    );
+    assert_close("Submerged depth", output.depth, 1.0, POSITION_TOLERANCE);
+
+    let sensor = get::<TnuaProximitySensor>(&app, submerged);
+    let output = sensor
+        .output
+        .as_ref()
+        .expect("The sensor should detect the floor");
+    assert_eq!(
+        output.entity, floor,
+        "The proximity sensor should skip the water and detect the floor"
+    );
+
+    let output = get::<TnuaWaterSensor>(&app, above_water)
+        .output
+        .as_ref()
+        .expect("The water sensor should detect the water below it");
+    assert_eq!(
+        output.entity, water,
+        "The water sensor should detect the water"
+    );
+    assert_close("Above water depth", output.depth, -1.0, POSITION_TOLERANCE);
+
+    let output = &get::<TnuaWaterSensor>(&app, out_of_range).output;
+    assert!(
+        output.is_none(),
+        "The water is out of range, but the water sensor detects {output:?}"
+    );
+}
+
+/// The [`TnuaObstacleRadar`] must mark the obstacles within its cylinder (but not the character
+/// itself), and must be fed the character's entity, position, and the up direction (opposite to
+/// the gravity).
+pub fn radar_marks_nearby_obstacles<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let near_obstacle = H::spawn_static_cuboid(
+        app.world_mut(),
+        Vector3::new(1.5, 2.0, 0.0),
+        Vector3::new(0.5, 2.0, 0.5),
+    );
+    let far_obstacle = H::spawn_static_cuboid(
+        app.world_mut(),
+        Vector3::new(10.0, 2.0, 0.0),
+        Vector3::new(0.5, 2.0, 0.5),
+    );
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaObstacleRadar::new(2.0, 2.0));
+    run_frames(&mut app, 5);
+
+    let radar = get::<TnuaObstacleRadar>(&app, character);
+    assert_eq!(
+        radar.tracked_entity(),
+        character,
+        "The radar should track its character"
+    );
+    assert_close(
+        "Radar position X",
+        radar.tracked_position().x,
+        0.0,
+        POSITION_TOLERANCE,
+    );
+    assert_close(
+        "Radar position Y",
+        radar.tracked_position().y,
+        2.0,
+        POSITION_TOLERANCE,
+    );
+    assert_close(
+        "Radar up direction Y",
+        radar.up_direction().adjust_precision().y,
+        1.0,
+        VELOCITY_TOLERANCE,
+    );
+    assert!(
+        radar.has_blip(near_obstacle),
+        "The radar should detect the near obstacle"
+    );
+    assert!(
+        !radar.has_blip(far_obstacle),
+        "The radar should not detect the far obstacle"
+    );
+    assert!(
+        !radar.has_blip(character),
+        "The radar should not detect its own character"
+    );
+}
+
+/// The [`TnuaObstacleRadar`] must stop reporting obstacles that are no longer there.
+pub fn radar_forgets_removed_obstacles<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let obstacle = H::spawn_static_cuboid(
+        app.world_mut(),
+        Vector3::new(1.5, 2.0, 0.0),
+        Vector3::new(0.5, 2.0, 0.5),
+    );
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaObstacleRadar::new(2.0, 2.0));
+    run_frames(&mut app, 5);
+    assert!(
+        get::<TnuaObstacleRadar>(&app, character).has_blip(obstacle),
+        "The radar should detect the obstacle"
+    );
+
+    app.world_mut().despawn(obstacle);
+    run_frames(&mut app, 5);
+    assert!(
+        !get::<TnuaObstacleRadar>(&app, character).has_blip(obstacle),
+        "The radar should forget the despawned obstacle"
+    );
+}
+
+/// The [`boost`](crate::data_for_backends::TnuaVelChange::boost) of the [`TnuaMotor`] must be
+/// added to the velocity of the rigid body.
+pub fn motor_applies_boost<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaGravity(Vector3::ZERO));
+    run_frames(&mut app, 2);
+
+    get_mut::<TnuaMotor>(&mut app, character).lin.boost = Vector3::new(3.0, 0.0, 0.0);
+    run_frames(&mut app, 1);
+    *get_mut::<TnuaMotor>(&mut app, character) = Default::default();
+    run_frames(&mut app, 3);
+
+    let tracker = get::<TnuaRigidBodyTracker>(&app, character);
+    assert_close(
+        "Tracked velocity X",
+        tracker.velocity.x,
+        3.0,
+        VELOCITY_TOLERANCE,
+    );
+}
+
+/// The [`acceleration`](crate::data_for_backends::TnuaVelChange::acceleration) of the
+/// [`TnuaMotor`] must be applied over time to the velocity of the rigid body.
+pub fn motor_applies_acceleration<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let character = spawn_character::<H>(&mut app, Vector3::new(0.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaGravity(Vector3::ZERO));
+    run_frames(&mut app, 2);
+
+    get_mut::<TnuaMotor>(&mut app, character).lin.acceleration = Vector3::new(6.0, 0.0, 0.0);
+    run_frames(&mut app, 10);
+    let velocity_while_accelerating = get::<TnuaRigidBodyTracker>(&app, character).velocity.x;
+    // Give or take a frame, since the tracker may lag behind the physics engine.
+    assert!(
+        (6.0 * 8.0 * FRAME_DURATION..=6.0 * 11.0 * FRAME_DURATION)
+            .contains(&velocity_while_accelerating),
+        "After accelerating at 6 for 10 frames, the tracked velocity X should be about {}, but it is {velocity_while_accelerating}",
+        6.0 * 10.0 * FRAME_DURATION,
+    );
+
+    *get_mut::<TnuaMotor>(&mut app, character) = Default::default();
+    run_frames(&mut app, 3);
+    let velocity_after_accelerating = get::<TnuaRigidBodyTracker>(&app, character).velocity.x;
+    run_frames(&mut app, 5);
+    assert_close(
+        "Tracked velocity X after the acceleration stopped",
+        get::<TnuaRigidBodyTracker>(&app, character).velocity.x,
+        velocity_after_accelerating,
+        VELOCITY_TOLERANCE,
+    );
+}
+
+/// With [`TnuaToggle::Disabled`], the backend must neither update the sensors nor apply the
+/// motor.
+pub fn toggle_disabled_skips_sensors_and_motors<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    spawn_floor::<H>(&mut app);
+    let character = spawn_character::<H>(&mut app, Vector3::new(-3.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert((TnuaToggle::Disabled, TnuaGravity(Vector3::ZERO)));
+    get_mut::<TnuaMotor>(&mut app, character).lin.boost = Vector3::new(3.0, 0.0, 0.0);
+    run_frames(&mut app, 5);
+
+    let tracker = get::<TnuaRigidBodyTracker>(&app, character);
+    assert_eq!(
+        tracker.translation,
+        Vector3::ZERO,
+        "The tracker should not be updated when disabled"
+    );
+    let sensor = get::<TnuaProximitySensor>(&app, character);
+    assert!(
+        sensor.output.is_none(),
+        "The sensor should not be updated when disabled"
+    );
+
+    *get_mut::<TnuaMotor>(&mut app, character) = Default::default();
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaToggle::Enabled);
+    run_frames(&mut app, 3);
+    let tracker = get::<TnuaRigidBodyTracker>(&app, character);
+    assert_close("Tracked X", tracker.translation.x, -3.0, POSITION_TOLERANCE);
+    assert_close(
+        "Tracked velocity X (boost should not apply while disabled)",
+        tracker.velocity.x,
+        0.0,
+        VELOCITY_TOLERANCE,
+    );
+}
+
+/// With [`TnuaToggle::SenseOnly`], the backend must update the sensors but not apply the motor.
+///
+/// This must not affect other characters.
+pub fn toggle_sense_only_skips_motors<H: TnuaConformanceHarness>() {
+    let mut app = create_app::<H>();
+    let floor = spawn_floor::<H>(&mut app);
+    // Spawned first, so that a backend that stops processing the motors once it encounters it
+    // will fail the test for the enabled character.
+    let sense_only = spawn_character::<H>(&mut app, Vector3::new(-3.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(sense_only)
+        .insert((TnuaToggle::SenseOnly, TnuaGravity(Vector3::ZERO)));
+    let enabled = spawn_character::<H>(&mut app, Vector3::new(3.0, 2.0, 0.0));
+    app.world_mut()
+        .entity_mut(enabled)
+        .insert(TnuaGravity(Vector3::ZERO));
+    run_frames(&mut app, 2);
+
+    for character in [sense_only, enabled] {
+        get_mut::<TnuaMotor>(&mut app, character).lin.acceleration = Vector3::new(6.0, 0.0, 0.0);
+    }
+    run_frames(&mut app, 10);
+
+    let tracker = get::<TnuaRigidBodyTracker>(&app, sense_only);
+    assert_close("Tracked X", tracker.translation.x, -3.0, POSITION_TOLERANCE);
+    assert_close(
+        "Tracked velocity X (acceleration should not apply with `SenseOnly`)",
+        tracker.velocity.x,
+        0.0,
+        VELOCITY_TOLERANCE,
+    );
+    let sensor = get::<TnuaProximitySensor>(&app, sense_only);
+    assert_eq!(
+        sensor.output.as_ref().map(|output| output.entity),
+        Some(floor),
+        "The sensor should be updated with `SenseOnly`"
+    );
+
+    let tracker = get::<TnuaRigidBodyTracker>(&app, enabled);
+    assert!(
+        0.5 < tracker.velocity.x,
+        "The acceleration should apply to the enabled character, but its tracked velocity is {}",
+        tracker.velocity,
+    );
+}
@@ -11,7 +11,8 @@ pub use crate::obstacle_radar::TnuaObstacleRadar;
 ///
 /// This component is not mandatory - if omitted, Tnua will just assume it is enabled for that
 /// entity.
-#[derive(Component, Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Component, Default, Debug, PartialEq, Eq, Clone, Copy, Reflect)]
+#[reflect(Component)]
 pub enum TnuaToggle {
     /// Do not update the sensors, and do not apply forces from the motor.
     ///
@@ -36,7 +37,9 @@ pub enum TnuaToggle {
 /// like velocity are dependent on the physics engine. The physics backend is responsible for
 /// updating this component from the physics engine during
 /// [`TnuaPipelineStages::Sensors`](crate::TnuaPipelineStages::Sensors).
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaRigidBodyTracker {
     pub translation: Vector3,
     pub rotation: Quaternion,
@@ -64,7 +67,8 @@ impl Default for TnuaRigidBodyTracker {
 /// The physics backend is responsible for updating this component from the physics engine during
 /// [`TnuaPipelineStages::Sensors`](crate::TnuaPipelineStages::Sensors), usually by casting a ray
 /// or a shape in the `cast_direction`.
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
 pub struct TnuaProximitySensor {
     /// The cast origin in the entity's coord system.
     pub cast_origin: Vector3,
@@ -87,7 +91,8 @@ impl Default for TnuaProximitySensor {
 }
 
 /// Information from [`TnuaProximitySensor`] that have detected another collider.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaProximitySensorOutput {
     /// The entity of the collider detected by the ray.
     pub entity: Entity,
@@ -105,7 +110,8 @@ pub struct TnuaProximitySensorOutput {
 }
 
 /// Represents a change to velocity (linear or angular)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaVelChange {
     // The part of the velocity change that gets multiplied by the frame duration.
     //
@@ -239,7 +245,9 @@ impl AddAssign for TnuaVelChange {
 ///
 /// This documentation uses the term "forces", but in fact these numbers ignore mass and are
 /// applied directly to the velocity.
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaMotor {
     /// How much velocity to add to the rigid body in the current frame.
     pub lin: TnuaVelChange,
@@ -259,7 +267,8 @@ pub struct TnuaMotor {
 /// See <https://github.com/idanarye/bevy-tnua/wiki/Jump-fall-Through-Platforms>
 ///
 /// See `TnuaSimpleFallThroughPlatformsHelper`.
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component)]
 pub struct TnuaGhostSensor(pub Vec<TnuaProximitySensorOutput>);
 
 impl TnuaGhostSensor {
@@ -277,15 +286,75 @@ impl TnuaGhostSensor {
 /// See <https://github.com/idanarye/bevy-tnua/wiki/Jump-fall-Through-Platforms>
 ///
 /// See `TnuaSimpleFallThroughPlatformsHelper`.
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component)]
 pub struct TnuaGhostPlatform;
 
 /// Change the gravity for a Tnua-controlled character.
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
 pub struct TnuaGravity(pub Vector3);
 
 /// Marker component for colliders which Tnua should not treat as platform.
 ///
 /// This means that the ray/shape cast ignores these hits.
-#[derive(Component, Default, Debug)]
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component)]
 pub struct TnuaNotPlatform;
+
+/// A marker for water volumes, that [`TnuaWaterSensor`] can detect.
+///
+/// The proximity sensor ignores water volumes, so the character will not try to stand on them.
+/// Their colliders should usually be sensors (or otherwise not interact with the character's
+/// collider) so that the character can enter them.
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TnuaWaterVolume;
+
+/// Detects the [`TnuaWaterVolume`] the character is in, and how deep in it the character is.
+///
+/// The physics backend is responsible for updating this component from the physics engine during
+/// [`TnuaPipelineStages::Sensors`](crate::TnuaPipelineStages::Sensors), usually by casting a ray
+/// in the `cast_direction` from `cast_range` above the `cast_origin`, and only considering water
+/// volumes.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TnuaWaterSensor {
+    /// The point, in the entity's coord system, whose depth in the water is measured.
+    pub cast_origin: Vector3,
+    /// The direction in world coord system (unmodified by the entity's transform). Points down
+    /// into the water.
+    ///
+    /// Tnua will update this field to be the opposite of the up direction.
+    pub cast_direction: Dir3,
+    /// The maximum depth that can be measured, both below and above the water surface.
+    pub cast_range: Float,
+    pub output: Option<TnuaWaterSensorOutput>,
+}
+
+impl Default for TnuaWaterSensor {
+    fn default() -> Self {
+        Self {
+            cast_origin: Vector3::ZERO,
+            cast_direction: Dir3::NEG_Y,
+            cast_range: 2.0,
+            output: None,
+        }
+    }
+}
+
+/// Information from [`TnuaWaterSensor`] that have detected a water volume.
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaWaterSensorOutput {
+    /// The entity of the water volume.
+    pub entity: Entity,
+    /// The distance of [`cast_origin`](TnuaWaterSensor::cast_origin) below the water surface.
+    ///
+    /// Negative if the origin is above the water surface. When the origin is deeper than the
+    /// [`cast_range`](TnuaWaterSensor::cast_range), the surface cannot be found and this will be
+    /// equal to the `cast_range`.
+    pub depth: Float,
+    /// The velocity of the water volume.
+    pub entity_linvel: Vector3,
+}
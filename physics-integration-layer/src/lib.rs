@@ -33,7 +33,13 @@
 //!       physics engine. The name of that component should be
 //!       `Tnua<physics-engine-name>SensorShape`.
 //!     * The detection should skip entities marked with the
-//!       [`TnuaNotPlatform`](data_for_backends::TnuaNotPlatform) component.
+//!       [`TnuaNotPlatform`](data_for_backends::TnuaNotPlatform) or the
+//!       [`TnuaWaterVolume`](data_for_backends::TnuaWaterVolume) components.
+//!   * [`TnuaWaterSensor`](data_for_backends::TnuaWaterSensor) with the first
+//!     [`TnuaWaterVolume`](data_for_backends::TnuaWaterVolume) collider hit by a ray cast from
+//!     `cast_range` above the sensor's origin, and the depth of the origin below the point where
+//!     that ray enters the water volume. Only water volumes should be considered by that ray, and
+//!     the owner rules are the same as with the proximity sensor.
 //!   * [`TnuaObstacleRadar`](data_for_backends::TnuaObstacleRadar) with all the entities within
 //!     proximity (defined by `radius` and `height` fields on the radar component). The system must
 //!     first call the
@@ -87,8 +93,14 @@
 //! body and a collider, so they should not go in that bundle, but if the crate needs things users
 //! rarely think about - for example, bevy_rapier's `ReadMassProperties` - then these components
 //! should go in that bundle.
+//!
+//! The `conformance` feature of this crate adds the `conformance` module - a test kit that checks
+//! these rules against an integration crate. Integration crates should enable it in their
+//! dev-dependencies and run it in their tests using the `tnua_conformance_tests!` macro.
 use bevy::prelude::*;
 
+#[cfg(feature = "conformance")]
+pub mod conformance;
 pub mod data_for_backends;
 pub mod math;
 pub mod obstacle_radar;
@@ -23,6 +23,9 @@ use bevy_tnua_physics_integration_layer::data_for_backends::{TnuaGhostPlatform,
 use bevy_tnua_physics_integration_layer::data_for_backends::{
     TnuaMotor, TnuaProximitySensor, TnuaProximitySensorOutput, TnuaRigidBodyTracker,
 };
+use bevy_tnua_physics_integration_layer::data_for_backends::{
+    TnuaWaterSensor, TnuaWaterSensorOutput, TnuaWaterVolume,
+};
 use bevy_tnua_physics_integration_layer::obstacle_radar::TnuaObstacleRadar;
 use bevy_tnua_physics_integration_layer::subservient_sensors::TnuaSubservientSensor;
 use bevy_tnua_physics_integration_layer::TnuaPipelineStages;
@@ -67,6 +70,7 @@ impl Plugin for TnuaRapier3dPlugin {
             (
                 update_rigid_body_trackers_system,
                 update_proximity_sensors_system,
+                update_water_sensors_system,
                 update_obstacle_radars_system,
             )
                 .in_set(TnuaPipelineStages::Sensors),
@@ -159,7 +163,7 @@ fn update_proximity_sensors_system(
         Option<&TnuaToggle>,
     )>,
     ghost_platforms_query: Query<(), With<TnuaGhostPlatform>>,
-    not_platform_query: Query<(), With<TnuaNotPlatform>>,
+    not_platform_query: Query<(), Or<(With<TnuaNotPlatform>, With<TnuaWaterVolume>)>>,
     other_object_query: Query<(&GlobalTransform, &Velocity)>,
 ) {
     query.par_iter_mut().for_each(
@@ -378,6 +382,65 @@ fn update_proximity_sensors_system(
     );
 }
 
+#[allow(clippy::type_complexity)]
+fn update_water_sensors_system(
+    rapier_context_query: Query<RapierContext>,
+    mut query: Query<(
+        Entity,
+        &RapierContextEntityLink,
+        &GlobalTransform,
+        &mut TnuaWaterSensor,
+        Option<&TnuaToggle>,
+    )>,
+    water_volumes_query: Query<(), With<TnuaWaterVolume>>,
+    velocities_query: Query<&Velocity>,
+) {
+    for (owner_entity, rapier_context_entity_link, transform, mut sensor, tnua_toggle) in
+        query.iter_mut()
+    {
+        match tnua_toggle.copied().unwrap_or_default() {
+            TnuaToggle::Disabled => continue,
+            TnuaToggle::SenseOnly => {}
+            TnuaToggle::Enabled => {}
+        }
+
+        let Ok(rapier_context) = rapier_context_query.get(rapier_context_entity_link.0) else {
+            continue;
+        };
+
+        let cast_origin = transform.transform_point(sensor.cast_origin);
+        let cast_direction = sensor.cast_direction;
+        // Cast from above the origin, so that the ray will enter the water volume through its
+        // surface.
+        let cast_start = cast_origin - sensor.cast_range * *cast_direction;
+
+        let predicate = |other_entity: Entity| water_volumes_query.contains(other_entity);
+        let query_filter = QueryFilter::new()
+            .exclude_rigid_body(owner_entity)
+            .predicate(&predicate);
+
+        sensor.output = rapier_context
+            .query_pipeline
+            .cast_ray(
+                rapier_context.colliders,
+                rapier_context.rigidbody_set,
+                cast_start,
+                *cast_direction,
+                2.0 * sensor.cast_range,
+                true,
+                query_filter,
+            )
+            .map(|(entity, time_of_impact)| TnuaWaterSensorOutput {
+                entity,
+                depth: sensor.cast_range - time_of_impact,
+                entity_linvel: velocities_query
+                    .get(entity)
+                    .map(|velocity| velocity.linvel)
+                    .unwrap_or(Vec3::ZERO),
+            });
+    }
+}
+
 fn update_obstacle_radars_system(
     rapier_world_query: Query<(RapierContext, &RapierConfiguration)>,
     mut radars_query: Query<(
@@ -403,7 +466,7 @@ fn update_obstacle_radars_system(
         radar.pre_marking_update(
             radar_owner_entity,
             radar_translation,
-            Dir3::new(rapier_config.gravity).unwrap_or(Dir3::Y),
+            Dir3::new(-rapier_config.gravity).unwrap_or(Dir3::Y),
         );
         rapier_context.query_pipeline.intersections_with_shape(
             rapier_context.colliders,
@@ -440,7 +503,7 @@ fn apply_motors_system(
         match tnua_toggle.copied().unwrap_or_default() {
             TnuaToggle::Disabled | TnuaToggle::SenseOnly => {
                 *external_force = Default::default();
-                return;
+                continue;
             }
             TnuaToggle::Enabled => {}
         }
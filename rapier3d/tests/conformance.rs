@@ -0,0 +1,63 @@
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use bevy_tnua_physics_integration_layer::conformance::{TnuaConformanceHarness, CHARACTER_RADIUS};
+use bevy_tnua_physics_integration_layer::data_for_backends::{TnuaGhostPlatform, TnuaWaterVolume};
+use bevy_tnua_physics_integration_layer::math::Vector3;
+use bevy_tnua_physics_integration_layer::tnua_conformance_tests;
+use bevy_tnua_rapier3d::TnuaRapier3dPlugin;
+
+struct Harness;
+
+impl TnuaConformanceHarness for Harness {
+    fn schedule() -> InternedScheduleLabel {
+        Update.intern()
+    }
+
+    fn add_backend(app: &mut App) {
+        app.add_plugins((
+            RapierPhysicsPlugin::<NoUserData>::default(),
+            TnuaRapier3dPlugin::default(),
+        ));
+    }
+
+    fn spawn_static_cuboid(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        world
+            .spawn((
+                Transform::from_translation(position),
+                RigidBody::Fixed,
+                Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+            ))
+            .id()
+    }
+
+    fn spawn_ghost_platform(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        let entity = Self::spawn_static_cuboid(world, position, half_extents);
+        world.entity_mut(entity).insert((
+            TnuaGhostPlatform,
+            SolverGroups {
+                memberships: Group::empty(),
+                filters: Group::empty(),
+            },
+        ));
+        entity
+    }
+
+    fn spawn_water_volume(world: &mut World, position: Vector3, half_extents: Vector3) -> Entity {
+        let entity = Self::spawn_static_cuboid(world, position, half_extents);
+        world.entity_mut(entity).insert((TnuaWaterVolume, Sensor));
+        entity
+    }
+
+    fn spawn_character(world: &mut World, position: Vector3) -> Entity {
+        world
+            .spawn((
+                Transform::from_translation(position),
+                RigidBody::Dynamic,
+                Collider::ball(CHARACTER_RADIUS),
+            ))
+            .id()
+    }
+}
+
+tnua_conformance_tests!(Harness);
@@ -4,7 +4,7 @@ use bevy_tnua_physics_integration_layer::math::{Float, Vector3};
 
 use std::{any::Any, time::Duration};
 
-use crate::{TnuaMotor, TnuaProximitySensor, TnuaRigidBodyTracker};
+use crate::{TnuaMotor, TnuaProximitySensor, TnuaRigidBodyTracker, TnuaWaterSensor};
 
 /// Various data passed to [`TnuaBasis::apply`].
 pub struct TnuaBasisContext<'a> {
@@ -17,6 +17,9 @@ pub struct TnuaBasisContext<'a> {
     /// A sensor that tracks the distance of the character's center from the ground.
     pub proximity_sensor: &'a TnuaProximitySensor,
 
+    /// A sensor that tracks the depth of the character in water, if the character has one.
+    pub water_sensor: Option<&'a TnuaWaterSensor>,
+
     /// The direction considered as "up".
     pub up_direction: Dir3,
 }
@@ -32,7 +35,7 @@ pub struct TnuaBasisContext<'a> {
 /// overwritten each frame by the controller system of the game code. Configuration is considered
 /// as part of the input. If the basis needs to persist data between frames it must keep it in its
 /// [state](Self::State).
-pub trait TnuaBasis: 'static + Send + Sync {
+pub trait TnuaBasis: 'static + Send + Sync + Clone + Reflect {
     /// The default name of the basis.
     ///
     /// [Once `type_name` becomes `const`](https://github.com/rust-lang/rust/issues/63084), this
@@ -50,7 +53,7 @@ pub trait TnuaBasis: 'static + Send + Sync {
     ///
     /// 3. Inspect the basis from game code systems, like an animation controlling system that
     ///    needs to know which animation to play based on the basis' current state.
-    type State: Default + Send + Sync;
+    type State: Default + Send + Sync + Clone + Reflect;
 
     /// This is where the basis affects the character's motion.
     ///
@@ -94,11 +97,21 @@ pub trait TnuaBasis: 'static + Send + Sync {
     /// If the basis is at coyote time - finish the coyote time.
     ///
     /// This will be called automatically by Tnua, if the controller runs an action that  [violated
-    /// coyote time](TnuaAction::VIOLATES_COYOTE_TIME), so that a long coyote time will not allow,
+    /// coyote time](TnuaAction::violates_coyote_time), so that a long coyote time will not allow,
     /// for example, unaccounted air jumps.
     ///
     /// If the character is fully grounded, this method must not change that.
     fn violate_coyote_time(&self, state: &mut Self::State);
+
+    /// Ignore the ground on the next frame, so that the character will not land (or stay
+    /// grounded) while the ground is ignored.
+    ///
+    /// This will be called automatically by Tnua, every frame the controller runs an action that
+    /// [suppresses landing](TnuaAction::suppresses_landing) - for example, while the character's
+    /// gravity is being flipped.
+    ///
+    /// The default implementation does nothing, which is fine for bases that do not land.
+    fn suppress_landing(&self, _state: &mut Self::State) {}
 }
 
 /// Helper trait for accessing a basis and its trait with dynamic dispatch.
@@ -106,6 +119,9 @@ pub trait DynamicBasis: Send + Sync + Any + 'static {
     #[doc(hidden)]
     fn as_any(&self) -> &dyn Any;
 
+    #[doc(hidden)]
+    fn clone_boxed(&self) -> Box<dyn DynamicBasis>;
+
     #[doc(hidden)]
     fn as_mut_any(&mut self) -> &mut dyn Any;
 
@@ -130,8 +146,25 @@ pub trait DynamicBasis: Send + Sync + Any + 'static {
     /// Dynamically invokes [`TnuaBasis::is_airborne`].
     fn is_airborne(&self) -> bool;
 
+    /// The basis input, for reflection.
+    fn input_reflect(&self) -> &dyn Reflect;
+
+    /// The basis input, for reflection with mutable access.
+    ///
+    /// Note that the input is usually overwritten every frame by the game code.
+    fn input_reflect_mut(&mut self) -> &mut dyn Reflect;
+
+    /// The basis [state](TnuaBasis::State), for reflection.
+    fn state_reflect(&self) -> &dyn Reflect;
+
+    /// The basis [state](TnuaBasis::State), for reflection with mutable access.
+    fn state_reflect_mut(&mut self) -> &mut dyn Reflect;
+
     #[doc(hidden)]
     fn violate_coyote_time(&mut self);
+
+    #[doc(hidden)]
+    fn suppress_landing(&mut self);
 }
 
 pub(crate) struct BoxableBasis<B: TnuaBasis> {
@@ -153,6 +186,13 @@ impl<B: TnuaBasis> DynamicBasis for BoxableBasis<B> {
         self
     }
 
+    fn clone_boxed(&self) -> Box<dyn DynamicBasis> {
+        Box::new(Self {
+            input: self.input.clone(),
+            state: self.state.clone(),
+        })
+    }
+
     fn as_mut_any(&mut self) -> &mut dyn Any {
         self
     }
@@ -185,9 +225,29 @@ impl<B: TnuaBasis> DynamicBasis for BoxableBasis<B> {
         self.input.is_airborne(&self.state)
     }
 
+    fn input_reflect(&self) -> &dyn Reflect {
+        &self.input
+    }
+
+    fn input_reflect_mut(&mut self) -> &mut dyn Reflect {
+        &mut self.input
+    }
+
+    fn state_reflect(&self) -> &dyn Reflect {
+        &self.state
+    }
+
+    fn state_reflect_mut(&mut self) -> &mut dyn Reflect {
+        &mut self.state
+    }
+
     fn violate_coyote_time(&mut self) {
         self.input.violate_coyote_time(&mut self.state)
     }
+
+    fn suppress_landing(&mut self) {
+        self.input.suppress_landing(&mut self.state)
+    }
 }
 
 /// Various data passed to [`TnuaAction::apply`].
@@ -202,6 +262,9 @@ pub struct TnuaActionContext<'a> {
     /// A sensor that tracks the distance of the character's center from the ground.
     pub proximity_sensor: &'a TnuaProximitySensor,
 
+    /// A sensor that tracks the depth of the character in water, if the character has one.
+    pub water_sensor: Option<&'a TnuaWaterSensor>,
+
     /// The direction considered as "up".
     pub up_direction: Dir3,
 
@@ -227,6 +290,7 @@ impl<'a> TnuaActionContext<'a> {
             frame_duration: self.frame_duration,
             tracker: self.tracker,
             proximity_sensor: self.proximity_sensor,
+            water_sensor: self.water_sensor,
             up_direction: self.up_direction,
         }
     }
@@ -377,7 +441,7 @@ pub enum TnuaActionInitiationDirective {
 /// overwritten each frame by the controller system of the game code - although unlike basis the
 /// input will probably be the exact same. Configuration is considered as part of the input. If the
 /// action needs to persist data between frames it must keep it in its [state](Self::State).
-pub trait TnuaAction: 'static + Send + Sync {
+pub trait TnuaAction: 'static + Send + Sync + Clone + Reflect {
     /// The default name of the action.
     ///
     /// [Once `type_name` becomes `const`](https://github.com/rust-lang/rust/issues/63084), this
@@ -395,9 +459,12 @@ pub trait TnuaAction: 'static + Send + Sync {
     ///
     /// 3. Inspect the action from game code systems, like an animation controlling system that
     ///    needs to know which animation to play based on the action's current state.
-    type State: Default + Send + Sync;
+    type State: Default + Send + Sync + Clone + Reflect;
 
     /// Set this to true for actions that may launch the character into the air.
+    ///
+    /// Actions that only launch the character in some of their states should also override
+    /// [`violates_coyote_time`](Self::violates_coyote_time).
     const VIOLATES_COYOTE_TIME: bool;
 
     /// This is where the action affects the character's motion.
@@ -445,10 +512,37 @@ pub trait TnuaAction: 'static + Send + Sync {
     fn target_entity(&self, _state: &Self::State) -> Option<Entity> {
         None
     }
+
+    /// Return `true` to make the basis ignore the ground on the next frame.
+    ///
+    /// This is checked every frame after [`apply`](Self::apply), and when it returns `true` Tnua
+    /// will call the basis' [`suppress_landing`](TnuaBasis::suppress_landing).
+    fn suppresses_landing(&self, _state: &Self::State) -> bool {
+        false
+    }
+
+    /// Return `true` to make the basis finish its coyote time.
+    ///
+    /// This is checked every frame after [`apply`](Self::apply), and when it returns `true` Tnua
+    /// will call the basis' [`violate_coyote_time`](TnuaBasis::violate_coyote_time). Defaults to
+    /// [`VIOLATES_COYOTE_TIME`](Self::VIOLATES_COYOTE_TIME).
+    fn violates_coyote_time(&self, _state: &Self::State) -> bool {
+        Self::VIOLATES_COYOTE_TIME
+    }
+
+    /// Return `true` to make air actions counters count this action like a jump from the ground.
+    ///
+    /// This is checked on the frame the action starts. It is only a hint for
+    /// [`TnuaSimpleAirActionsCounter`](crate::control_helpers::TnuaSimpleAirActionsCounter) -
+    /// Tnua itself does not use it.
+    fn refreshes_air_actions(&self, _state: &Self::State) -> bool {
+        false
+    }
 }
 
 pub trait DynamicAction: Send + Sync + Any + 'static {
     fn as_any(&self) -> &dyn Any;
+    fn clone_boxed(&self) -> Box<dyn DynamicAction>;
     fn as_mut_any(&mut self) -> &mut dyn Any;
     fn apply(
         &mut self,
@@ -464,6 +558,22 @@ pub trait DynamicAction: Send + Sync + Any + 'static {
     ) -> TnuaActionInitiationDirective;
     fn violates_coyote_time(&self) -> bool;
     fn target_entity(&self) -> Option<Entity>;
+    fn suppresses_landing(&self) -> bool;
+    fn refreshes_air_actions(&self) -> bool;
+
+    /// The action input, for reflection.
+    fn input_reflect(&self) -> &dyn Reflect;
+
+    /// The action input, for reflection with mutable access.
+    ///
+    /// Note that the input is usually overwritten every frame by the game code.
+    fn input_reflect_mut(&mut self) -> &mut dyn Reflect;
+
+    /// The action [state](TnuaAction::State), for reflection.
+    fn state_reflect(&self) -> &dyn Reflect;
+
+    /// The action [state](TnuaAction::State), for reflection with mutable access.
+    fn state_reflect_mut(&mut self) -> &mut dyn Reflect;
 }
 
 pub(crate) struct BoxableAction<A: TnuaAction> {
@@ -485,6 +595,13 @@ impl<A: TnuaAction> DynamicAction for BoxableAction<A> {
         self
     }
 
+    fn clone_boxed(&self) -> Box<dyn DynamicAction> {
+        Box::new(Self {
+            input: self.input.clone(),
+            state: self.state.clone(),
+        })
+    }
+
     fn as_mut_any(&mut self) -> &mut dyn Any {
         self
     }
@@ -512,10 +629,34 @@ impl<A: TnuaAction> DynamicAction for BoxableAction<A> {
     }
 
     fn violates_coyote_time(&self) -> bool {
-        A::VIOLATES_COYOTE_TIME
+        self.input.violates_coyote_time(&self.state)
     }
 
     fn target_entity(&self) -> Option<Entity> {
         self.input.target_entity(&self.state)
     }
+
+    fn suppresses_landing(&self) -> bool {
+        self.input.suppresses_landing(&self.state)
+    }
+
+    fn refreshes_air_actions(&self) -> bool {
+        self.input.refreshes_air_actions(&self.state)
+    }
+
+    fn input_reflect(&self) -> &dyn Reflect {
+        &self.input
+    }
+
+    fn input_reflect_mut(&mut self) -> &mut dyn Reflect {
+        &mut self.input
+    }
+
+    fn state_reflect(&self) -> &dyn Reflect {
+        &self.state
+    }
+
+    fn state_reflect_mut(&mut self) -> &mut dyn Reflect {
+        &mut self.state
+    }
 }
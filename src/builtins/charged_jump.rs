@@ -0,0 +1,204 @@
+use crate::math::Float;
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+
+use crate::{
+    TnuaAction, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor,
+};
+
+use super::{TnuaBuiltinCrouch, TnuaBuiltinCrouchState, TnuaBuiltinJump, TnuaBuiltinJumpState};
+
+/// A jump [action](TnuaAction) that is charged by holding the jump button.
+///
+/// The action must be fed for as long as the player holds the jump button. While it is fed, the
+/// jump is being charged - and if [`crouch`](Self::crouch) is set, the character crouches using
+/// [`TnuaBuiltinCrouch`]'s logic. When the action stops being fed, the character jumps with
+/// [`jump`](Self::jump), using a height between [`min_height`](Self::min_height) and
+/// [`max_height`](Self::max_height) according to how long the jump was charged.
+///
+/// Unlike [`TnuaBuiltinJump`] on its own, releasing the button does not shorten the jump - the
+/// button is already released when the jump starts. The charge progress is exposed in the
+/// [state](TnuaBuiltinChargedJumpState::charge_progress), for UI and animation.
+///
+/// Charging does not violate the basis' coyote time - only the jump itself does. If the character
+/// is in the air when the button is released (e.g. because it walked off a ledge while charging),
+/// the action finishes without jumping - unless the jump's
+/// [`allow_in_air`](TnuaBuiltinJump::allow_in_air) is set.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinChargedJump {
+    /// The jump to perform when the charge is released.
+    ///
+    /// Its [`height`](TnuaBuiltinJump::height) is ignored - the height is decided by the charge.
+    pub jump: TnuaBuiltinJump,
+
+    /// The height of the jump when it is released without charging.
+    pub min_height: Float,
+
+    /// The height of the jump when it is released fully charged.
+    pub max_height: Float,
+
+    /// The time, in seconds, it takes to fully charge the jump.
+    pub full_charge_duration: Float,
+
+    /// The shape of the curve that maps the charge progress to the jump height. With 1.0 the
+    /// height grows linearly, with higher values it grows slowly at first and faster near the
+    /// full charge, and with values between 0.0 and 1.0 it grows fast at first and slower near
+    /// the full charge.
+    pub charge_curve_exponent: Float,
+
+    /// Crouch while charging.
+    pub crouch: Option<TnuaBuiltinCrouch>,
+}
+
+impl Default for TnuaBuiltinChargedJump {
+    fn default() -> Self {
+        Self {
+            jump: Default::default(),
+            min_height: 1.0,
+            max_height: 4.0,
+            full_charge_duration: 1.0,
+            charge_curve_exponent: 1.0,
+            crouch: None,
+        }
+    }
+}
+
+impl TnuaBuiltinChargedJump {
+    /// The height of the jump for the given charge progress (from 0.0 to 1.0)
+    pub fn height_for_charge(&self, progress: Float) -> Float {
+        let curved_progress = progress.clamp(0.0, 1.0).powf(self.charge_curve_exponent);
+        self.min_height + (self.max_height - self.min_height) * curved_progress
+    }
+}
+
+impl TnuaAction for TnuaBuiltinChargedJump {
+    const NAME: &'static str = "TnuaBuiltinChargedJump";
+    type State = TnuaBuiltinChargedJumpState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        self.jump.initiation_decision(ctx, being_fed_for)
+    }
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        // TODO: Once `std::mem::variant_count` gets stabilized, use that instead.
+        for _ in 0..3 {
+            return match state {
+                TnuaBuiltinChargedJumpState::NoCharge => {
+                    *state = TnuaBuiltinChargedJumpState::Charging {
+                        elapsed: 0.0,
+                        progress: 0.0,
+                        crouch_state: Default::default(),
+                    };
+                    continue;
+                }
+                TnuaBuiltinChargedJumpState::Charging {
+                    elapsed,
+                    progress,
+                    crouch_state,
+                } => match lifecycle_status {
+                    TnuaActionLifecycleStatus::Initiated
+                    | TnuaActionLifecycleStatus::CancelledFrom
+                    | TnuaActionLifecycleStatus::StillFed => {
+                        *elapsed += ctx.frame_duration;
+                        *progress = if 0.0 < self.full_charge_duration {
+                            (*elapsed / self.full_charge_duration).min(1.0)
+                        } else {
+                            1.0
+                        };
+                        if let Some(crouch) = &self.crouch {
+                            crouch.apply(
+                                crouch_state,
+                                ctx,
+                                TnuaActionLifecycleStatus::StillFed,
+                                motor,
+                            );
+                        }
+                        TnuaActionLifecycleDirective::StillActive
+                    }
+                    TnuaActionLifecycleStatus::NoLongerFed => {
+                        if !self.jump.allow_in_air && ctx.basis.is_airborne() {
+                            // The character left the ground while charging (e.g. walked off a
+                            // ledge), and the jump cannot take off from the air.
+                            return TnuaActionLifecycleDirective::Finished;
+                        }
+                        *state = TnuaBuiltinChargedJumpState::Jumping {
+                            height: self.height_for_charge(*progress),
+                            jump_state: Default::default(),
+                        };
+                        continue;
+                    }
+                    TnuaActionLifecycleStatus::CancelledInto => {
+                        TnuaActionLifecycleDirective::Finished
+                    }
+                },
+                TnuaBuiltinChargedJumpState::Jumping { height, jump_state } => {
+                    let jump = TnuaBuiltinJump {
+                        height: *height,
+                        ..self.jump.clone()
+                    };
+                    let jump_lifecycle_status = match (&*jump_state, lifecycle_status) {
+                        (_, TnuaActionLifecycleStatus::CancelledInto) => {
+                            TnuaActionLifecycleStatus::CancelledInto
+                        }
+                        (TnuaBuiltinJumpState::NoJump, _) => TnuaActionLifecycleStatus::Initiated,
+                        // The button was released to start the jump, so the jump should not be
+                        // shortened.
+                        _ => TnuaActionLifecycleStatus::StillFed,
+                    };
+                    jump.apply(jump_state, ctx, jump_lifecycle_status, motor)
+                }
+            };
+        }
+        error!("Tnua could not decide on charged jump state");
+        TnuaActionLifecycleDirective::Finished
+    }
+
+    fn violates_coyote_time(&self, state: &Self::State) -> bool {
+        matches!(state, TnuaBuiltinChargedJumpState::Jumping { .. })
+    }
+}
+
+#[derive(Default, Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinChargedJumpState {
+    #[default]
+    NoCharge,
+    /// The jump button is held, and the jump is being charged.
+    Charging {
+        elapsed: Float,
+        /// How much of the charge is done, from 0.0 to 1.0.
+        progress: Float,
+        crouch_state: TnuaBuiltinCrouchState,
+    },
+    /// The jump button was released, and the character is jumping.
+    Jumping {
+        /// The height decided by the charge.
+        height: Float,
+        jump_state: TnuaBuiltinJumpState,
+    },
+}
+
+impl TnuaBuiltinChargedJumpState {
+    /// How much of the charge is done, from 0.0 to 1.0 - or `None` if the jump is not being
+    /// charged.
+    pub fn charge_progress(&self) -> Option<Float> {
+        if let Self::Charging { progress, .. } = self {
+            Some(*progress)
+        } else {
+            None
+        }
+    }
+}
@@ -9,7 +9,8 @@ use crate::{
 };
 
 /// An [action](TnuaAction) for climbing on things.
-#[derive(Clone)]
+#[derive(Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinClimb {
     /// The entity being climbed on.
     pub climbable_entity: Option<Entity>,
@@ -181,7 +182,8 @@ impl TnuaAction for TnuaBuiltinClimb {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TnuaBuiltinClimbState {
     Climbing { climbing_velocity: Vector3 },
     Coyote(Timer),
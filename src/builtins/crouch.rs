@@ -22,7 +22,8 @@ use super::TnuaBuiltinWalk;
 /// upward toward the obstacle - which will bring about undesired physics behavior (especially if
 /// the player tries to move). To prevent that, use this action together with
 /// [`TnuaCrouchEnforcer`](crate::control_helpers::TnuaCrouchEnforcer).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinCrouch {
     /// Controls how low the character will crouch, compared to its regular float offset while
     /// standing.
@@ -180,7 +181,8 @@ impl TnuaBuiltinCrouch {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TnuaBuiltinCrouchState {
     /// The character is transitioning from standing to crouching.
     #[default]
@@ -0,0 +1,272 @@
+use crate::math::{AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::control_helpers::TnuaCrouchEnforcedAction;
+use crate::{TnuaAction, TnuaMotor, TnuaVelChange};
+use crate::{
+    TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus,
+};
+
+use super::TnuaBuiltinWalk;
+
+/// An [action](TnuaAction) for sliding on the ground while crouching. Only works when
+/// [`TnuaBuiltinWalk`] is the [basis](crate::TnuaBasis).
+///
+/// The slide can only start when the character is on the ground and moving horizontally at
+/// [`min_start_speed`](Self::min_start_speed) or faster. The character lowers its float height by
+/// [`float_offset`](Self::float_offset) (just like
+/// [`TnuaBuiltinCrouch`](crate::builtins::TnuaBuiltinCrouch) does) and keeps its horizontal
+/// momentum, which decays by [`friction`](Self::friction) and gets accelerated when sliding down
+/// slopes.
+///
+/// Once the character slows down below [`stop_speed`](Self::stop_speed), or when the action is no
+/// longer fed, the slide ends. If [`end_in_crouch`](Self::end_in_crouch) is set, the character
+/// stays crouched for as long as the action is still fed - otherwise it stands up.
+///
+/// Like [`TnuaBuiltinCrouch`](crate::builtins::TnuaBuiltinCrouch), this action can be used with
+/// [`TnuaCrouchEnforcer`](crate::control_helpers::TnuaCrouchEnforcer) to prevent the character
+/// from standing up at the end of a slide that ends under an obstacle.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinCrouchSlide {
+    /// Controls how low the character will slide, compared to its regular float offset while
+    /// standing.
+    ///
+    /// This field should typically have a negative value.
+    pub float_offset: Float,
+
+    /// A duration, in seconds, that it should take for the character to change its floating height
+    /// to start or stop the slide.
+    pub height_change_impulse_for_duration: Float,
+
+    /// The maximum impulse to apply when starting or stopping the slide.
+    pub height_change_impulse_limit: Float,
+
+    /// The slide will not start unless the character moves horizontally at least this fast.
+    pub min_start_speed: Float,
+
+    /// The deceleration of the slide on flat ground.
+    pub friction: Float,
+
+    /// How much of the gravity pulling the character down the slope is used for accelerating the
+    /// slide. With 0.0 slopes have no effect, and with 1.0 the character slides down slopes as if
+    /// there was no friction (other than [`friction`](Self::friction))
+    pub slope_acceleration: Float,
+
+    /// The slide cannot get faster than this, no matter how steep the slope is.
+    pub max_speed: Float,
+
+    /// When the slide gets slower than this, it ends.
+    pub stop_speed: Float,
+
+    /// If `true`, the character stays crouched after the slide ends, for as long as the action is
+    /// still fed. If `false`, the character stands up right after the slide ends.
+    pub end_in_crouch: bool,
+
+    /// If set to `true`, this action will not yield to other action who try to take control.
+    pub uncancellable: bool,
+}
+
+impl Default for TnuaBuiltinCrouchSlide {
+    fn default() -> Self {
+        Self {
+            float_offset: 0.0,
+            height_change_impulse_for_duration: 0.02,
+            height_change_impulse_limit: 40.0,
+            min_start_speed: 5.0,
+            friction: 4.0,
+            slope_acceleration: 1.0,
+            max_speed: 20.0,
+            stop_speed: 1.5,
+            end_in_crouch: false,
+            uncancellable: false,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinCrouchSlide {
+    const NAME: &'static str = "TnuaBuiltinCrouchSlide";
+    type State = TnuaBuiltinCrouchSlideState;
+    const VIOLATES_COYOTE_TIME: bool = false;
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        let Some(sensor_output) = &ctx.proximity_sensor.output else {
+            return TnuaActionInitiationDirective::Delay;
+        };
+        let horizontal_velocity = (ctx.tracker.velocity - sensor_output.entity_linvel)
+            .reject_from(ctx.up_direction.adjust_precision());
+        if horizontal_velocity.length() < self.min_start_speed {
+            TnuaActionInitiationDirective::Reject
+        } else {
+            TnuaActionInitiationDirective::Allow
+        }
+    }
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        let Some((walk_basis, walk_state)) = ctx.concrete_basis::<TnuaBuiltinWalk>() else {
+            error!("Cannot slide - basis is not TnuaBuiltinWalk");
+            return TnuaActionLifecycleDirective::Finished;
+        };
+        let Some(sensor_output) = &ctx.proximity_sensor.output else {
+            // Slid off a ledge
+            return TnuaActionLifecycleDirective::Finished;
+        };
+        let up = ctx.up_direction.adjust_precision();
+        let spring_offset_up = walk_basis.float_height - sensor_output.proximity.adjust_precision();
+        let spring_offset_down =
+            spring_offset_up.adjust_precision() + self.float_offset.adjust_precision();
+
+        match lifecycle_status {
+            TnuaActionLifecycleStatus::Initiated
+            | TnuaActionLifecycleStatus::CancelledFrom
+            | TnuaActionLifecycleStatus::StillFed => {}
+            TnuaActionLifecycleStatus::NoLongerFed => {
+                if !self.uncancellable {
+                    *state = TnuaBuiltinCrouchSlideState::Rising;
+                }
+            }
+            TnuaActionLifecycleStatus::CancelledInto => {
+                if !self.uncancellable {
+                    // Let the other action take over, with the slide's momentum.
+                    return TnuaActionLifecycleDirective::Finished;
+                }
+            }
+        }
+
+        let spring_force = |spring_offset: Float| -> TnuaVelChange {
+            walk_basis.spring_force(walk_state, &ctx.as_basis_context(), spring_offset)
+        };
+
+        let impulse_or_spring_force = |spring_offset: Float| -> TnuaVelChange {
+            let spring_force = spring_force(spring_offset);
+            let spring_force_boost = crate::util::calc_boost(&spring_force, ctx.frame_duration);
+            let impulse_boost = self.impulse_boost(spring_offset);
+            if spring_force_boost.length_squared() < impulse_boost.powi(2) {
+                TnuaVelChange::boost(impulse_boost * up)
+            } else {
+                spring_force
+            }
+        };
+
+        let stay_down = || {
+            if spring_offset_down < -0.01 {
+                impulse_or_spring_force(spring_offset_down)
+            } else {
+                spring_force(spring_offset_down)
+            }
+        };
+
+        // TODO: Once `std::mem::variant_count` gets stabilized, use that instead.
+        for _ in 0..3 {
+            return match state {
+                TnuaBuiltinCrouchSlideState::NoSlide => {
+                    *state = TnuaBuiltinCrouchSlideState::Sliding {
+                        velocity: (ctx.tracker.velocity - sensor_output.entity_linvel)
+                            .reject_from(up),
+                    };
+                    continue;
+                }
+                TnuaBuiltinCrouchSlideState::Sliding { velocity } => {
+                    let gravity_along_slope = ctx
+                        .tracker
+                        .gravity
+                        .reject_from(sensor_output.normal.adjust_precision())
+                        .reject_from(up);
+                    *velocity += self.slope_acceleration * gravity_along_slope * ctx.frame_duration;
+
+                    let speed = velocity.length();
+                    let speed_after_friction =
+                        (speed - self.friction * ctx.frame_duration).clamp(0.0, self.max_speed);
+                    *velocity = velocity.normalize_or_zero() * speed_after_friction;
+
+                    if speed_after_friction < self.stop_speed {
+                        *state = if self.end_in_crouch || self.uncancellable {
+                            TnuaBuiltinCrouchSlideState::Crouching
+                        } else {
+                            TnuaBuiltinCrouchSlideState::Rising
+                        };
+                        continue;
+                    }
+
+                    let desired_horizontal_velocity =
+                        *velocity + sensor_output.entity_linvel.reject_from(up);
+                    motor.lin = TnuaVelChange {
+                        acceleration: Vector3::ZERO,
+                        boost: desired_horizontal_velocity - ctx.tracker.velocity.reject_from(up),
+                    } + stay_down();
+                    TnuaActionLifecycleDirective::StillActive
+                }
+                TnuaBuiltinCrouchSlideState::Crouching => {
+                    motor.lin.cancel_on_axis(up);
+                    motor.lin += stay_down();
+                    // If it's finished/cancelled, something else should changed its state
+                    TnuaActionLifecycleDirective::StillActive
+                }
+                TnuaBuiltinCrouchSlideState::Rising => {
+                    if self.uncancellable {
+                        // The crouch is being enforced - probably because of an obstacle above.
+                        *state = TnuaBuiltinCrouchSlideState::Crouching;
+                        continue;
+                    }
+                    if 0.01 < spring_offset_up {
+                        motor.lin.cancel_on_axis(up);
+                        motor.lin += impulse_or_spring_force(spring_offset_up);
+                        TnuaActionLifecycleDirective::StillActive
+                    } else {
+                        TnuaActionLifecycleDirective::Finished
+                    }
+                }
+            };
+        }
+        error!("Tnua could not decide on crouch slide state");
+        TnuaActionLifecycleDirective::Finished
+    }
+}
+
+impl TnuaBuiltinCrouchSlide {
+    fn impulse_boost(&self, spring_offset: Float) -> Float {
+        let velocity_to_get_to_new_float_height =
+            spring_offset / self.height_change_impulse_for_duration;
+        velocity_to_get_to_new_float_height.clamp(
+            -self.height_change_impulse_limit,
+            self.height_change_impulse_limit,
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinCrouchSlideState {
+    #[default]
+    NoSlide,
+    /// The character is sliding.
+    Sliding {
+        /// The horizontal velocity of the slide, relative to the ground.
+        velocity: Vector3,
+    },
+    /// The slide is over, and the character stays crouched.
+    Crouching,
+    /// The character is transitioning from crouching to standing.
+    Rising,
+}
+
+impl TnuaCrouchEnforcedAction for TnuaBuiltinCrouchSlide {
+    fn range_to_cast_up(&self, _state: &Self::State) -> Float {
+        -self.float_offset
+    }
+
+    fn prevent_cancellation(&mut self) {
+        self.uncancellable = true;
+    }
+}
@@ -8,7 +8,8 @@ use crate::{
 };
 
 /// The basic dash [action](TnuaAction).
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinDash {
     /// The direction and distance of the dash.
     ///
@@ -168,7 +169,8 @@ impl TnuaAction for TnuaBuiltinDash {
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TnuaBuiltinDashState {
     #[default]
     PreDash,
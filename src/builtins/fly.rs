@@ -0,0 +1,159 @@
+use crate::math::{float_consts, AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::{calc_angular_velchange_to_stay_upright, MotionHelper};
+use crate::TnuaBasis;
+use crate::TnuaBasisContext;
+
+/// A [basis](TnuaBasis) for flying freely, ignoring the ground.
+///
+/// This can be used for things like debug noclip, jetpacks and flying enemies. The gravity is
+/// negated, and the character accelerates toward the [`desired_velocity`](Self::desired_velocity)
+/// in all directions.
+///
+/// The character is always considered airborne, so actions that need the ground (like
+/// [`TnuaBuiltinJump`](crate::builtins::TnuaBuiltinJump) without `allow_in_air`) will not start
+/// while flying. Actions that can be done in the air (like
+/// [`TnuaBuiltinDash`](crate::builtins::TnuaBuiltinDash) with `allow_in_air`, or
+/// [`TnuaBuiltinKnockback`](crate::builtins::TnuaBuiltinKnockback)) work as usual.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinFly {
+    /// The direction (in the world space) and speed to accelerate to.
+    ///
+    /// Unlike with [`TnuaBuiltinWalk`](crate::builtins::TnuaBuiltinWalk), this vector does not
+    /// need to be orthogonal to the up direction.
+    pub desired_velocity: Vector3,
+
+    /// If non-zero, Tnua will rotate the character so that its negative Z will face in that
+    /// direction.
+    ///
+    /// Tnua assumes that this vector is orthogonal to the up direction.
+    pub desired_forward: Option<Dir3>,
+
+    /// The acceleration used when the desired speed is higher than the current speed.
+    pub acceleration: Float,
+
+    /// The acceleration used when the desired speed is lower than the current speed (including
+    /// when stopping)
+    pub deceleration: Float,
+
+    /// The height of the hover bobbing (from its center to its top). Set to 0.0 to disable it.
+    ///
+    /// The bobbing is added on top of the `desired_velocity`.
+    pub hover_bob_amplitude: Float,
+
+    /// The duration, in seconds, of a full cycle of the hover bobbing.
+    pub hover_bob_period: Float,
+
+    /// The maximum angular velocity used for keeping the character standing upright.
+    ///
+    /// NOTE: The character's rotation can also be locked to prevent it from being tilted, in which
+    /// case this paramter is redundant and can be set to 0.0.
+    pub tilt_offset_angvel: Float,
+
+    /// The maximum angular acceleration used for reaching `tilt_offset_angvel`.
+    ///
+    /// NOTE: The character's rotation can also be locked to prevent it from being tilted, in which
+    /// case this paramter is redundant and can be set to 0.0.
+    pub tilt_offset_angacl: Float,
+
+    /// The maximum angular velocity used for turning the character when the direction changes.
+    pub turning_angvel: Float,
+}
+
+impl Default for TnuaBuiltinFly {
+    fn default() -> Self {
+        Self {
+            desired_velocity: Vector3::ZERO,
+            desired_forward: None,
+            acceleration: 30.0,
+            deceleration: 40.0,
+            hover_bob_amplitude: 0.0,
+            hover_bob_period: 2.0,
+            tilt_offset_angvel: 5.0,
+            tilt_offset_angacl: 500.0,
+            turning_angvel: 10.0,
+        }
+    }
+}
+
+impl TnuaBasis for TnuaBuiltinFly {
+    const NAME: &'static str = "TnuaBuiltinFly";
+    type State = TnuaBuiltinFlyState;
+
+    fn apply(&self, state: &mut Self::State, ctx: TnuaBasisContext, motor: &mut crate::TnuaMotor) {
+        state.effective_velocity = ctx.tracker.velocity;
+
+        let bob_velocity = if 0.0 < self.hover_bob_amplitude && 0.0 < self.hover_bob_period {
+            state.hover_time = (state.hover_time + ctx.frame_duration) % self.hover_bob_period;
+            let angular_frequency = float_consts::TAU / self.hover_bob_period;
+            self.hover_bob_amplitude
+                * angular_frequency
+                * (angular_frequency * state.hover_time).cos()
+                * ctx.up_direction.adjust_precision()
+        } else {
+            state.hover_time = 0.0;
+            Vector3::ZERO
+        };
+
+        let target_velocity = self.desired_velocity + bob_velocity;
+        let acceleration =
+            if state.effective_velocity.length_squared() < target_velocity.length_squared() {
+                self.acceleration
+            } else {
+                self.deceleration
+            };
+
+        let steering = ctx.adjust_velocity(target_velocity, acceleration, |v| v);
+        state.flying_velocity =
+            state.effective_velocity + steering.calc_mean_boost(ctx.frame_duration);
+        motor.lin = ctx.negate_gravity() + steering;
+
+        motor.ang = calc_angular_velchange_to_stay_upright(
+            ctx.tracker,
+            ctx.up_direction,
+            self.desired_forward,
+            ctx.frame_duration,
+            self.tilt_offset_angvel,
+            self.tilt_offset_angacl,
+            self.turning_angvel,
+        );
+    }
+
+    fn proximity_sensor_cast_range(&self, _state: &Self::State) -> Float {
+        0.0
+    }
+
+    fn displacement(&self, _state: &Self::State) -> Option<Vector3> {
+        None
+    }
+
+    fn effective_velocity(&self, state: &Self::State) -> Vector3 {
+        state.effective_velocity
+    }
+
+    fn vertical_velocity(&self, _state: &Self::State) -> Float {
+        0.0
+    }
+
+    fn neutralize(&mut self) {
+        self.desired_velocity = Vector3::ZERO;
+        self.desired_forward = None;
+    }
+
+    fn is_airborne(&self, _state: &Self::State) -> bool {
+        true
+    }
+
+    fn violate_coyote_time(&self, _state: &mut Self::State) {}
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinFlyState {
+    effective_velocity: Vector3,
+    hover_time: Float,
+    /// The velocity the character is supposed to fly at after this frame's motor is applied.
+    pub flying_velocity: Vector3,
+}
@@ -0,0 +1,187 @@
+use crate::math::{AdjustPrecision, AsF32, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::MotionHelper;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+/// An [action](TnuaAction) for gliding (or parachuting) down.
+///
+/// While gliding, the character's fall speed is capped at
+/// [`max_fall_speed`](Self::max_fall_speed), and the fall speed that gets removed by the cap is
+/// partially converted into forward speed. The character always moves forward in its heading,
+/// which turns toward the [`desired_heading`](Self::desired_heading) at a limited
+/// [`turn_rate`](Self::turn_rate) - so steering is gradual rather than instant.
+///
+/// The action can only start while the character is airborne, and it ends when the character
+/// lands. To toggle the glide on and off mid-air, simply start and stop feeding it. Since gliding
+/// is an air action, every time it starts it is reported by
+/// [`TnuaAirActionsTracker`](crate::control_helpers::TnuaAirActionsTracker) - so games that want
+/// to limit the number of glides per jump can use
+/// [`TnuaSimpleAirActionsCounter`](crate::control_helpers::TnuaSimpleAirActionsCounter) for that.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinGlide {
+    /// The direction to steer the glide toward.
+    ///
+    /// Only the horizontal part of this vector is used, so it's okay to pass the raw movement input
+    /// here. Set to zero to keep gliding in the current heading.
+    pub desired_heading: Vector3,
+
+    /// The maximum speed, in radians per second, the heading can turn at.
+    pub turn_rate: Float,
+
+    /// The maximum speed the character can fall at while gliding.
+    pub max_fall_speed: Float,
+
+    /// The maximum acceleration used for slowing down the fall to
+    /// [`max_fall_speed`](Self::max_fall_speed).
+    ///
+    /// Should be stronger than the gravity - otherwise the fall speed cannot be capped.
+    pub fall_deceleration: Float,
+
+    /// How much of the fall speed removed by the cap is converted into forward speed.
+    pub fall_to_forward_ratio: Float,
+
+    /// The forward speed the glide converges to when there is no fall speed to convert.
+    pub cruise_speed: Float,
+
+    /// The acceleration used for converging to the [`cruise_speed`](Self::cruise_speed).
+    pub cruise_acceleration: Float,
+
+    /// The forward speed cannot go above this, no matter how much fall speed gets converted.
+    pub max_forward_speed: Float,
+}
+
+impl Default for TnuaBuiltinGlide {
+    fn default() -> Self {
+        Self {
+            desired_heading: Vector3::ZERO,
+            turn_rate: 2.0,
+            max_fall_speed: 2.0,
+            fall_deceleration: 40.0,
+            fall_to_forward_ratio: 0.5,
+            cruise_speed: 6.0,
+            cruise_acceleration: 3.0,
+            max_forward_speed: 15.0,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinGlide {
+    const NAME: &'static str = "TnuaBuiltinGlide";
+    type State = TnuaBuiltinGlideState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if !lifecycle_status.is_active() {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        let up = ctx.up_direction.adjust_precision();
+        let horizontal_velocity = ctx.tracker.velocity.reject_from(up);
+
+        let is_first_frame = matches!(state, TnuaBuiltinGlideState::NoGlide);
+        if is_first_frame {
+            let heading = Dir3::new(horizontal_velocity.f32())
+                .or_else(|_| {
+                    Dir3::new(
+                        (ctx.tracker.rotation.f32() * Vec3::NEG_Z).reject_from(*ctx.up_direction),
+                    )
+                })
+                .unwrap_or(Dir3::NEG_Z);
+            *state = TnuaBuiltinGlideState::Gliding {
+                heading,
+                forward_speed: horizontal_velocity.dot(heading.adjust_precision()),
+                fall_speed: -ctx.tracker.velocity.dot(up),
+            };
+        } else if !ctx.basis.is_airborne() {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        let TnuaBuiltinGlideState::Gliding {
+            heading,
+            forward_speed,
+            fall_speed,
+        } = state
+        else {
+            unreachable!("state was set to Gliding above");
+        };
+
+        if let Ok(desired_heading) =
+            Dir3::new(self.desired_heading.f32().reject_from(*ctx.up_direction))
+        {
+            let max_turn = (self.turn_rate * ctx.frame_duration).f32();
+            let turn = heading.angle_between(*desired_heading).min(max_turn);
+            let turn_sign = heading
+                .cross(*desired_heading)
+                .dot(*ctx.up_direction)
+                .signum();
+            *heading = Quat::from_axis_angle(*ctx.up_direction, turn_sign * turn) * *heading;
+        }
+
+        // Where the fall speed is going to be at the end of the frame if the glide does not
+        // intervene.
+        let current_fall_speed = -ctx.tracker.velocity.dot(up);
+        let predicted_fall_speed =
+            current_fall_speed - ctx.tracker.gravity.dot(up) * ctx.frame_duration;
+        let fall_braking = ((predicted_fall_speed - self.max_fall_speed) / ctx.frame_duration)
+            .clamp(0.0, self.fall_deceleration);
+        *fall_speed = predicted_fall_speed - fall_braking * ctx.frame_duration;
+
+        let converted_speed = (current_fall_speed - *fall_speed).max(0.0);
+        let max_cruise_change = self.cruise_acceleration * ctx.frame_duration;
+        let cruise_change =
+            (self.cruise_speed - *forward_speed).clamp(-max_cruise_change, max_cruise_change);
+        *forward_speed =
+            (*forward_speed + cruise_change + self.fall_to_forward_ratio * converted_speed)
+                .min(self.max_forward_speed);
+
+        motor.lin = TnuaVelChange {
+            acceleration: fall_braking * up,
+            boost: *forward_speed * heading.adjust_precision() - horizontal_velocity,
+        };
+
+        motor
+            .ang
+            .cancel_on_axis(ctx.up_direction.adjust_precision());
+        motor.ang += ctx.turn_to_direction(*heading, ctx.up_direction);
+
+        TnuaActionLifecycleDirective::StillActive
+    }
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if ctx.basis.is_airborne() {
+            TnuaActionInitiationDirective::Allow
+        } else {
+            TnuaActionInitiationDirective::Delay
+        }
+    }
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinGlideState {
+    #[default]
+    NoGlide,
+    Gliding {
+        /// The horizontal direction the character glides in.
+        heading: Dir3,
+        /// The speed the character glides at in the `heading` direction.
+        forward_speed: Float,
+        /// The speed the character falls at.
+        fall_speed: Float,
+    },
+}
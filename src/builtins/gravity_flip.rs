@@ -0,0 +1,292 @@
+use crate::math::{float_consts, AdjustPrecision, AsF32, Float, Quaternion, Vector3};
+use bevy::prelude::*;
+
+use crate::controller::TnuaController;
+use crate::util::calc_angular_velchange_to_stay_upright;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaGravity, TnuaMotor,
+};
+
+/// An [action](TnuaAction) for changing the direction of the character's gravity - e.g. for
+/// walking on ceilings.
+///
+/// The gravity is changed through the [`TnuaGravity`] component of the character (which will be
+/// added if the character does not have it), and stays changed after the action finishes. During
+/// the [`transition_duration`](Self::transition_duration) the gravity is rotated from its old
+/// direction to its new one, and the character is rotated to match the new up direction.
+///
+/// While the flip is in progress, the basis will not land and will not have coyote time. The
+/// action finishes once the gravity transition is over and the character is upright relative to
+/// the new gravity - even if it is no longer fed or another action tries to cancel it, so that the
+/// gravity will not be left midway. Everything the flip needs is kept in its
+/// [state](TnuaBuiltinGravityFlipState), so it can also be resumed from a
+/// [snapshot](crate::controller::TnuaController::snapshot).
+///
+/// Note that this action should not be combined with
+/// [`TnuaAffectedByGravitySources`](crate::control_helpers::TnuaAffectedByGravitySources), since
+/// the gravity sources will override the gravity set by the flip.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinGravityFlip {
+    /// The gravity to switch to. When `None`, the current gravity is inverted.
+    ///
+    /// This input parameter is cached when the action starts.
+    pub new_gravity: Option<Vector3>,
+
+    /// The time, in seconds, it takes to rotate the gravity from its old direction to the new
+    /// one. Set to 0.0 to switch the gravity instantly (the character's rotation will still be
+    /// gradual)
+    pub transition_duration: Float,
+
+    /// Allow this action to start even if the character is not touching ground nor in coyote time.
+    pub allow_in_air: bool,
+
+    /// The maximum angular velocity used for rotating the character to the new up direction.
+    ///
+    /// This is used instead of the basis' `tilt_offset_angvel` while the flip is in progress, and
+    /// should usually be higher than it.
+    pub tilt_offset_angvel: Float,
+
+    /// The maximum angular acceleration used for reaching `tilt_offset_angvel`.
+    pub tilt_offset_angacl: Float,
+
+    /// The action will not finish until the angle, in radians, between the character's up and the
+    /// new up direction is below this value.
+    ///
+    /// If the character's rotation is locked, set this to [`PI`](std::f32::consts::PI) so that
+    /// the action will finish right after the gravity transition.
+    pub upright_tolerance: Float,
+}
+
+impl Default for TnuaBuiltinGravityFlip {
+    fn default() -> Self {
+        Self {
+            new_gravity: None,
+            transition_duration: 0.3,
+            allow_in_air: false,
+            tilt_offset_angvel: 15.0,
+            tilt_offset_angacl: 1000.0,
+            upright_tolerance: 0.1,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinGravityFlip {
+    const NAME: &'static str = "TnuaBuiltinGravityFlip";
+    type State = TnuaBuiltinGravityFlipState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        _lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        let character_up = ctx.tracker.rotation.mul_vec3(Vector3::Y);
+
+        if let TnuaBuiltinGravityFlipState::NoFlip = state {
+            let from_gravity = ctx.tracker.gravity;
+            let to_gravity = self.new_gravity.unwrap_or(-from_gravity);
+            let from_up = -from_gravity.normalize_or_zero();
+            let to_up = -to_gravity.normalize_or_zero();
+            let axis = from_up.cross(to_up).normalize_or_zero();
+            let axis = if axis != Vector3::ZERO {
+                axis
+            } else {
+                // A full flip - rotate around the character's Z axis, which is also the rotation
+                // axis in 2D.
+                let axis = ctx
+                    .tracker
+                    .rotation
+                    .mul_vec3(Vector3::Z)
+                    .reject_from(from_up)
+                    .normalize_or_zero();
+                if axis != Vector3::ZERO {
+                    axis
+                } else {
+                    from_up.any_orthonormal_vector()
+                }
+            };
+            *state = TnuaBuiltinGravityFlipState::Flipping {
+                from_gravity,
+                to_gravity,
+                axis,
+                elapsed: 0.0,
+                gravity_progress: 0.0,
+                rotation_progress: 0.0,
+            };
+        }
+
+        let TnuaBuiltinGravityFlipState::Flipping {
+            from_gravity,
+            to_gravity,
+            axis,
+            elapsed,
+            gravity_progress,
+            rotation_progress,
+        } = state
+        else {
+            unreachable!("state was set to Flipping above");
+        };
+
+        // Only finish on a frame where the gravity was already fully changed, so that the
+        // change gets applied before the action ends.
+        let gravity_transition_was_over = 1.0 <= *gravity_progress;
+
+        *elapsed += ctx.frame_duration;
+        *gravity_progress = if 0.0 < self.transition_duration {
+            (*elapsed / self.transition_duration).min(1.0)
+        } else {
+            1.0
+        };
+
+        let from_up = -from_gravity.normalize_or_zero();
+        let to_up = -to_gravity.normalize_or_zero();
+        let total_angle = from_up.angle_between(to_up);
+        let remaining_angle = character_up.angle_between(to_up);
+        *rotation_progress = if 0.0 < total_angle {
+            (1.0 - remaining_angle / total_angle).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        if gravity_transition_was_over && remaining_angle <= self.upright_tolerance {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        // Rotate toward the current up direction, but never by more than a quarter turn at a time
+        // so that the tilt correction will not pick its own axis for a full flip.
+        let current_up = -TnuaBuiltinGravityFlipState::gravity_at_progress(
+            *from_gravity,
+            *to_gravity,
+            *axis,
+            *gravity_progress,
+        )
+        .normalize_or_zero();
+        let target_up = if float_consts::FRAC_PI_2 < character_up.angle_between(current_up) {
+            Quaternion::from_axis_angle(*axis, float_consts::FRAC_PI_2).mul_vec3(character_up)
+        } else {
+            current_up
+        };
+
+        // Let the character fall freely toward the new gravity.
+        motor
+            .lin
+            .cancel_on_axis(ctx.up_direction.adjust_precision());
+        if let Ok(target_up) = Dir3::new(target_up.f32()) {
+            motor.ang = calc_angular_velchange_to_stay_upright(
+                ctx.tracker,
+                target_up,
+                None,
+                ctx.frame_duration,
+                self.tilt_offset_angvel,
+                self.tilt_offset_angacl,
+                0.0,
+            );
+        }
+
+        TnuaActionLifecycleDirective::StillActive
+    }
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        let new_gravity = self.new_gravity.unwrap_or(-ctx.tracker.gravity);
+        if !new_gravity.is_finite()
+            || new_gravity == Vector3::ZERO
+            || new_gravity == ctx.tracker.gravity
+        {
+            TnuaActionInitiationDirective::Reject
+        } else if self.allow_in_air || !ctx.basis.is_airborne() {
+            TnuaActionInitiationDirective::Allow
+        } else {
+            TnuaActionInitiationDirective::Reject
+        }
+    }
+
+    fn suppresses_landing(&self, state: &Self::State) -> bool {
+        matches!(state, TnuaBuiltinGravityFlipState::Flipping { .. })
+    }
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinGravityFlipState {
+    #[default]
+    NoFlip,
+    Flipping {
+        from_gravity: Vector3,
+        to_gravity: Vector3,
+        /// The axis the gravity and the character rotate around.
+        axis: Vector3,
+        elapsed: Float,
+        /// How much of the gravity transition is done, from 0.0 to 1.0.
+        gravity_progress: Float,
+        /// How much of the character's rotation to the new up direction is done, from 0.0 to 1.0.
+        ///
+        /// This is the value to use for animating the flip.
+        rotation_progress: Float,
+    },
+}
+
+impl TnuaBuiltinGravityFlipState {
+    /// The gravity the flip applies to the character, or `None` if the flip did not start yet.
+    pub fn current_gravity(&self) -> Option<Vector3> {
+        match self {
+            TnuaBuiltinGravityFlipState::NoFlip => None,
+            TnuaBuiltinGravityFlipState::Flipping {
+                from_gravity,
+                to_gravity,
+                axis,
+                gravity_progress,
+                ..
+            } => Some(Self::gravity_at_progress(
+                *from_gravity,
+                *to_gravity,
+                *axis,
+                *gravity_progress,
+            )),
+        }
+    }
+
+    fn gravity_at_progress(
+        from_gravity: Vector3,
+        to_gravity: Vector3,
+        axis: Vector3,
+        progress: Float,
+    ) -> Vector3 {
+        let from_direction = from_gravity.normalize_or_zero();
+        let angle = from_direction.angle_between(to_gravity.normalize_or_zero());
+        let direction =
+            Quaternion::from_axis_angle(axis, angle * progress).mul_vec3(from_direction);
+        let magnitude =
+            from_gravity.length() + (to_gravity.length() - from_gravity.length()) * progress;
+        direction * magnitude
+    }
+}
+
+pub(crate) fn apply_gravity_flips_system(
+    mut query: Query<(Entity, &TnuaController, Option<&mut TnuaGravity>)>,
+    mut commands: Commands,
+) {
+    for (entity, controller, gravity) in query.iter_mut() {
+        // The action only finishes on a frame after its gravity transition was over, so the
+        // gravity is already complete by the time the action is gone.
+        let Some(new_gravity) = controller
+            .iter_action_layers()
+            .find_map(|(_, layer)| layer.concrete_action::<TnuaBuiltinGravityFlip>())
+            .and_then(|(_, state)| state.current_gravity())
+        else {
+            continue;
+        };
+        if let Some(mut gravity) = gravity {
+            gravity.0 = new_gravity;
+        } else {
+            commands.entity(entity).insert(TnuaGravity(new_gravity));
+        }
+    }
+}
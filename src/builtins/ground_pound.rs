@@ -0,0 +1,244 @@
+use crate::math::{AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::MotionHelper;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+/// An [action](TnuaAction) for slamming down to the ground from the air.
+///
+/// When the action starts, the character's upward velocity is cancelled and it hovers in place
+/// for [`windup_duration`](Self::windup_duration) seconds. Then it slams straight down at
+/// [`slam_speed`](Self::slam_speed) until it hits something, and then stays in place for
+/// [`recovery_duration`](Self::recovery_duration) seconds before the action finishes. Once
+/// started, the action continues until the end of the recovery even if it is no longer fed.
+///
+/// The impact is reported in the [state](TnuaBuiltinGroundPoundState::impact_report) on the frame
+/// it happens, and with the [`TnuaBuiltinGroundPoundImpacted`] event - so that the game can break
+/// whatever the character landed on or damage it.
+///
+/// The ground pound is an air action, so it will be counted by
+/// [`TnuaSimpleAirActionsCounter`](crate::control_helpers::TnuaSimpleAirActionsCounter), and the
+/// air actions count resets once the character lands.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinGroundPound {
+    /// The duration, in seconds, of hovering in the air before slamming down.
+    pub windup_duration: Float,
+
+    /// The speed to slam down at.
+    pub slam_speed: Float,
+
+    /// The acceleration used for reaching the [`slam_speed`](Self::slam_speed).
+    pub slam_acceleration: Float,
+
+    /// The duration, in seconds, of staying in place after the impact.
+    pub recovery_duration: Float,
+}
+
+impl Default for TnuaBuiltinGroundPound {
+    fn default() -> Self {
+        Self {
+            windup_duration: 0.15,
+            slam_speed: 25.0,
+            slam_acceleration: 200.0,
+            recovery_duration: 0.25,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinGroundPound {
+    const NAME: &'static str = "TnuaBuiltinGroundPound";
+    type State = TnuaBuiltinGroundPoundState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if matches!(lifecycle_status, TnuaActionLifecycleStatus::CancelledInto) {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        let up = ctx.up_direction.adjust_precision();
+
+        // TODO: Once `std::mem::variant_count` gets stabilized, use that instead.
+        for _ in 0..3 {
+            return match state {
+                TnuaBuiltinGroundPoundState::NoPound => {
+                    *state = TnuaBuiltinGroundPoundState::Windup { elapsed: 0.0 };
+                    continue;
+                }
+                TnuaBuiltinGroundPoundState::Windup { elapsed } => {
+                    *elapsed += ctx.frame_duration;
+                    if self.windup_duration <= *elapsed {
+                        *state = TnuaBuiltinGroundPoundState::Slamming;
+                        continue;
+                    }
+                    motor.lin = ctx.negate_gravity() + TnuaVelChange::boost(-ctx.tracker.velocity);
+                    TnuaActionLifecycleDirective::StillActive
+                }
+                TnuaBuiltinGroundPoundState::Slamming => {
+                    if !ctx.basis.is_airborne() {
+                        let Some(sensor_output) = &ctx.proximity_sensor.output else {
+                            // The basis is grounded without anything under it - probably not
+                            // because of the ground pound.
+                            return TnuaActionLifecycleDirective::Finished;
+                        };
+                        *state = TnuaBuiltinGroundPoundState::Recovering {
+                            impact: TnuaBuiltinGroundPoundImpact {
+                                point: ctx.tracker.translation
+                                    + ctx.tracker.rotation
+                                        * ctx.proximity_sensor.cast_origin.adjust_precision()
+                                    + sensor_output.proximity
+                                        * ctx.proximity_sensor.cast_direction.adjust_precision(),
+                                entity: sensor_output.entity,
+                                speed: -(ctx.tracker.velocity - sensor_output.entity_linvel)
+                                    .dot(up),
+                                normal: sensor_output.normal,
+                            },
+                            elapsed: 0.0,
+                            just_impacted: true,
+                        };
+                        hold_in_place(&ctx, motor);
+                        return TnuaActionLifecycleDirective::StillActive;
+                    }
+                    let upward_speed = ctx.tracker.velocity.dot(up).max(0.0);
+                    motor.lin = ctx.negate_gravity()
+                        + TnuaVelChange::boost(-upward_speed * up)
+                        + ctx.adjust_vertical_velocity(-self.slam_speed, self.slam_acceleration)
+                        + TnuaVelChange::boost(-ctx.tracker.velocity.reject_from(up));
+                    TnuaActionLifecycleDirective::StillActive
+                }
+                TnuaBuiltinGroundPoundState::Recovering {
+                    impact: _,
+                    elapsed,
+                    just_impacted,
+                } => {
+                    *just_impacted = false;
+                    *elapsed += ctx.frame_duration;
+                    if self.recovery_duration <= *elapsed {
+                        return TnuaActionLifecycleDirective::Finished;
+                    }
+                    hold_in_place(&ctx, motor);
+                    TnuaActionLifecycleDirective::StillActive
+                }
+            };
+        }
+        error!("Tnua could not decide on ground pound state");
+        TnuaActionLifecycleDirective::Finished
+    }
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if ctx.basis.is_airborne() {
+            TnuaActionInitiationDirective::Allow
+        } else {
+            TnuaActionInitiationDirective::Reject
+        }
+    }
+}
+
+/// Let the basis handle the landing, but don't let it move the character.
+fn hold_in_place(ctx: &TnuaActionContext, motor: &mut TnuaMotor) {
+    let up = ctx.up_direction.adjust_precision();
+    motor.lin = motor.lin.project_onto_normalized(up)
+        + TnuaVelChange::boost(-ctx.tracker.velocity.reject_from(up));
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinGroundPoundState {
+    #[default]
+    NoPound,
+    /// Hovering in the air before the slam.
+    Windup { elapsed: Float },
+    /// Slamming down.
+    Slamming,
+    /// Staying in place after the impact.
+    Recovering {
+        impact: TnuaBuiltinGroundPoundImpact,
+        /// The time, in seconds, since the impact.
+        elapsed: Float,
+        /// Whether the impact happened on the last frame the action was applied.
+        just_impacted: bool,
+    },
+}
+
+impl TnuaBuiltinGroundPoundState {
+    /// Information about the impact - but only on the frame the impact happened.
+    ///
+    /// For the rest of the recovery, the impact information is still available in the
+    /// [`Recovering`](Self::Recovering) variant.
+    ///
+    /// The report stays when the controller is not applied, so it should only be acted upon when
+    /// [`TnuaController::applied_this_frame`] is `true`.
+    pub fn impact_report(&self) -> Option<&TnuaBuiltinGroundPoundImpact> {
+        if let Self::Recovering {
+            impact,
+            just_impacted: true,
+            ..
+        } = self
+        {
+            return Some(impact);
+        }
+        None
+    }
+}
+
+/// Information about a ground pound hitting something, recorded by [`TnuaBuiltinGroundPound`].
+///
+/// See [`TnuaBuiltinGroundPoundState::impact_report`] and [`TnuaBuiltinGroundPoundImpacted`].
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinGroundPoundImpact {
+    /// The point where the proximity sensor touched the struck entity.
+    pub point: Vector3,
+    /// The entity the character slammed into.
+    pub entity: Entity,
+    /// The speed, relative to the struck entity, the character slammed into it at.
+    pub speed: Float,
+    /// The normal of the struck entity's surface at the [`point`](Self::point).
+    pub normal: Dir3,
+}
+
+/// An event sent when a character performing a [`TnuaBuiltinGroundPound`] hits something.
+///
+/// It is sent by [`TnuaControllerPlugin`](crate::prelude::TnuaControllerPlugin) on the same frame
+/// [`TnuaBuiltinGroundPoundState::impact_report`] returns the report.
+#[derive(Event, Debug, Clone)]
+pub struct TnuaBuiltinGroundPoundImpacted {
+    /// The character entity.
+    pub entity: Entity,
+    pub impact: TnuaBuiltinGroundPoundImpact,
+}
+
+pub(crate) fn send_ground_pound_impact_events(
+    query: Query<(Entity, &TnuaController)>,
+    mut writer: EventWriter<TnuaBuiltinGroundPoundImpacted>,
+) {
+    for (entity, controller) in query.iter() {
+        if !controller.applied_this_frame() {
+            continue;
+        }
+        for (_, layer) in controller.iter_action_layers() {
+            let Some((_, state)) = layer.concrete_action::<TnuaBuiltinGroundPound>() else {
+                continue;
+            };
+            if let Some(impact) = state.impact_report() {
+                writer.write(TnuaBuiltinGroundPoundImpacted {
+                    entity,
+                    impact: impact.clone(),
+                });
+            }
+        }
+    }
+}
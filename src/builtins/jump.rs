@@ -4,13 +4,15 @@ use bevy::time::Stopwatch;
 
 use crate::util::{
     calc_angular_velchange_to_force_forward, SegmentedJumpDurationCalculator,
-    SegmentedJumpInitialVelocityCalculator, VelocityBoundary,
+    SegmentedJumpInitialVelocityCalculator, SegmentedJumpTrajectory, VelocityBoundary,
 };
 use crate::{
     TnuaAction, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
     TnuaActionLifecycleStatus,
 };
 
+use super::TnuaBuiltinWalkState;
+
 /// The basic jump [action](TnuaAction).
 ///
 /// This action implements jump physics explained in <https://youtu.be/hG9SzQxaCm8> and
@@ -21,7 +23,8 @@ use crate::{
 /// being fed, it'll apply extra gravity to shorten the jump. If the game desires fixed height
 /// jumps instead (where the player cannot make lower jumps by tapping the jump button)
 /// [`shorten_extra_gravity`](Self::shorten_extra_gravity) should be set to `0.0`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinJump {
     /// The height the character will jump to.
     ///
@@ -168,20 +171,9 @@ impl TnuaAction for TnuaBuiltinJump {
         let up = ctx.up_direction.adjust_precision();
 
         if lifecycle_status.just_started() {
-            let mut calculator = SegmentedJumpInitialVelocityCalculator::new(self.height);
-            let gravity = ctx.tracker.gravity.dot(-up);
-            let kinetic_energy = calculator
-                .add_segment(
-                    gravity + self.peak_prevention_extra_gravity,
-                    self.peak_prevention_at_upward_velocity,
-                )
-                .add_segment(gravity, self.takeoff_above_velocity)
-                .add_final_segment(gravity + self.takeoff_extra_gravity)
-                .kinetic_energy()
-                .expect("`add_final_segment` should have covered remaining height");
             *state = TnuaBuiltinJumpState::StartingJump {
                 origin: ctx.tracker.translation,
-                desired_energy: kinetic_energy,
+                desired_energy: self.takeoff_kinetic_energy(ctx.tracker.gravity.dot(-up)),
             };
         }
 
@@ -245,7 +237,8 @@ impl TnuaAction for TnuaBuiltinJump {
                         *state = TnuaBuiltinJumpState::SlowDownTooFastSlopeJump {
                             origin: *origin,
                             desired_energy: *desired_energy,
-                            zero_potential_energy_at: ctx.tracker.translation - extra_height * up,
+                            extra_height,
+                            last_position: ctx.tracker.translation,
                         };
                     }
                     self.directive_simple_or_reschedule(lifecycle_status)
@@ -253,7 +246,8 @@ impl TnuaAction for TnuaBuiltinJump {
                 TnuaBuiltinJumpState::SlowDownTooFastSlopeJump {
                     origin,
                     desired_energy,
-                    zero_potential_energy_at,
+                    extra_height,
+                    last_position,
                 } => {
                     let upward_velocity = up.dot(effective_velocity);
                     if upward_velocity <= ctx.basis.vertical_velocity() {
@@ -264,10 +258,12 @@ impl TnuaAction for TnuaBuiltinJump {
                         continue;
                     }
                     let relative_velocity = effective_velocity.dot(up);
-                    let extra_height =
-                        (ctx.tracker.translation - *zero_potential_energy_at).dot(up);
+                    // Integrated frame by frame (instead of measured from a fixed point) so that
+                    // it stays coherent when the up direction changes mid-jump.
+                    *extra_height += (ctx.tracker.translation - *last_position).dot(up);
+                    *last_position = ctx.tracker.translation;
                     let gravity = ctx.tracker.gravity.dot(-up);
-                    let energy_from_extra_height = extra_height * gravity;
+                    let energy_from_extra_height = *extra_height * gravity;
                     let desired_kinetic_energy = *desired_energy - energy_from_extra_height;
                     let desired_upward_velocity =
                         SegmentedJumpInitialVelocityCalculator::kinetic_energy_to_velocity(
@@ -422,6 +418,118 @@ impl TnuaAction for TnuaBuiltinJump {
 }
 
 impl TnuaBuiltinJump {
+    /// The kinetic energy (for a mass of 1.0) required at takeoff for reaching the full
+    /// [`height`](Self::height) under the given gravity.
+    fn takeoff_kinetic_energy(&self, gravity: Float) -> Float {
+        SegmentedJumpInitialVelocityCalculator::new(self.height)
+            .add_segment(
+                gravity + self.peak_prevention_extra_gravity,
+                self.peak_prevention_at_upward_velocity,
+            )
+            .add_segment(gravity, self.takeoff_above_velocity)
+            .add_final_segment(gravity + self.takeoff_extra_gravity)
+            .kinetic_energy()
+            .expect("`add_final_segment` should have covered remaining height")
+    }
+
+    /// The upward velocity the jump takes off with when jumping from the float height under the
+    /// given gravity.
+    pub fn takeoff_upward_velocity(&self, gravity: Float) -> Float {
+        SegmentedJumpInitialVelocityCalculator::kinetic_energy_to_velocity(
+            self.takeoff_kinetic_energy(gravity),
+        )
+    }
+
+    /// The time, in seconds, it takes the jump to reach its full [`height`](Self::height) under
+    /// the given gravity, if the action is fed until the peak.
+    pub fn duration_to_peak(&self, gravity: Float) -> Float {
+        SegmentedJumpDurationCalculator::new(self.takeoff_upward_velocity(gravity))
+            .add_segment(
+                gravity + self.takeoff_extra_gravity,
+                self.takeoff_above_velocity,
+            )
+            .add_segment(gravity, self.peak_prevention_at_upward_velocity)
+            .add_segment(gravity + self.peak_prevention_extra_gravity, 0.0)
+            .duration()
+    }
+
+    /// Predict the vertical motion of the jump under the given gravity.
+    ///
+    /// If `release_after` is `None`, the action is assumed to be fed until the peak of the jump.
+    /// Otherwise, the action is assumed to stop being fed that many seconds after the takeoff -
+    /// after which [`shorten_extra_gravity`](Self::shorten_extra_gravity) is applied.
+    ///
+    /// Heights are relative to the character's center at its float height.
+    pub fn predict_vertical_trajectory(
+        &self,
+        gravity: Float,
+        release_after: Option<Float>,
+    ) -> SegmentedJumpTrajectory {
+        let release_after = release_after.unwrap_or(Float::INFINITY);
+        let mut trajectory = SegmentedJumpTrajectory::new(self.takeoff_upward_velocity(gravity));
+        trajectory
+            // While the action is fed
+            .add_segment_until(
+                gravity + self.takeoff_extra_gravity,
+                self.takeoff_above_velocity,
+                release_after,
+            )
+            .add_segment_until(
+                gravity,
+                self.peak_prevention_at_upward_velocity,
+                release_after,
+            )
+            .add_segment_until(
+                gravity + self.peak_prevention_extra_gravity,
+                0.0,
+                release_after,
+            )
+            // After the action stops being fed (if the peak was not reached yet)
+            .add_segment(
+                gravity + self.shorten_extra_gravity + self.takeoff_extra_gravity,
+                self.takeoff_above_velocity,
+            )
+            .add_segment(gravity + self.shorten_extra_gravity, 0.0)
+            // After the peak
+            .add_final_segment(gravity + self.fall_extra_gravity);
+        trajectory
+    }
+
+    /// Predict the trajectory of the jump if it starts now.
+    ///
+    /// * `walk_state` is the current state of the [`TnuaBuiltinWalk`](super::TnuaBuiltinWalk)
+    ///   basis. Its [`standing_offset`](TnuaBuiltinWalkState::standing_offset) is used for finding
+    ///   the float height the jump is measured from, and its
+    ///   [`running_velocity`](TnuaBuiltinWalkState::running_velocity) is used as the horizontal
+    ///   velocity of the jump.
+    /// * `position` is the current position of the character.
+    /// * `up` is the up direction of the character.
+    /// * `gravity` is the magnitude of the gravity pulling the character down.
+    /// * `release_after` is the number of seconds after the takeoff the action stops being fed,
+    ///   or `None` if it is fed until the peak of the jump. See
+    ///   [`predict_vertical_trajectory`](Self::predict_vertical_trajectory).
+    ///
+    /// The prediction assumes the horizontal velocity does not change during the jump - so it does
+    /// not account for air control, for the velocity of the entity the character stands on, or
+    /// for obstacles.
+    pub fn predict_trajectory(
+        &self,
+        walk_state: &TnuaBuiltinWalkState,
+        position: Vector3,
+        up: Dir3,
+        gravity: Float,
+        release_after: Option<Float>,
+    ) -> TnuaBuiltinJumpTrajectory {
+        TnuaBuiltinJumpTrajectory {
+            origin: position - walk_state.standing_offset,
+            up,
+            horizontal_velocity: walk_state
+                .running_velocity
+                .reject_from(up.adjust_precision()),
+            vertical: self.predict_vertical_trajectory(gravity, release_after),
+        }
+    }
+
     fn finish_or_reschedule(&self) -> TnuaActionLifecycleDirective {
         if let Some(cooldown) = self.reschedule_cooldown {
             TnuaActionLifecycleDirective::Reschedule {
@@ -444,7 +552,8 @@ impl TnuaBuiltinJump {
     }
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TnuaBuiltinJumpState {
     #[default]
     NoJump,
@@ -462,7 +571,10 @@ pub enum TnuaBuiltinJumpState {
     SlowDownTooFastSlopeJump {
         origin: Vector3,
         desired_energy: Float,
-        zero_potential_energy_at: Vector3,
+        /// The height above the point where the potential energy is defined as 0, accumulated
+        /// along the up direction of each frame.
+        extra_height: Float,
+        last_position: Vector3,
     },
     MaintainingJump {
         wait_one_frame_before_updating_velocity_boundary: bool,
@@ -471,3 +583,72 @@ pub enum TnuaBuiltinJumpState {
     StoppedMaintainingJump,
     FallSection,
 }
+
+/// A prediction of a [`TnuaBuiltinJump`]'s trajectory, made by
+/// [`TnuaBuiltinJump::predict_trajectory`].
+///
+/// Times are measured in seconds since the takeoff, and heights are measured along the up
+/// direction from the [`origin`](Self::origin) - just like [`TnuaBuiltinJump::height`].
+#[derive(Debug, Clone, Reflect)]
+pub struct TnuaBuiltinJumpTrajectory {
+    /// The position of the character's center at its float height when the jump starts.
+    pub origin: Vector3,
+    pub up: Dir3,
+    /// The horizontal velocity of the jump, which is assumed to stay constant.
+    pub horizontal_velocity: Vector3,
+    /// The vertical part of the jump's motion.
+    pub vertical: SegmentedJumpTrajectory,
+}
+
+impl TnuaBuiltinJumpTrajectory {
+    /// The maximum number of positions [`sample`](Self::sample) returns, not counting the last
+    /// one.
+    pub const MAX_SAMPLES: usize = 10_000;
+
+    /// The predicted position of the character at the given time.
+    pub fn position_at(&self, time: Float) -> Vector3 {
+        self.origin
+            + self.horizontal_velocity * time
+            + self.vertical.height_at(time) * self.up.adjust_precision()
+    }
+
+    /// The time and the position of the highest point of the jump.
+    pub fn apex(&self) -> Option<(Float, Vector3)> {
+        let (time, _) = self.vertical.peak()?;
+        Some((time, self.position_at(time)))
+    }
+
+    /// The time the character lands at the given height, on its way down.
+    ///
+    /// Returns `None` if the jump does not reach that height.
+    pub fn time_to_land_at(&self, height: Float) -> Option<Float> {
+        self.vertical.time_to_descend_to(height)
+    }
+
+    /// Sample the predicted positions every `time_step` seconds, from the takeoff until
+    /// `duration` (which is always included as the last sample).
+    ///
+    /// To sample the arc until the landing, pass the result of
+    /// [`time_to_land_at`](Self::time_to_land_at) as the `duration`.
+    ///
+    /// Nothing is sampled if `duration` is negative or not finite, or if `time_step` is not
+    /// positive. The `time_step` is enlarged if needed to keep the number of samples under
+    /// [`MAX_SAMPLES`](Self::MAX_SAMPLES).
+    pub fn sample(
+        &self,
+        time_step: Float,
+        duration: Float,
+    ) -> impl Iterator<Item = (Float, Vector3)> + '_ {
+        let valid = duration.is_finite() && 0.0 <= duration && 0.0 < time_step;
+        let time_step = time_step.max(duration / Self::MAX_SAMPLES as Float);
+        let samples = if valid {
+            (duration / time_step).ceil() as usize + 1
+        } else {
+            0
+        };
+        (0..samples).map(move |step| {
+            let time = (step as Float * time_step).min(duration);
+            (time, self.position_at(time))
+        })
+    }
+}
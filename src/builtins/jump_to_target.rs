@@ -0,0 +1,254 @@
+use crate::math::{AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+
+use crate::{
+    TnuaAction, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+use super::{TnuaBuiltinJump, TnuaBuiltinJumpState};
+
+/// A jump [action](TnuaAction) that lands the character on a given point.
+///
+/// Instead of jumping to a fixed [`height`](TnuaBuiltinJump::height), the action works out the
+/// height and the horizontal velocity required for landing on the [`target`](Self::target) - using
+/// the same segmented gravity model [`TnuaBuiltinJump`] uses - and keeps that horizontal velocity
+/// for the entire jump, ignoring the basis' horizontal input. The jump always peaks
+/// [`apex_clearance`](Self::apex_clearance) above the target (or above the takeoff point, if the
+/// target is lower).
+///
+/// The target is where the character's center should be when it stands on the landing point -
+/// i.e. the point on the ground plus the float height. It is cached when the action starts, so
+/// changing it mid-jump has no effect.
+///
+/// If the target is unreachable with the current gravity settings, the action will not start. Use
+/// [`solve`](Self::solve) to check this in advance (e.g. for AI) and to find out why.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinJumpToTarget {
+    /// The point the character's center should land on.
+    pub target: Vector3,
+
+    /// The jump to perform.
+    ///
+    /// Its [`height`](TnuaBuiltinJump::height) and
+    /// [`vertical_displacement`](TnuaBuiltinJump::vertical_displacement) are ignored - they are
+    /// decided by the target.
+    pub jump: TnuaBuiltinJump,
+
+    /// How high above the target (or above the takeoff point, if it is higher) the jump should
+    /// peak.
+    ///
+    /// Negative values are treated as zero, since the jump cannot peak below the point it lands
+    /// on.
+    pub apex_clearance: Float,
+
+    /// The target is considered unreachable if reaching it requires a jump higher than this.
+    pub max_height: Float,
+
+    /// The target is considered unreachable if reaching it requires a horizontal speed higher than
+    /// this.
+    pub max_horizontal_speed: Float,
+}
+
+impl Default for TnuaBuiltinJumpToTarget {
+    fn default() -> Self {
+        Self {
+            target: Vector3::NAN,
+            jump: Default::default(),
+            apex_clearance: 1.0,
+            max_height: 6.0,
+            max_horizontal_speed: 20.0,
+        }
+    }
+}
+
+impl TnuaBuiltinJumpToTarget {
+    /// Calculate the jump required for reaching the [`target`](Self::target).
+    ///
+    /// * `origin` is the character's position when standing at its float height - the character's
+    ///   position minus the basis' [`displacement`](crate::TnuaBasis::displacement).
+    /// * `up` is the up direction of the character.
+    /// * `gravity` is the magnitude of the gravity pulling the character down.
+    pub fn solve(
+        &self,
+        origin: Vector3,
+        up: Dir3,
+        gravity: Float,
+    ) -> Result<TnuaBuiltinJumpToTargetSolution, TnuaBuiltinJumpToTargetUnreachable> {
+        if !self.target.is_finite() {
+            return Err(TnuaBuiltinJumpToTargetUnreachable::NoTarget);
+        }
+        if gravity <= 0.0 {
+            return Err(TnuaBuiltinJumpToTargetUnreachable::NoGravity);
+        }
+        let up = up.adjust_precision();
+        let offset = self.target - origin;
+        let target_height = offset.dot(up);
+
+        let height = target_height.max(0.0) + self.apex_clearance.max(0.0);
+        if self.max_height < height {
+            return Err(TnuaBuiltinJumpToTargetUnreachable::TooHigh {
+                required_height: height,
+                max_height: self.max_height,
+            });
+        }
+
+        let jump = TnuaBuiltinJump {
+            height,
+            ..self.jump.clone()
+        };
+        let fall_height = height - target_height;
+        let fall_duration = (2.0 * fall_height / (gravity + jump.fall_extra_gravity)).sqrt();
+        let duration = jump.duration_to_peak(gravity) + fall_duration;
+        if duration <= 0.0 {
+            return Err(TnuaBuiltinJumpToTargetUnreachable::NoGravity);
+        }
+
+        let horizontal_velocity = offset.reject_from(up) / duration;
+        let horizontal_speed = horizontal_velocity.length();
+        if self.max_horizontal_speed < horizontal_speed {
+            return Err(TnuaBuiltinJumpToTargetUnreachable::TooFar {
+                required_speed: horizontal_speed,
+                max_speed: self.max_horizontal_speed,
+            });
+        }
+
+        Ok(TnuaBuiltinJumpToTargetSolution {
+            height,
+            horizontal_velocity,
+            duration,
+        })
+    }
+
+    fn solve_in_context(
+        &self,
+        ctx: &TnuaActionContext,
+    ) -> Result<TnuaBuiltinJumpToTargetSolution, TnuaBuiltinJumpToTargetUnreachable> {
+        let origin = ctx.tracker.translation - ctx.basis.displacement().unwrap_or(Vector3::ZERO);
+        self.solve(
+            origin,
+            ctx.up_direction,
+            ctx.tracker
+                .gravity
+                .dot(-ctx.up_direction.adjust_precision()),
+        )
+    }
+}
+
+impl TnuaAction for TnuaBuiltinJumpToTarget {
+    const NAME: &'static str = "TnuaBuiltinJumpToTarget";
+    type State = TnuaBuiltinJumpToTargetState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if self.solve_in_context(&ctx).is_err() {
+            return TnuaActionInitiationDirective::Reject;
+        }
+        self.jump.initiation_decision(ctx, being_fed_for)
+    }
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if let TnuaBuiltinJumpToTargetState::NoJump = state {
+            match self.solve_in_context(&ctx) {
+                Ok(solution) => {
+                    *state = TnuaBuiltinJumpToTargetState::Jumping {
+                        solution,
+                        jump_state: Default::default(),
+                    };
+                }
+                Err(err) => {
+                    warn!("Cannot jump to target - {err}");
+                    return TnuaActionLifecycleDirective::Finished;
+                }
+            }
+        }
+
+        let TnuaBuiltinJumpToTargetState::Jumping {
+            solution,
+            jump_state,
+        } = state
+        else {
+            unreachable!("state was set to Jumping above");
+        };
+
+        let jump = TnuaBuiltinJump {
+            height: solution.height,
+            vertical_displacement: None,
+            ..self.jump.clone()
+        };
+        let jump_lifecycle_status = match (&*jump_state, lifecycle_status) {
+            (_, TnuaActionLifecycleStatus::CancelledInto) => {
+                TnuaActionLifecycleStatus::CancelledInto
+            }
+            (TnuaBuiltinJumpState::NoJump, _) => TnuaActionLifecycleStatus::Initiated,
+            // Shortening the jump would make it miss the target.
+            _ => TnuaActionLifecycleStatus::StillFed,
+        };
+        let up = ctx.up_direction.adjust_precision();
+        let horizontal_velocity = ctx.tracker.velocity.reject_from(up);
+
+        let directive = jump.apply(jump_state, ctx, jump_lifecycle_status, motor);
+
+        motor.lin = motor.lin.project_onto_normalized(up)
+            + TnuaVelChange::boost(solution.horizontal_velocity - horizontal_velocity);
+
+        directive
+    }
+}
+
+/// The jump required for reaching a [`TnuaBuiltinJumpToTarget`]'s target, as calculated by
+/// [`TnuaBuiltinJumpToTarget::solve`].
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinJumpToTargetSolution {
+    /// The [`height`](TnuaBuiltinJump::height) of the jump.
+    pub height: Float,
+    /// The horizontal velocity to keep throughout the jump.
+    pub horizontal_velocity: Vector3,
+    /// The time, in seconds, from the takeoff to the landing on the target.
+    pub duration: Float,
+}
+
+/// The reason a [`TnuaBuiltinJumpToTarget`]'s target cannot be reached.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq)]
+pub enum TnuaBuiltinJumpToTargetUnreachable {
+    #[error("No target was set")]
+    NoTarget,
+    #[error("Gravity does not pull the character down")]
+    NoGravity,
+    #[error("Target requires a jump height of {required_height}, but the maximum is {max_height}")]
+    TooHigh {
+        required_height: Float,
+        max_height: Float,
+    },
+    #[error(
+        "Target requires a horizontal speed of {required_speed}, but the maximum is {max_speed}"
+    )]
+    TooFar {
+        required_speed: Float,
+        max_speed: Float,
+    },
+}
+
+#[derive(Default, Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinJumpToTargetState {
+    #[default]
+    NoJump,
+    Jumping {
+        solution: TnuaBuiltinJumpToTargetSolution,
+        jump_state: TnuaBuiltinJumpState,
+    },
+}
@@ -0,0 +1,269 @@
+use bevy::prelude::*;
+use bevy_tnua_physics_integration_layer::math::{AdjustPrecision, Float, Vector3};
+
+use crate::util::MotionHelper;
+use crate::TnuaActionContext;
+use crate::{
+    TnuaAction, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor,
+};
+
+/// An [action](TnuaAction) for grabbing a ledge and hanging from it.
+///
+/// Use [`find_grabbable_ledge`](crate::radar_lens::TnuaRadarBlipLens::find_grabbable_ledge) to
+/// find the ledge and fill the [`edge_point`](Self::edge_point) and the
+/// [`wall_direction`](Self::wall_direction). The action can only start while the character is
+/// airborne and not moving upward.
+///
+/// While hanging, the character can shimmy along the edge using
+/// [`desired_shimmy_velocity`](Self::desired_shimmy_velocity). Note that the action does not know
+/// where the ledge ends - the game code should keep updating the `edge_point` and stop feeding the
+/// action when there is no longer a ledge to hang from.
+///
+/// There are two ways to exit the action:
+///
+/// * Set [`climb_up`](Self::climb_up) to make the character climb over the edge. Once started,
+///   the climb will finish even if the action is no longer fed.
+/// * Stop feeding the action to make the character drop off. Consider using
+///   [`TnuaBlipReuseAvoidance`](crate::control_helpers::TnuaBlipReuseAvoidance) to prevent the
+///   character from grabbing the same ledge again while dropping.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinLedgeHang {
+    /// The entity of the ledge.
+    pub ledge_entity: Option<Entity>,
+
+    /// A point on the top edge of the ledge.
+    pub edge_point: Vector3,
+
+    /// The horizontal direction from the character toward the ledge's wall.
+    ///
+    /// The character will face that direction while hanging.
+    pub wall_direction: Dir3,
+
+    /// How far below the [`edge_point`](Self::edge_point) the character's center hangs.
+    pub hang_height: Float,
+
+    /// How far away from the wall, horizontally, the character's center hangs.
+    pub hang_distance: Float,
+
+    /// The maximum speed used for pulling the character to its hanging position.
+    pub hang_speed: Float,
+
+    /// The acceleration used for pulling the character to its hanging position.
+    pub hang_acceleration: Float,
+
+    /// The velocity to move along the edge at.
+    ///
+    /// Only the component of this vector that is parallel to the edge is used, so it's okay to
+    /// pass the raw movement input here.
+    pub desired_shimmy_velocity: Vector3,
+
+    /// The acceleration used for moving along the edge.
+    pub shimmy_acceleration: Float,
+
+    /// Set to `true` to climb over the edge.
+    pub climb_up: bool,
+
+    /// The speed to climb over the edge at.
+    pub climb_up_speed: Float,
+
+    /// How high above the [`edge_point`](Self::edge_point) the character's center needs to rise
+    /// before moving over the edge.
+    ///
+    /// This should usually be a little higher than
+    /// [`TnuaBuiltinWalk::float_height`](crate::builtins::TnuaBuiltinWalk::float_height).
+    pub climb_up_height: Float,
+
+    /// How far past the edge the character's center needs to move before the climb is over.
+    pub climb_up_forward: Float,
+}
+
+impl Default for TnuaBuiltinLedgeHang {
+    fn default() -> Self {
+        Self {
+            ledge_entity: None,
+            edge_point: Vector3::NAN,
+            wall_direction: Dir3::NEG_Z,
+            hang_height: 0.5,
+            hang_distance: 0.5,
+            hang_speed: 10.0,
+            hang_acceleration: 100.0,
+            desired_shimmy_velocity: Vector3::ZERO,
+            shimmy_acceleration: 20.0,
+            climb_up: false,
+            climb_up_speed: 4.0,
+            climb_up_height: 1.2,
+            climb_up_forward: 0.6,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinLedgeHang {
+    const NAME: &'static str = "TnuaBuiltinLedgeHang";
+
+    type State = TnuaBuiltinLedgeHangState;
+
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        let up = ctx.up_direction.adjust_precision();
+
+        for _ in 0..3 {
+            return match state {
+                TnuaBuiltinLedgeHangState::Hanging { shimmy_velocity } => {
+                    if !lifecycle_status.is_active() {
+                        return TnuaActionLifecycleDirective::Finished;
+                    }
+                    if self.climb_up {
+                        *state = TnuaBuiltinLedgeHangState::ClimbingUp {
+                            edge_point: self.edge_point,
+                            wall_direction: self.wall_direction,
+                            over_the_edge: false,
+                        };
+                        continue;
+                    }
+
+                    let wall = self.wall_direction.reject_from(*ctx.up_direction);
+                    let wall = wall.normalize_or_zero().adjust_precision();
+                    let edge_direction = up.cross(wall);
+
+                    let hang_position =
+                        self.edge_point - self.hang_height * up - self.hang_distance * wall;
+                    let offset = (hang_position - ctx.tracker.translation)
+                        .reject_from_normalized(edge_direction);
+                    let hold_velocity =
+                        (offset / ctx.frame_duration).clamp_length_max(self.hang_speed);
+
+                    *shimmy_velocity = ctx.tracker.velocity.project_onto_normalized(edge_direction);
+
+                    motor.lin = ctx.negate_gravity()
+                        + ctx.adjust_velocity(hold_velocity, self.hang_acceleration, |v| {
+                            v.reject_from_normalized(edge_direction)
+                        })
+                        + ctx.adjust_velocity(
+                            self.desired_shimmy_velocity,
+                            self.shimmy_acceleration,
+                            |v| v.project_onto_normalized(edge_direction),
+                        );
+
+                    motor
+                        .ang
+                        .cancel_on_axis(ctx.up_direction.adjust_precision());
+                    motor.ang += ctx.turn_to_direction(self.wall_direction, ctx.up_direction);
+
+                    TnuaActionLifecycleDirective::StillActive
+                }
+                TnuaBuiltinLedgeHangState::ClimbingUp {
+                    edge_point,
+                    wall_direction,
+                    over_the_edge,
+                } => {
+                    let wall = wall_direction.reject_from(*ctx.up_direction);
+                    let wall = wall.normalize_or_zero().adjust_precision();
+
+                    let offset_from_edge = ctx.tracker.translation - *edge_point;
+                    let height_above_edge = offset_from_edge.dot(up);
+                    let distance_past_edge = offset_from_edge.dot(wall);
+
+                    if !*over_the_edge && self.climb_up_height <= height_above_edge {
+                        *over_the_edge = true;
+                    }
+                    if *over_the_edge && self.climb_up_forward <= distance_past_edge {
+                        return TnuaActionLifecycleDirective::Finished;
+                    }
+
+                    let velocity_toward = |distance: Float| {
+                        (distance / ctx.frame_duration)
+                            .clamp(-self.climb_up_speed, self.climb_up_speed)
+                    };
+                    let (vertical_velocity, forward_velocity) = if *over_the_edge {
+                        (
+                            velocity_toward(self.climb_up_height - height_above_edge),
+                            self.climb_up_speed,
+                        )
+                    } else {
+                        // Stay next to the wall while rising.
+                        (
+                            self.climb_up_speed,
+                            velocity_toward(-self.hang_distance - distance_past_edge),
+                        )
+                    };
+
+                    motor.lin = ctx.negate_gravity()
+                        + ctx.adjust_velocity(
+                            vertical_velocity * up + forward_velocity * wall,
+                            self.hang_acceleration,
+                            |v| v,
+                        );
+
+                    motor
+                        .ang
+                        .cancel_on_axis(ctx.up_direction.adjust_precision());
+                    motor.ang += ctx.turn_to_direction(*wall_direction, ctx.up_direction);
+
+                    TnuaActionLifecycleDirective::StillActive
+                }
+            };
+        }
+        error!("Tnua could not decide on ledge hang state");
+        TnuaActionLifecycleDirective::Finished
+    }
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if !self.edge_point.is_finite() {
+            TnuaActionInitiationDirective::Reject
+        } else if !ctx.basis.is_airborne() {
+            TnuaActionInitiationDirective::Delay
+        } else if 0.0
+            < ctx
+                .tracker
+                .velocity
+                .dot(ctx.up_direction.adjust_precision())
+        {
+            // Only grab ledges while falling
+            TnuaActionInitiationDirective::Delay
+        } else {
+            TnuaActionInitiationDirective::Allow
+        }
+    }
+
+    fn target_entity(&self, _state: &Self::State) -> Option<Entity> {
+        self.ledge_entity
+    }
+}
+
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinLedgeHangState {
+    /// Hanging from the ledge.
+    Hanging {
+        /// The velocity of the character along the edge.
+        shimmy_velocity: Vector3,
+    },
+    /// Climbing over the edge. The ledge's position is cached when the climb starts.
+    ClimbingUp {
+        edge_point: Vector3,
+        wall_direction: Dir3,
+        /// `false` while rising along the wall, `true` while moving over the edge.
+        over_the_edge: bool,
+    },
+}
+
+impl Default for TnuaBuiltinLedgeHangState {
+    fn default() -> Self {
+        Self::Hanging {
+            shimmy_velocity: Vector3::ZERO,
+        }
+    }
+}
@@ -1,15 +1,52 @@
+mod charged_jump;
 mod climb;
 mod crouch;
+mod crouch_slide;
 mod dash;
+mod fly;
+mod glide;
+mod gravity_flip;
+mod ground_pound;
 mod jump;
+mod jump_to_target;
 mod knockback;
+mod ledge_hang;
+mod rope_swing;
+mod swim;
+mod vault;
 mod walk;
+mod wall_jump;
+mod wall_run;
 mod wall_slide;
 
+pub use charged_jump::{TnuaBuiltinChargedJump, TnuaBuiltinChargedJumpState};
 pub use climb::{TnuaBuiltinClimb, TnuaBuiltinClimbState};
 pub use crouch::{TnuaBuiltinCrouch, TnuaBuiltinCrouchState};
+pub use crouch_slide::{TnuaBuiltinCrouchSlide, TnuaBuiltinCrouchSlideState};
 pub use dash::{TnuaBuiltinDash, TnuaBuiltinDashState};
-pub use jump::{TnuaBuiltinJump, TnuaBuiltinJumpState};
+pub use fly::{TnuaBuiltinFly, TnuaBuiltinFlyState};
+pub use glide::{TnuaBuiltinGlide, TnuaBuiltinGlideState};
+pub(crate) use gravity_flip::apply_gravity_flips_system;
+pub use gravity_flip::{TnuaBuiltinGravityFlip, TnuaBuiltinGravityFlipState};
+pub(crate) use ground_pound::send_ground_pound_impact_events;
+pub use ground_pound::{
+    TnuaBuiltinGroundPound, TnuaBuiltinGroundPoundImpact, TnuaBuiltinGroundPoundImpacted,
+    TnuaBuiltinGroundPoundState,
+};
+pub use jump::{TnuaBuiltinJump, TnuaBuiltinJumpState, TnuaBuiltinJumpTrajectory};
+pub use jump_to_target::{
+    TnuaBuiltinJumpToTarget, TnuaBuiltinJumpToTargetSolution, TnuaBuiltinJumpToTargetState,
+    TnuaBuiltinJumpToTargetUnreachable,
+};
 pub use knockback::{TnuaBuiltinKnockback, TnuaBuiltinKnockbackState};
-pub use walk::{TnuaBuiltinWalk, TnuaBuiltinWalkState};
+pub use ledge_hang::{TnuaBuiltinLedgeHang, TnuaBuiltinLedgeHangState};
+pub use rope_swing::{TnuaBuiltinRopeSwing, TnuaBuiltinRopeSwingState};
+pub use swim::{TnuaBuiltinSwim, TnuaBuiltinSwimMode, TnuaBuiltinSwimState};
+pub use vault::{TnuaBuiltinVault, TnuaBuiltinVaultState};
+pub(crate) use walk::send_walk_landed_events;
+pub use walk::{
+    TnuaBuiltinWalk, TnuaBuiltinWalkLanded, TnuaBuiltinWalkLandingReport, TnuaBuiltinWalkState,
+};
+pub use wall_jump::{TnuaBuiltinWallJump, TnuaBuiltinWallJumpState};
+pub use wall_run::{TnuaBuiltinWallRun, TnuaBuiltinWallRunState};
 pub use wall_slide::{TnuaBuiltinWallSlide, TnuaBuiltinWallSlideState};
@@ -0,0 +1,232 @@
+use crate::math::{AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::MotionHelper;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+/// An [action](TnuaAction) for swinging from a rope, like a grappling hook.
+///
+/// The character is attached to the [`anchor`](Self::anchor) with a rope. While the rope is slack
+/// the basis controls the character as usual, and when it is taut the action prevents the
+/// character from moving further away from the anchor - which, together with the gravity, makes
+/// the character swing like a pendulum. While the rope is taut, the character can pump the swing
+/// with [`desired_pump`](Self::desired_pump).
+///
+/// When the anchor is attached to a moving entity, keep updating the `anchor` and set the
+/// [`anchor_velocity`](Self::anchor_velocity) to that entity's linear velocity every frame. The
+/// rope constraint is calculated relative to that velocity, so the character will be carried
+/// along - similar to how [`TnuaBuiltinWalk`](crate::builtins::TnuaBuiltinWalk) uses
+/// [`TnuaProximitySensorOutput::entity_linvel`](crate::TnuaProximitySensorOutput::entity_linvel)
+/// for moving platforms.
+///
+/// There are two ways to exit the action:
+///
+/// * Set [`release`](Self::release) to jump off the rope. The character is launched with the
+///   release velocity, which is also exposed in the [state](TnuaBuiltinRopeSwingState) while
+///   swinging (e.g. for drawing the predicted trajectory)
+/// * Stop feeding the action to let go of the rope, keeping the character's current velocity.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinRopeSwing {
+    /// The entity the rope is attached to.
+    pub anchor_entity: Option<Entity>,
+
+    /// The point the rope is attached to.
+    pub anchor: Vector3,
+
+    /// The linear velocity of the [`anchor`](Self::anchor).
+    ///
+    /// Leave it at zero for static anchors.
+    pub anchor_velocity: Vector3,
+
+    /// The length of the rope.
+    ///
+    /// This input parameter is cached when the action starts. After that, the length of the rope
+    /// can only be changed with [`reel_in_speed`](Self::reel_in_speed).
+    pub rope_length: Float,
+
+    /// The speed to shorten the rope at. Use negative values to lengthen it.
+    pub reel_in_speed: Float,
+
+    /// Reeling in will not make the rope shorter than this.
+    pub min_rope_length: Float,
+
+    /// Reeling out will not make the rope longer than this.
+    pub max_rope_length: Float,
+
+    /// The direction to pump the swing in.
+    ///
+    /// Only the part of this vector that is perpendicular to the rope is used, so it's okay to
+    /// pass the raw movement input here. Its length should be between 0.0 and 1.0.
+    pub desired_pump: Vector3,
+
+    /// The acceleration applied when the [`desired_pump`](Self::desired_pump) is at full length.
+    pub pump_acceleration: Float,
+
+    /// Set to `true` to release the rope and jump off it.
+    pub release: bool,
+
+    /// A factor applied to the velocity (relative to the anchor) of the character when it
+    /// releases the rope.
+    pub release_velocity_factor: Float,
+
+    /// The upward speed added when the character releases the rope.
+    pub release_jump_speed: Float,
+
+    /// Force the character to face in a particular direction.
+    pub desired_forward: Option<Dir3>,
+}
+
+impl Default for TnuaBuiltinRopeSwing {
+    fn default() -> Self {
+        Self {
+            anchor_entity: None,
+            anchor: Vector3::NAN,
+            anchor_velocity: Vector3::ZERO,
+            rope_length: 5.0,
+            reel_in_speed: 0.0,
+            min_rope_length: 1.0,
+            max_rope_length: 20.0,
+            desired_pump: Vector3::ZERO,
+            pump_acceleration: 5.0,
+            release: false,
+            release_velocity_factor: 1.2,
+            release_jump_speed: 4.0,
+            desired_forward: None,
+        }
+    }
+}
+
+impl TnuaBuiltinRopeSwing {
+    /// The velocity the character would have if it released the rope now.
+    fn release_velocity(&self, ctx: &TnuaActionContext) -> Vector3 {
+        self.anchor_velocity
+            + self.release_velocity_factor * (ctx.tracker.velocity - self.anchor_velocity)
+            + self.release_jump_speed * ctx.up_direction.adjust_precision()
+    }
+}
+
+impl TnuaAction for TnuaBuiltinRopeSwing {
+    const NAME: &'static str = "TnuaBuiltinRopeSwing";
+    type State = TnuaBuiltinRopeSwingState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if !lifecycle_status.is_active() {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        if let TnuaBuiltinRopeSwingState::NoSwing = state {
+            *state = TnuaBuiltinRopeSwingState::Swinging {
+                rope_length: self
+                    .rope_length
+                    .clamp(self.min_rope_length, self.max_rope_length),
+                rope_direction: -ctx.up_direction.adjust_precision(),
+                taut: false,
+                release_velocity: Vector3::ZERO,
+            };
+        }
+
+        let TnuaBuiltinRopeSwingState::Swinging {
+            rope_length,
+            rope_direction,
+            taut,
+            release_velocity,
+        } = state
+        else {
+            unreachable!("state was set to Swinging above");
+        };
+
+        *release_velocity = self.release_velocity(&ctx);
+        if self.release {
+            motor.lin = TnuaVelChange::boost(*release_velocity - ctx.tracker.velocity);
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        *rope_length = (*rope_length - self.reel_in_speed * ctx.frame_duration)
+            .clamp(self.min_rope_length, self.max_rope_length);
+
+        let offset_from_anchor = ctx.tracker.translation - self.anchor;
+        let distance = offset_from_anchor.length();
+        if let Some(direction) = offset_from_anchor.try_normalize() {
+            *rope_direction = direction;
+        }
+        let rope_direction = *rope_direction;
+
+        if let Some(desired_forward) = self.desired_forward {
+            motor
+                .ang
+                .cancel_on_axis(ctx.up_direction.adjust_precision());
+            motor.ang += ctx.turn_to_direction(desired_forward, ctx.up_direction);
+        }
+
+        let pump_acceleration = self
+            .desired_pump
+            .reject_from_normalized(rope_direction)
+            .clamp_length_max(1.0)
+            * self.pump_acceleration;
+
+        // Where the character's velocity, relative to the anchor, is going to be at the end of the
+        // frame if the rope does not intervene.
+        let relative_velocity = ctx.tracker.velocity - self.anchor_velocity;
+        let predicted_velocity =
+            relative_velocity + (ctx.tracker.gravity + pump_acceleration) * ctx.frame_duration;
+        let outward_speed = predicted_velocity.dot(rope_direction);
+        let max_outward_speed = (*rope_length - distance) / ctx.frame_duration;
+
+        *taut = max_outward_speed < outward_speed;
+        if !*taut {
+            // The basis keeps control over a slack rope.
+            return TnuaActionLifecycleDirective::StillActive;
+        }
+
+        motor.lin = TnuaVelChange {
+            acceleration: pump_acceleration,
+            boost: (max_outward_speed - outward_speed) * rope_direction,
+        };
+
+        TnuaActionLifecycleDirective::StillActive
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if self.anchor.is_finite() {
+            TnuaActionInitiationDirective::Allow
+        } else {
+            TnuaActionInitiationDirective::Reject
+        }
+    }
+
+    fn target_entity(&self, _state: &Self::State) -> Option<Entity> {
+        self.anchor_entity
+    }
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinRopeSwingState {
+    #[default]
+    NoSwing,
+    Swinging {
+        /// The current length of the rope, after reeling in or out.
+        rope_length: Float,
+        /// The direction from the anchor to the character.
+        rope_direction: Vector3,
+        /// `true` if the rope is stretched to its full length, restraining the character.
+        taut: bool,
+        /// The velocity the character will be launched with if it releases the rope.
+        release_velocity: Vector3,
+    },
+}
@@ -0,0 +1,317 @@
+use crate::math::{AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::calc_angular_velchange_to_stay_upright;
+use crate::TnuaBasisContext;
+use crate::{TnuaBasis, TnuaVelChange};
+
+/// A [basis](TnuaBasis) for swimming in water volumes.
+///
+/// The water volumes must be marked with [`TnuaWaterVolume`](crate::TnuaWaterVolume), and the
+/// character must have a [`TnuaWaterSensor`](crate::TnuaWaterSensor) for detecting them. The
+/// [`cast_range`](crate::TnuaWaterSensor::cast_range) of that sensor should be larger than the
+/// [`float_depth`](Self::float_depth).
+///
+/// At the surface the character floats at the `float_depth`, moving only perpendicular to the up
+/// direction. When the [`desired_velocity`](Self::desired_velocity) points downward the character
+/// dives, and can move freely in all directions until it swims back up to the surface.
+///
+/// When the water is shallow enough for the character to reach the ground below it, it will float
+/// above that ground the way [`TnuaBuiltinWalk`](crate::builtins::TnuaBuiltinWalk) does - which
+/// allows it to walk out of the water. Once the character is out of the water, the
+/// [`mode`](TnuaBuiltinSwimState::mode) will become [`TnuaBuiltinSwimMode::OnGround`] (or
+/// [`TnuaBuiltinSwimMode::Airborne`] if there is no ground), which is the game code's cue to
+/// switch back to the walk basis. Similarly, the game code should switch from the walk basis to
+/// the swim basis when the water sensor detects that the character is deep enough in the water.
+///
+/// Like with [`TnuaBuiltinWalk`](crate::builtins::TnuaBuiltinWalk), the
+/// [`float_height`](Self::float_height) defaults to 0.0 and must be set for the character to
+/// float above the ground.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinSwim {
+    /// The direction (in the world space) and speed to accelerate to.
+    ///
+    /// Unlike with [`TnuaBuiltinWalk`](crate::builtins::TnuaBuiltinWalk), this vector does not
+    /// need to be orthogonal to the up direction. A downward component will make the character
+    /// dive, and while diving an upward component will make it swim up.
+    pub desired_velocity: Vector3,
+
+    /// If non-zero, Tnua will rotate the character so that its negative Z will face in that
+    /// direction.
+    ///
+    /// Tnua assumes that this vector is orthogonal to the up direction.
+    pub desired_forward: Option<Dir3>,
+
+    /// The depth, below the water surface, at which the character will float at rest.
+    ///
+    /// Note that this is the depth of the [water sensor's origin](crate::TnuaWaterSensor) - which
+    /// is usually the character's center of mass.
+    pub float_depth: Float,
+
+    /// The height at which the character will float above ground when the water is too shallow
+    /// to float at the [`float_depth`](Self::float_depth).
+    ///
+    /// This should usually be the same as
+    /// [`TnuaBuiltinWalk::float_height`](crate::builtins::TnuaBuiltinWalk::float_height).
+    pub float_height: Float,
+
+    /// Extra distance above the [`float_height`](Self::float_height) where the ground is still
+    /// detected.
+    ///
+    /// When the character is at at most this distance above the `float_height`, the spring force
+    /// will pull it down to the ground. Without it, a character standing at the float height would
+    /// be at the very edge of the ground sensor's range, and would keep flickering between
+    /// [`OnGround`](TnuaBuiltinSwimMode::OnGround) and
+    /// [`Airborne`](TnuaBuiltinSwimMode::Airborne).
+    ///
+    /// This should usually be the same as
+    /// [`TnuaBuiltinWalk::cling_distance`](crate::builtins::TnuaBuiltinWalk::cling_distance).
+    pub cling_distance: Float,
+
+    /// The force that pushes the character to the float depth (or the float height, when the
+    /// ground is closer)
+    ///
+    /// The actual force applied is in direct linear relationship to the displacement from the
+    /// float depth.
+    pub spring_strength: Float,
+
+    /// A force that slows down the characters vertical spring motion.
+    ///
+    /// The actual dampening is in direct linear relationship to the vertical velocity it tries to
+    /// dampen.
+    pub spring_dampening: Float,
+
+    /// The acceleration for movement while floating at the surface (or while standing on the
+    /// ground)
+    pub surface_acceleration: Float,
+
+    /// The acceleration for movement while diving.
+    pub underwater_acceleration: Float,
+
+    /// The maximum angular velocity used for keeping the character standing upright.
+    ///
+    /// NOTE: The character's rotation can also be locked to prevent it from being tilted, in which
+    /// case this paramter is redundant and can be set to 0.0.
+    pub tilt_offset_angvel: Float,
+
+    /// The maximum angular acceleration used for reaching `tilt_offset_angvel`.
+    ///
+    /// NOTE: The character's rotation can also be locked to prevent it from being tilted, in which
+    /// case this paramter is redundant and can be set to 0.0.
+    pub tilt_offset_angacl: Float,
+
+    /// The maximum angular velocity used for turning the character when the direction changes.
+    pub turning_angvel: Float,
+}
+
+impl Default for TnuaBuiltinSwim {
+    fn default() -> Self {
+        Self {
+            desired_velocity: Vector3::ZERO,
+            desired_forward: None,
+            float_depth: 0.5,
+            float_height: 0.0,
+            cling_distance: 1.0,
+            spring_strength: 100.0,
+            spring_dampening: 1.2,
+            surface_acceleration: 30.0,
+            underwater_acceleration: 20.0,
+            tilt_offset_angvel: 5.0,
+            tilt_offset_angacl: 500.0,
+            turning_angvel: 10.0,
+        }
+    }
+}
+
+impl TnuaBasis for TnuaBuiltinSwim {
+    const NAME: &'static str = "TnuaBuiltinSwim";
+    type State = TnuaBuiltinSwimState;
+
+    fn apply(&self, state: &mut Self::State, ctx: TnuaBasisContext, motor: &mut crate::TnuaMotor) {
+        let up = ctx.up_direction.adjust_precision();
+
+        let water = ctx
+            .water_sensor
+            .and_then(|water_sensor| water_sensor.output.as_ref())
+            .filter(|water| 0.0 <= water.depth);
+        state.water_entity = water.map(|water| water.entity);
+        state.depth = water.map(|water| water.depth);
+
+        // Positive offsets mean the character needs to go up.
+        let water_offset = water.map(|water| water.depth - self.float_depth);
+        let ground_offset = ctx
+            .proximity_sensor
+            .output
+            .as_ref()
+            .map(|ground| self.float_height - ground.proximity);
+
+        let desired_vertical_velocity = self.desired_velocity.dot(up);
+        state.mode = match (water_offset, ground_offset) {
+            (None, None) => TnuaBuiltinSwimMode::Airborne,
+            (None, Some(_)) => TnuaBuiltinSwimMode::OnGround,
+            (Some(water_offset), _) => {
+                if desired_vertical_velocity < 0.0 {
+                    TnuaBuiltinSwimMode::Underwater
+                } else if state.mode == TnuaBuiltinSwimMode::Underwater && 0.0 < water_offset {
+                    // Keep diving until the character swims back up to the surface.
+                    TnuaBuiltinSwimMode::Underwater
+                } else {
+                    TnuaBuiltinSwimMode::Surface
+                }
+            }
+        };
+
+        let frame_of_reference_velocity = match state.mode {
+            TnuaBuiltinSwimMode::Surface | TnuaBuiltinSwimMode::Underwater => {
+                water.map(|water| water.entity_linvel)
+            }
+            TnuaBuiltinSwimMode::OnGround | TnuaBuiltinSwimMode::Airborne => None,
+        }
+        .or_else(|| {
+            ctx.proximity_sensor
+                .output
+                .as_ref()
+                .map(|ground| ground.entity_linvel)
+        })
+        .unwrap_or(Vector3::ZERO);
+        state.effective_velocity = ctx.tracker.velocity - frame_of_reference_velocity;
+
+        let vertical_velocity = state.effective_velocity.dot(up);
+        let spring_force = |spring_offset: Float| TnuaVelChange {
+            acceleration: up * spring_offset * self.spring_strength - ctx.tracker.gravity,
+            boost: up * -(vertical_velocity * self.spring_dampening),
+        };
+
+        let accelerate_to = |current_velocity: Vector3, desired_velocity: Vector3, limit: Float| {
+            let desired_boost = desired_velocity - current_velocity;
+            TnuaVelChange::acceleration(
+                (desired_boost / ctx.frame_duration).clamp_length_max(limit),
+            )
+        };
+
+        motor.lin = match state.mode {
+            TnuaBuiltinSwimMode::Surface | TnuaBuiltinSwimMode::OnGround => {
+                // When the ground is closer than the float depth, float above the ground instead
+                // so that the character can walk out of the water.
+                let spring_offset = match (water_offset, ground_offset) {
+                    (Some(water_offset), Some(ground_offset)) => water_offset.max(ground_offset),
+                    (Some(offset), None) | (None, Some(offset)) => offset,
+                    (None, None) => 0.0,
+                };
+                state.standing_offset = -spring_offset * up;
+                accelerate_to(
+                    state.effective_velocity.reject_from(up),
+                    self.desired_velocity.reject_from(up),
+                    self.surface_acceleration,
+                ) + spring_force(spring_offset)
+            }
+            TnuaBuiltinSwimMode::Underwater => {
+                state.standing_offset = Vector3::ZERO;
+                let floor_push = match ground_offset {
+                    Some(ground_offset) if 0.0 < ground_offset => {
+                        TnuaVelChange::acceleration(up * ground_offset * self.spring_strength)
+                    }
+                    _ => TnuaVelChange::ZERO,
+                };
+                // The water cancels the gravity, so that the character can stay at any depth.
+                accelerate_to(
+                    state.effective_velocity,
+                    self.desired_velocity,
+                    self.underwater_acceleration,
+                ) + TnuaVelChange::acceleration(-ctx.tracker.gravity)
+                    + floor_push
+            }
+            TnuaBuiltinSwimMode::Airborne => {
+                state.standing_offset = Vector3::ZERO;
+                TnuaVelChange::ZERO
+            }
+        };
+
+        motor.ang = calc_angular_velchange_to_stay_upright(
+            ctx.tracker,
+            ctx.up_direction,
+            self.desired_forward,
+            ctx.frame_duration,
+            self.tilt_offset_angvel,
+            self.tilt_offset_angacl,
+            self.turning_angvel,
+        );
+    }
+
+    fn proximity_sensor_cast_range(&self, _state: &Self::State) -> Float {
+        self.float_height + self.cling_distance
+    }
+
+    fn displacement(&self, state: &Self::State) -> Option<Vector3> {
+        match state.mode {
+            TnuaBuiltinSwimMode::Surface | TnuaBuiltinSwimMode::OnGround => {
+                Some(state.standing_offset)
+            }
+            TnuaBuiltinSwimMode::Underwater | TnuaBuiltinSwimMode::Airborne => None,
+        }
+    }
+
+    fn effective_velocity(&self, state: &Self::State) -> Vector3 {
+        state.effective_velocity
+    }
+
+    fn vertical_velocity(&self, _state: &Self::State) -> Float {
+        0.0
+    }
+
+    fn neutralize(&mut self) {
+        self.desired_velocity = Vector3::ZERO;
+        self.desired_forward = None;
+    }
+
+    fn is_airborne(&self, state: &Self::State) -> bool {
+        state.mode == TnuaBuiltinSwimMode::Airborne
+    }
+
+    fn violate_coyote_time(&self, _state: &mut Self::State) {}
+}
+
+/// The current situation of a character controlled by a [`TnuaBuiltinSwim`] basis.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinSwimMode {
+    /// Neither in the water nor on the ground.
+    #[default]
+    Airborne,
+    /// Floating at the water surface (or standing on the ground in shallow water)
+    Surface,
+    /// Diving below the water surface.
+    Underwater,
+    /// Out of the water, standing on the ground.
+    OnGround,
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinSwimState {
+    /// The current situation of the character.
+    pub mode: TnuaBuiltinSwimMode,
+    /// The depth of the water sensor's origin below the water surface, or `None` if the character
+    /// is not in the water.
+    pub depth: Option<Float>,
+    /// The current distance of the character from where it's supposed to float.
+    pub standing_offset: Vector3,
+    water_entity: Option<Entity>,
+    effective_velocity: Vector3,
+}
+
+impl TnuaBuiltinSwimState {
+    /// Returns the water volume the character is in.
+    pub fn water_entity(&self) -> Option<Entity> {
+        self.water_entity
+    }
+
+    /// Returns `true` if the character has left the water.
+    pub fn is_out_of_water(&self) -> bool {
+        matches!(
+            self.mode,
+            TnuaBuiltinSwimMode::OnGround | TnuaBuiltinSwimMode::Airborne
+        )
+    }
+}
@@ -0,0 +1,227 @@
+use crate::math::{float_consts, AdjustPrecision, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::MotionHelper;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor,
+};
+
+/// An [action](TnuaAction) for vaulting over low obstacles, or mantling onto them.
+///
+/// Use [`find_vaultable_obstacle`](crate::radar_lens::TnuaRadarBlipLens::find_vaultable_obstacle)
+/// to find the obstacle and fill the [`top_point`](Self::top_point) and the
+/// [`direction`](Self::direction). The character then follows a scripted arc - first it rises
+/// until it is [`clearance`](Self::clearance) above the `top_point`, and then it moves forward
+/// until it is [`over_distance`](Self::over_distance) past it. For vaulting, set `over_distance`
+/// to be a bit more than the obstacle's depth. For mantling, set it to a bit more than the
+/// character's radius.
+///
+/// Once started, the arc will be completed even if the action is no longer fed. The
+/// [state](TnuaBuiltinVaultState) exposes the current phase and its progress, for animating the
+/// vault.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinVault {
+    /// The entity of the obstacle.
+    pub obstacle_entity: Option<Entity>,
+
+    /// The point on the top edge of the obstacle that is closest to the character.
+    ///
+    /// This input parameter is cached when the action starts.
+    pub top_point: Vector3,
+
+    /// The horizontal direction to vault in.
+    ///
+    /// This input parameter is cached when the action starts.
+    pub direction: Dir3,
+
+    /// How high above the [`top_point`](Self::top_point) the character's center should be when
+    /// it goes over the obstacle.
+    ///
+    /// This should usually be a little higher than
+    /// [`TnuaBuiltinWalk::float_height`](crate::builtins::TnuaBuiltinWalk::float_height).
+    pub clearance: Float,
+
+    /// How far past the [`top_point`](Self::top_point), horizontally, the character's center
+    /// should move before the action finishes.
+    pub over_distance: Float,
+
+    /// The duration, in seconds, of rising above the obstacle.
+    pub rise_duration: Float,
+
+    /// The duration, in seconds, of moving over the obstacle.
+    pub over_duration: Float,
+}
+
+impl Default for TnuaBuiltinVault {
+    fn default() -> Self {
+        Self {
+            obstacle_entity: None,
+            top_point: Vector3::NAN,
+            direction: Dir3::NEG_Z,
+            clearance: 1.2,
+            over_distance: 1.0,
+            rise_duration: 0.2,
+            over_duration: 0.2,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinVault {
+    const NAME: &'static str = "TnuaBuiltinVault";
+    type State = TnuaBuiltinVaultState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        _lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        let up = ctx.up_direction.adjust_precision();
+
+        // TODO: Once `std::mem::variant_count` gets stabilized, use that instead.
+        for _ in 0..3 {
+            return match state {
+                TnuaBuiltinVaultState::NoVault => {
+                    let Ok(direction) = Dir3::new(self.direction.reject_from(*ctx.up_direction))
+                    else {
+                        return TnuaActionLifecycleDirective::Finished;
+                    };
+                    let start = ctx.tracker.translation;
+                    let over_height = self.top_point + self.clearance * up;
+                    *state = TnuaBuiltinVaultState::Rising {
+                        direction,
+                        start,
+                        apex: start + (over_height - start).dot(up) * up,
+                        end: over_height + self.over_distance * direction.adjust_precision(),
+                        elapsed: 0.0,
+                        progress: 0.0,
+                    };
+                    continue;
+                }
+                TnuaBuiltinVaultState::Rising {
+                    direction,
+                    start,
+                    apex,
+                    end,
+                    elapsed,
+                    progress,
+                } => {
+                    *elapsed += ctx.frame_duration;
+                    *progress = phase_progress(*elapsed, self.rise_duration);
+                    if 1.0 <= *progress {
+                        *state = TnuaBuiltinVaultState::GoingOver {
+                            direction: *direction,
+                            start: *apex,
+                            end: *end,
+                            elapsed: 0.0,
+                            progress: 0.0,
+                        };
+                        continue;
+                    }
+                    // Ease-out, so that the character will reach the apex with no vertical speed.
+                    let next_position =
+                        start.lerp(*apex, (float_consts::FRAC_PI_2 * *progress).sin());
+                    apply_scripted_motion(&ctx, motor, next_position, *direction);
+                    TnuaActionLifecycleDirective::StillActive
+                }
+                TnuaBuiltinVaultState::GoingOver {
+                    direction,
+                    start,
+                    end,
+                    elapsed,
+                    progress,
+                } => {
+                    *elapsed += ctx.frame_duration;
+                    *progress = phase_progress(*elapsed, self.over_duration);
+                    if 1.0 <= *progress {
+                        return TnuaActionLifecycleDirective::Finished;
+                    }
+                    // Constant speed, so that the character will leave the obstacle with that
+                    // speed instead of stopping on it.
+                    let next_position = start.lerp(*end, *progress);
+                    apply_scripted_motion(&ctx, motor, next_position, *direction);
+                    TnuaActionLifecycleDirective::StillActive
+                }
+            };
+        }
+        error!("Tnua could not decide on vault state");
+        TnuaActionLifecycleDirective::Finished
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if self.top_point.is_finite() {
+            TnuaActionInitiationDirective::Allow
+        } else {
+            TnuaActionInitiationDirective::Reject
+        }
+    }
+
+    fn target_entity(&self, _state: &Self::State) -> Option<Entity> {
+        self.obstacle_entity
+    }
+
+    fn suppresses_landing(&self, state: &Self::State) -> bool {
+        !matches!(state, TnuaBuiltinVaultState::NoVault)
+    }
+}
+
+fn phase_progress(elapsed: Float, duration: Float) -> Float {
+    if 0.0 < duration {
+        (elapsed / duration).min(1.0)
+    } else {
+        1.0
+    }
+}
+
+fn apply_scripted_motion(
+    ctx: &TnuaActionContext,
+    motor: &mut TnuaMotor,
+    next_position: Vector3,
+    direction: Dir3,
+) {
+    let desired_velocity = (next_position - ctx.tracker.translation) / ctx.frame_duration;
+    motor.lin = Default::default();
+    motor.lin.acceleration = -ctx.tracker.gravity;
+    motor.lin.boost = desired_velocity - ctx.tracker.velocity;
+
+    motor
+        .ang
+        .cancel_on_axis(ctx.up_direction.adjust_precision());
+    motor.ang += ctx.turn_to_direction(direction, ctx.up_direction);
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinVaultState {
+    #[default]
+    NoVault,
+    /// Rising above the obstacle.
+    Rising {
+        direction: Dir3,
+        start: Vector3,
+        /// Where the character will be when it finishes rising.
+        apex: Vector3,
+        /// Where the character will be when it finishes moving over the obstacle.
+        end: Vector3,
+        elapsed: Float,
+        /// How much of the rising is done, from 0.0 to 1.0.
+        progress: Float,
+    },
+    /// Moving over the obstacle.
+    GoingOver {
+        direction: Dir3,
+        start: Vector3,
+        end: Vector3,
+        elapsed: Float,
+        /// How much of the moving over is done, from 0.0 to 1.0.
+        progress: Float,
+    },
+}
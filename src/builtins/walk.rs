@@ -1,9 +1,10 @@
 use std::time::Duration;
 
-use crate::math::{float_consts, AdjustPrecision, AsF32, Float, Quaternion, Vector3};
+use crate::math::{float_consts, AdjustPrecision, AsF32, Float, Vector3};
 use bevy::prelude::*;
 
-use crate::util::rotation_arc_around_axis;
+use crate::prelude::TnuaController;
+use crate::util::calc_angular_velchange_to_stay_upright;
 use crate::TnuaBasisContext;
 use crate::{TnuaBasis, TnuaVelChange};
 
@@ -32,7 +33,8 @@ use crate::{TnuaBasis, TnuaVelChange};
 ///   leaving `desired_forward` as the default `Vector3::ZERO` may cause the character to spin
 ///   uncontrollably when it contacts other colliders. Unless, of course, some other mechanism
 ///   prevents that.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinWalk {
     /// The direction (in the world space) and speed to accelerate to.
     ///
@@ -147,9 +149,24 @@ impl TnuaBasis for TnuaBuiltinWalk {
     type State = TnuaBuiltinWalkState;
 
     fn apply(&self, state: &mut Self::State, ctx: TnuaBasisContext, motor: &mut crate::TnuaMotor) {
+        state.landing_report = None;
+        let ground = if std::mem::take(&mut state.landing_suppressed) {
+            None
+        } else {
+            ctx.proximity_sensor.output.as_ref()
+        };
         if let Some(stopwatch) = &mut state.airborne_timer {
             #[allow(clippy::unnecessary_cast)]
             stopwatch.tick(Duration::from_secs_f64(ctx.frame_duration as f64));
+            if let Some(airborne) = &mut state.airborne {
+                airborne.airtime += ctx.frame_duration;
+                // Integrate the height frame by frame, so that it stays coherent when the up
+                // direction changes mid-air (e.g. with radial gravity)
+                airborne.height += (ctx.tracker.translation - airborne.last_position)
+                    .dot(ctx.up_direction.adjust_precision());
+                airborne.last_position = ctx.tracker.translation;
+                airborne.highest_height = airborne.highest_height.max(airborne.height);
+            }
         }
 
         let climb_vectors: Option<ClimbVectors>;
@@ -157,7 +174,7 @@ impl TnuaBasis for TnuaBuiltinWalk {
         let impulse_to_offset: Vector3;
         let slipping_vector: Option<Vector3>;
 
-        if let Some(sensor_output) = &ctx.proximity_sensor.output {
+        if let Some(sensor_output) = ground {
             state.effective_velocity = ctx.tracker.velocity - sensor_output.entity_linvel;
             let sideways_unnormalized = sensor_output
                 .normal
@@ -325,7 +342,7 @@ impl TnuaBasis for TnuaBuiltinWalk {
                 match &mut state.airborne_timer {
                     None => {
                         if let (false, Some(sensor_output)) =
-                            (should_disable_due_to_slipping, &ctx.proximity_sensor.output)
+                            (should_disable_due_to_slipping, ground)
                         {
                             // not doing the jump calculation here
                             let spring_offset =
@@ -338,15 +355,35 @@ impl TnuaBasis for TnuaBuiltinWalk {
                                 self.coyote_time as f32,
                                 TimerMode::Once,
                             ));
+                            state.airborne = Some(AirborneState {
+                                takeoff_position: ctx.tracker.translation,
+                                last_position: ctx.tracker.translation,
+                                height: 0.0,
+                                highest_height: 0.0,
+                                airtime: 0.0,
+                            });
                             continue;
                         }
                     }
                     Some(_) => {
                         if let (false, Some(sensor_output)) =
-                            (should_disable_due_to_slipping, &ctx.proximity_sensor.output)
+                            (should_disable_due_to_slipping, ground)
                         {
                             if sensor_output.proximity.adjust_precision() <= self.float_height {
                                 state.airborne_timer = None;
+                                if let Some(airborne) = state.airborne.take() {
+                                    let up = ctx.up_direction.adjust_precision();
+                                    state.landing_report = Some(TnuaBuiltinWalkLandingReport {
+                                        impact_velocity: (ctx.tracker.velocity
+                                            - sensor_output.entity_linvel)
+                                            .dot(up),
+                                        fall_height: airborne.highest_height - airborne.height,
+                                        takeoff_position: airborne.takeoff_position,
+                                        airtime: airborne.airtime,
+                                        surface_entity: sensor_output.entity,
+                                        surface_normal: sensor_output.normal,
+                                    });
+                                }
                                 continue;
                             }
                         }
@@ -371,48 +408,14 @@ impl TnuaBasis for TnuaBuiltinWalk {
             - impulse_to_offset;
         state.running_velocity = new_velocity.reject_from(ctx.up_direction.adjust_precision());
 
-        // Tilt
-
-        let torque_to_fix_tilt = {
-            let tilted_up = ctx.tracker.rotation.mul_vec3(Vector3::Y);
-
-            let rotation_required_to_fix_tilt =
-                Quaternion::from_rotation_arc(tilted_up, ctx.up_direction.adjust_precision());
-
-            let desired_angvel = (rotation_required_to_fix_tilt.xyz() / ctx.frame_duration)
-                .clamp_length_max(self.tilt_offset_angvel);
-            let angular_velocity_diff = desired_angvel - ctx.tracker.angvel;
-            angular_velocity_diff.clamp_length_max(ctx.frame_duration * self.tilt_offset_angacl)
-        };
-
-        // Turning
-
-        let desired_angvel = if let Some(desired_forward) = self.desired_forward {
-            let current_forward = ctx.tracker.rotation.mul_vec3(Vector3::NEG_Z);
-            let rotation_along_up_axis = rotation_arc_around_axis(
-                ctx.up_direction,
-                current_forward,
-                desired_forward.adjust_precision(),
-            )
-            .unwrap_or(0.0);
-            (rotation_along_up_axis / ctx.frame_duration)
-                .clamp(-self.turning_angvel, self.turning_angvel)
-        } else {
-            0.0
-        };
-
-        // NOTE: This is the regular axis system so we used the configured up.
-        let existing_angvel = ctx.tracker.angvel.dot(ctx.up_direction.adjust_precision());
-
-        // This is the torque. Should it be clamped by an acceleration? From experimenting with
-        // this I think it's meaningless and only causes bugs.
-        let torque_to_turn = desired_angvel - existing_angvel;
-
-        let existing_turn_torque = torque_to_fix_tilt.dot(ctx.up_direction.adjust_precision());
-        let torque_to_turn = torque_to_turn - existing_turn_torque;
-
-        motor.ang = TnuaVelChange::boost(
-            torque_to_fix_tilt + torque_to_turn * ctx.up_direction.adjust_precision(),
+        motor.ang = calc_angular_velchange_to_stay_upright(
+            ctx.tracker,
+            ctx.up_direction,
+            self.desired_forward,
+            ctx.frame_duration,
+            self.tilt_offset_angvel,
+            self.tilt_offset_angacl,
+            self.turning_angvel,
         );
     }
 
@@ -452,6 +455,10 @@ impl TnuaBasis for TnuaBuiltinWalk {
             timer.set_duration(Duration::ZERO);
         }
     }
+
+    fn suppress_landing(&self, state: &mut Self::State) {
+        state.landing_suppressed = true;
+    }
 }
 
 impl TnuaBuiltinWalk {
@@ -485,15 +492,84 @@ impl TnuaBuiltinWalk {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct StandingOnState {
     entity: Entity,
     entity_linvel: Vector3,
 }
 
-#[derive(Default, Clone, Debug)]
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct AirborneState {
+    takeoff_position: Vector3,
+    last_position: Vector3,
+    /// The height above the takeoff position, accumulated along the up direction of each frame.
+    height: Float,
+    highest_height: Float,
+    airtime: Float,
+}
+
+/// Information about the character landing, recorded by [`TnuaBuiltinWalk`].
+///
+/// See [`TnuaBuiltinWalkState::landing_report`] and [`TnuaBuiltinWalkLanded`].
+#[derive(Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinWalkLandingReport {
+    /// The velocity of the character along the up direction, relative to the surface it landed
+    /// on, at the moment of landing. Negative when the character was falling.
+    pub impact_velocity: Float,
+    /// The height the character fell from - measured from the highest point it reached while
+    /// airborne (which is never lower than where it took off from) to where it landed.
+    pub fall_height: Float,
+    /// The position of the character when it stopped being grounded.
+    pub takeoff_position: Vector3,
+    /// The total time, in seconds, that the character was airborne - including the coyote time.
+    pub airtime: Float,
+    /// The entity the character landed on.
+    pub surface_entity: Entity,
+    /// The normal of the surface the character landed on.
+    pub surface_normal: Dir3,
+}
+
+/// An event sent when a character controlled by a [`TnuaBuiltinWalk`] basis lands.
+///
+/// It is sent by [`TnuaControllerPlugin`](crate::prelude::TnuaControllerPlugin) on the same frame
+/// [`TnuaBuiltinWalkState::landing_report`] returns the report.
+#[derive(Event, Debug, Clone)]
+pub struct TnuaBuiltinWalkLanded {
+    /// The character entity.
+    pub entity: Entity,
+    pub report: TnuaBuiltinWalkLandingReport,
+}
+
+pub(crate) fn send_walk_landed_events(
+    query: Query<(Entity, &TnuaController)>,
+    mut writer: EventWriter<TnuaBuiltinWalkLanded>,
+) {
+    for (entity, controller) in query.iter() {
+        if !controller.applied_this_frame() {
+            continue;
+        }
+        let Some((_, state)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
+            continue;
+        };
+        if let Some(report) = state.landing_report() {
+            writer.write(TnuaBuiltinWalkLanded {
+                entity,
+                report: report.clone(),
+            });
+        }
+    }
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinWalkState {
     airborne_timer: Option<Timer>,
+    airborne: Option<AirborneState>,
+    landing_report: Option<TnuaBuiltinWalkLandingReport>,
+    landing_suppressed: bool,
     /// The current distance of the character from the distance its supposed to float at.
     pub standing_offset: Vector3,
     standing_on: Option<StandingOnState>,
@@ -515,6 +591,15 @@ impl TnuaBuiltinWalkState {
 
     pub fn reset_airborne_timer(&mut self) {
         self.airborne_timer = None;
+        self.airborne = None;
+    }
+
+    /// The report of the character's landing, if it landed this frame.
+    ///
+    /// The report stays when the controller is not applied, so it should only be acted upon when
+    /// [`TnuaController::applied_this_frame`] is `true`.
+    pub fn landing_report(&self) -> Option<&TnuaBuiltinWalkLandingReport> {
+        self.landing_report.as_ref()
     }
 }
 
@@ -0,0 +1,185 @@
+use crate::math::{AdjustPrecision, AsF32, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::MotionHelper;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+/// An [action](TnuaAction) for jumping off walls.
+///
+/// The character is launched away from the wall at
+/// [`horizontal_speed`](Self::horizontal_speed) and upward at
+/// [`vertical_speed`](Self::vertical_speed), keeping its velocity along the wall. After the
+/// launch, for [`lockout_duration`](Self::lockout_duration) seconds, the basis is not allowed to
+/// accelerate the character back toward the wall - so that the player will not immediately cancel
+/// the wall jump by holding the movement input toward the wall.
+///
+/// Once launched, the action continues until the lockout is over even if it is no longer fed.
+/// During the lockout the basis will not land, so that wall jumps close to the ground will not be
+/// cut short.
+///
+/// By default, a wall jump refreshes the air actions budget counted by
+/// [`TnuaSimpleAirActionsCounter`](crate::control_helpers::TnuaSimpleAirActionsCounter) - it is
+/// counted like a jump from the ground. Set
+/// [`refresh_air_actions`](Self::refresh_air_actions) to `false` to make it count as a regular
+/// air action instead.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinWallJump {
+    /// The entity of the wall to jump off.
+    pub wall_entity: Option<Entity>,
+
+    /// The point on the wall where the character touches it.
+    ///
+    /// This input parameter is cached when the action starts.
+    pub contact_point: Vector3,
+
+    /// The wall's normal.
+    ///
+    /// Only the horizontal part of the normal is used, so slanted walls still launch the
+    /// character horizontally. This input parameter is cached when the action starts.
+    pub normal: Dir3,
+
+    /// The speed to launch the character away from the wall at.
+    pub horizontal_speed: Float,
+
+    /// The upward speed to launch the character at.
+    pub vertical_speed: Float,
+
+    /// The duration, in seconds, after the launch in which the basis cannot accelerate the
+    /// character toward the wall.
+    pub lockout_duration: Float,
+
+    /// Force the character to face in a particular direction during the wall jump.
+    pub force_forward: Option<Dir3>,
+
+    /// When `true`, the air actions budget is refreshed by the wall jump.
+    ///
+    /// This is what the action returns from
+    /// [`refreshes_air_actions`](TnuaAction::refreshes_air_actions).
+    pub refresh_air_actions: bool,
+}
+
+impl Default for TnuaBuiltinWallJump {
+    fn default() -> Self {
+        Self {
+            wall_entity: None,
+            contact_point: Vector3::ZERO,
+            normal: Dir3::Y, // obviously invalid value
+            horizontal_speed: 6.0,
+            vertical_speed: 10.0,
+            lockout_duration: 0.25,
+            force_forward: None,
+            refresh_air_actions: true,
+        }
+    }
+}
+
+impl TnuaAction for TnuaBuiltinWallJump {
+    const NAME: &'static str = "TnuaBuiltinWallJump";
+    type State = TnuaBuiltinWallJumpState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if matches!(lifecycle_status, TnuaActionLifecycleStatus::CancelledInto) {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        let up = ctx.up_direction.adjust_precision();
+
+        let normal = match state {
+            TnuaBuiltinWallJumpState::NoJump => {
+                let normal = self
+                    .normal
+                    .adjust_precision()
+                    .reject_from(up)
+                    .normalize_or_zero();
+                let velocity_along_wall = ctx.tracker.velocity.reject_from(up).reject_from(normal);
+                let launch_velocity =
+                    velocity_along_wall + self.horizontal_speed * normal + self.vertical_speed * up;
+                motor.lin = TnuaVelChange::boost(launch_velocity - ctx.tracker.velocity);
+                *state = TnuaBuiltinWallJumpState::Lockout {
+                    contact_point: self.contact_point,
+                    normal,
+                    elapsed: 0.0,
+                };
+                normal
+            }
+            TnuaBuiltinWallJumpState::Lockout {
+                contact_point: _,
+                normal,
+                elapsed,
+            } => {
+                *elapsed += ctx.frame_duration;
+                if self.lockout_duration <= *elapsed {
+                    return TnuaActionLifecycleDirective::Finished;
+                }
+                let normal = *normal;
+                for vector in [&mut motor.lin.acceleration, &mut motor.lin.boost] {
+                    let toward_wall = -vector.dot(normal);
+                    if 0.0 < toward_wall {
+                        *vector += toward_wall * normal;
+                    }
+                }
+                normal
+            }
+        };
+
+        if let Some(force_forward) = self.force_forward {
+            motor.ang.cancel_on_axis(up);
+            motor.ang += ctx.turn_to_direction(force_forward, ctx.up_direction);
+        } else if let Ok(away_from_wall) = Dir3::new(normal.f32()) {
+            motor.ang.cancel_on_axis(up);
+            motor.ang += ctx.turn_to_direction(away_from_wall, ctx.up_direction);
+        }
+
+        TnuaActionLifecycleDirective::StillActive
+    }
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if 0.99 < self.normal.dot(*ctx.up_direction).abs() {
+            TnuaActionInitiationDirective::Reject
+        } else {
+            TnuaActionInitiationDirective::Allow
+        }
+    }
+
+    fn target_entity(&self, _state: &Self::State) -> Option<Entity> {
+        self.wall_entity
+    }
+
+    fn suppresses_landing(&self, state: &Self::State) -> bool {
+        matches!(state, TnuaBuiltinWallJumpState::Lockout { .. })
+    }
+
+    fn refreshes_air_actions(&self, _state: &Self::State) -> bool {
+        self.refresh_air_actions
+    }
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinWallJumpState {
+    #[default]
+    NoJump,
+    /// The character was launched, and cannot accelerate toward the wall.
+    Lockout {
+        /// The point on the wall the character jumped off.
+        contact_point: Vector3,
+        /// The horizontal normal of the wall.
+        normal: Vector3,
+        elapsed: Float,
+    },
+}
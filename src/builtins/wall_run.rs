@@ -0,0 +1,272 @@
+use crate::math::{AdjustPrecision, AsF32, Float, Vector3};
+use bevy::prelude::*;
+
+use crate::util::MotionHelper;
+use crate::{
+    prelude::*, TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+
+/// An [action](TnuaAction) for running horizontally along walls.
+///
+/// Use [`find_runnable_wall`](crate::radar_lens::TnuaRadarBlipLens::find_runnable_wall) to find
+/// the wall and fill the [`contact_point_with_wall`](Self::contact_point_with_wall) and the
+/// [`normal`](Self::normal). The action can only start while the character is airborne and moving
+/// along the wall at [`min_entry_speed`](Self::min_entry_speed) or faster. The direction of the
+/// run (left or right along the wall) is decided by that movement.
+///
+/// While running, the character's height is held - or, if [`height_drop`](Self::height_drop) is
+/// set, decays along a curve. The action ends when:
+///
+/// * It is no longer fed.
+/// * It reaches its [`max_duration`](Self::max_duration).
+/// * The character reaches the ground.
+/// * [`jump`](Self::jump) is set - in which case the character jumps off the wall.
+///
+/// The [state](TnuaBuiltinWallRunState) contains the side of the wall and a suggested camera tilt,
+/// for cameras that lean away from the wall.
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TnuaBuiltinWallRun {
+    /// The entity of the wall to run on.
+    pub wall_entity: Option<Entity>,
+
+    /// The point on the wall where the character touches it.
+    pub contact_point_with_wall: Vector3,
+
+    /// The wall's normal. It should be perpendicular to the up direction.
+    pub normal: Dir3,
+
+    /// The speed to run along the wall at.
+    pub run_speed: Float,
+
+    /// The acceleration used for reaching the [`run_speed`](Self::run_speed).
+    pub acceleration: Float,
+
+    /// The action will not start unless the character's speed along the wall is at least this
+    /// fast.
+    pub min_entry_speed: Float,
+
+    /// The maximum duration, in seconds, of the run.
+    pub max_duration: Float,
+
+    /// How much height the character loses throughout the [`max_duration`](Self::max_duration)
+    /// of the run. Set to 0.0 to hold the height for the entire run.
+    pub height_drop: Float,
+
+    /// The shape of the height drop curve. With 1.0 the height drops linearly, and with higher
+    /// values the height is held for longer at the start of the run and then drops faster.
+    pub height_drop_exponent: Float,
+
+    /// A distance to maintain from the wall.
+    ///
+    /// Specifically - the distance from
+    /// [`contact_point_with_wall`](Self::contact_point_with_wall) in the direction of the
+    /// [`normal`](Self::normal). When `None`, the character only stops moving away from the wall.
+    pub maintain_distance: Option<Float>,
+
+    /// Set to `true` to jump off the wall, finishing the action.
+    pub jump: bool,
+
+    /// The speed added by jumping off the wall.
+    pub jump_speed: Float,
+
+    /// The direction of the jump off the wall. 0.0 is straight up, 1.0 is straight away from the
+    /// wall, and values in between mix the two.
+    ///
+    /// The speed along the wall is kept when jumping.
+    pub jump_normal_ratio: Float,
+
+    /// The maximum angle, in radians, of the camera tilt suggested in the
+    /// [state](TnuaBuiltinWallRunState).
+    pub camera_tilt_angle: Float,
+
+    /// The time, in seconds, it takes for the suggested camera tilt to reach its maximum angle at
+    /// the start of the run (and to go back to zero when the run reaches its
+    /// [`max_duration`](Self::max_duration))
+    pub camera_tilt_duration: Float,
+}
+
+impl Default for TnuaBuiltinWallRun {
+    fn default() -> Self {
+        Self {
+            wall_entity: None,
+            contact_point_with_wall: Vector3::ZERO,
+            normal: Dir3::Y, // obviously invalid value
+            run_speed: 8.0,
+            acceleration: 30.0,
+            min_entry_speed: 4.0,
+            max_duration: 1.5,
+            height_drop: 1.0,
+            height_drop_exponent: 2.0,
+            maintain_distance: None,
+            jump: false,
+            jump_speed: 10.0,
+            jump_normal_ratio: 0.5,
+            camera_tilt_angle: 0.2,
+            camera_tilt_duration: 0.2,
+        }
+    }
+}
+
+impl TnuaBuiltinWallRun {
+    /// The speed of the character along the wall. Positive when the wall is on the right.
+    fn speed_along_wall(&self, ctx: &TnuaActionContext) -> Float {
+        let right_wall_run_direction = self.normal.cross(*ctx.up_direction).adjust_precision();
+        ctx.tracker.velocity.dot(right_wall_run_direction)
+    }
+}
+
+impl TnuaAction for TnuaBuiltinWallRun {
+    const NAME: &'static str = "TnuaBuiltinWallRun";
+    type State = TnuaBuiltinWallRunState;
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if !lifecycle_status.is_active() {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        let up = ctx.up_direction.adjust_precision();
+        let normal = self.normal.adjust_precision();
+
+        if let TnuaBuiltinWallRunState::NoRun = state {
+            *state = TnuaBuiltinWallRunState::Running {
+                wall_side: self.speed_along_wall(&ctx).signum(),
+                run_direction: Dir3::NEG_Z,
+                start_height: ctx.tracker.translation.dot(up),
+                elapsed: 0.0,
+                camera_tilt: 0.0,
+            };
+        }
+
+        let TnuaBuiltinWallRunState::Running {
+            wall_side,
+            run_direction,
+            start_height,
+            elapsed,
+            camera_tilt,
+        } = state
+        else {
+            unreachable!("state was set to Running above");
+        };
+
+        let is_first_frame = *elapsed == 0.0;
+        *elapsed += ctx.frame_duration;
+        if self.max_duration <= *elapsed || (!is_first_frame && !ctx.basis.is_airborne()) {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        // Recalculated every frame, to follow curved walls.
+        if let Ok(direction) = Dir3::new((*wall_side * normal.cross(up)).f32()) {
+            *run_direction = direction;
+        }
+
+        let tilt_ramp = if 0.0 < self.camera_tilt_duration {
+            (*elapsed / self.camera_tilt_duration)
+                .min((self.max_duration - *elapsed) / self.camera_tilt_duration)
+                .min(1.0)
+        } else {
+            1.0
+        };
+        *camera_tilt = -*wall_side * self.camera_tilt_angle * tilt_ramp;
+
+        motor
+            .ang
+            .cancel_on_axis(ctx.up_direction.adjust_precision());
+        motor.ang += ctx.turn_to_direction(*run_direction, ctx.up_direction);
+
+        if self.jump {
+            let jump_direction = up.lerp(normal, self.jump_normal_ratio).normalize_or_zero();
+            let velocity_along_wall = ctx
+                .tracker
+                .velocity
+                .project_onto_normalized(run_direction.adjust_precision());
+            motor.lin = TnuaVelChange::boost(
+                velocity_along_wall + self.jump_speed * jump_direction - ctx.tracker.velocity,
+            );
+            return TnuaActionLifecycleDirective::Finished;
+        }
+
+        let progress = (*elapsed / self.max_duration).min(1.0);
+        let target_height =
+            *start_height - self.height_drop * progress.powf(self.height_drop_exponent);
+        let current_height = ctx.tracker.translation.dot(up);
+        let desired_vertical_velocity = (target_height - current_height) / ctx.frame_duration;
+        let vertical_boost = desired_vertical_velocity - ctx.tracker.velocity.dot(up);
+
+        motor.lin = ctx.negate_gravity()
+            + TnuaVelChange::boost(vertical_boost * up)
+            + ctx.adjust_velocity(
+                self.run_speed * run_direction.adjust_precision(),
+                self.acceleration,
+                |v| v.project_onto_normalized(run_direction.adjust_precision()),
+            );
+
+        let current_distance = (ctx.tracker.translation - self.contact_point_with_wall).dot(normal);
+        let current_cling_speed = ctx.tracker.velocity.dot(normal);
+        let desired_cling_speed = if let Some(maintain_distance) = self.maintain_distance {
+            (maintain_distance - current_distance) / ctx.frame_duration
+        } else {
+            current_cling_speed.min(0.0)
+        };
+        motor.lin += TnuaVelChange::boost((desired_cling_speed - current_cling_speed) * normal);
+
+        TnuaActionLifecycleDirective::StillActive
+    }
+
+    fn initiation_decision(
+        &self,
+        ctx: TnuaActionContext,
+        _being_fed_for: &bevy::time::Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        if 0.1 < self.normal.dot(*ctx.up_direction).abs() {
+            TnuaActionInitiationDirective::Reject
+        } else if !ctx.basis.is_airborne()
+            || self.speed_along_wall(&ctx).abs() < self.min_entry_speed
+        {
+            TnuaActionInitiationDirective::Delay
+        } else {
+            TnuaActionInitiationDirective::Allow
+        }
+    }
+
+    fn target_entity(&self, _state: &Self::State) -> Option<Entity> {
+        self.wall_entity
+    }
+}
+
+#[derive(Default, Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TnuaBuiltinWallRunState {
+    #[default]
+    NoRun,
+    Running {
+        /// `1.0` if the wall is on the character's right, `-1.0` if it is on its left.
+        wall_side: Float,
+        /// The direction along the wall the character runs in.
+        run_direction: Dir3,
+        /// The height (along the up direction) the run started at.
+        start_height: Float,
+        elapsed: Float,
+        /// A suggested roll angle, in radians, for the camera.
+        ///
+        /// This is a rotation around the `run_direction` that tilts the camera's up direction away
+        /// from the wall. It goes from zero to the action's
+        /// [`camera_tilt_angle`](TnuaBuiltinWallRun::camera_tilt_angle) at the start of the run,
+        /// and back to zero as the run approaches its
+        /// [`max_duration`](TnuaBuiltinWallRun::max_duration).
+        ///
+        /// A run that ends earlier - because it is no longer fed, is cancelled, jumps off the wall
+        /// or reaches the ground - ends with the tilt still applied, and the state is gone with
+        /// the action. The camera code should ease its tilt back to zero on its own once the
+        /// action is over, instead of dropping it at once.
+        camera_tilt: Float,
+    },
+}
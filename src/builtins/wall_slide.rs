@@ -7,7 +7,8 @@ use crate::{
 use bevy::prelude::*;
 
 /// An [action](TnuaAction) for sliding on walls.
-#[derive(Clone)]
+#[derive(Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinWallSlide {
     /// The entity of the wall to slide on.
     pub wall_entity: Option<Entity>,
@@ -160,5 +161,6 @@ impl TnuaAction for TnuaBuiltinWallSlide {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TnuaBuiltinWallSlideState {}
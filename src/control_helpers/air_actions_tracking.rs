@@ -102,6 +102,12 @@ pub enum TnuaAirActionsUpdate {
 /// A simple counter that counts together all the air actions a character is able to perform.
 ///
 /// It's [`update`](Self::update) must be called every frame.
+///
+/// Only the actions of the [main action layer](TnuaController::MAIN_ACTION_LAYER) are counted.
+/// Actions that [refresh the air actions](crate::TnuaAction::refreshes_air_actions) (like
+/// [`TnuaBuiltinWallJump`](crate::builtins::TnuaBuiltinWallJump)) reset the counter to 1 when they
+/// start in the air, as if the character has just jumped from the ground - regardless of the layer
+/// they run in.
 #[derive(Component, Default)]
 pub struct TnuaSimpleAirActionsCounter {
     tracker: TnuaAirActionsTracker,
@@ -121,6 +127,14 @@ impl TnuaSimpleAirActionsCounter {
                 self.air_actions_count += 1;
             }
             TnuaAirActionsUpdate::AirActionStarted(action_name) => {
+                if controller
+                    .dynamic_action()
+                    .is_some_and(|action| action.refreshes_air_actions())
+                {
+                    // Count the action like a jump from the ground - it gets an air number of 0,
+                    // and the counter is left at 1 like after any other takeoff.
+                    self.air_actions_count = 0;
+                }
                 self.current_action = Some((action_name, self.air_actions_count));
                 self.air_actions_count += 1;
             }
@@ -132,6 +146,17 @@ impl TnuaSimpleAirActionsCounter {
                 self.air_actions_count = 0;
             }
         }
+        // Actions on the other layers are not counted, but can still refresh the air actions.
+        for (layer_name, layer) in controller.iter_action_layers() {
+            if layer_name != TnuaController::MAIN_ACTION_LAYER
+                && layer.action_flow_status().just_starting().is_some()
+                && layer
+                    .dynamic_action()
+                    .is_some_and(|action| action.refreshes_air_actions())
+            {
+                self.reset_count();
+            }
+        }
     }
 
     /// Resets the air actions counter to a specific count, excluding the current action.
@@ -170,8 +195,9 @@ impl TnuaSimpleAirActionsCounter {
     ///
     /// * When the character is grounded, this method returns `None`. This is only for mutating the
     ///   counter while the character is airborne.
-    /// * When the character jumps from the ground, or starts a free fall, the counter is one - not
-    ///   zero. Setting the counter to 0 will mean that the next air jump will actually be treated
+    /// * When the character jumps from the ground, starts a free fall, or starts an action that
+    ///   [refreshes the air actions](crate::TnuaAction::refreshes_air_actions), the counter is
+    ///   one - not zero. Setting the counter to 0 will mean that the next air jump will actually be treated
     ///   as a ground jump - and they'll get another air jump in addition to it. This is usually
     ///   not the desired behavior.
     /// * Changing the action counter returned by this method will not affect the value
@@ -0,0 +1,186 @@
+use bevy::ecs::component::HookContext;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+use bevy_tnua_physics_integration_layer::math::{AdjustPrecision, Float, Vector3};
+
+use crate::{TnuaGravity, TnuaPipelineStages, TnuaSystemSet};
+
+/// A plugin required for making [`TnuaGravitySource`] work.
+pub struct TnuaGravitySourcesPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl TnuaGravitySourcesPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for TnuaGravitySourcesPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for TnuaGravitySourcesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TnuaGravitySource>();
+        app.register_type::<TnuaAffectedByGravitySources>();
+        app.add_systems(
+            self.schedule,
+            update_gravity_from_sources_system
+                .in_set(TnuaSystemSet)
+                .before(TnuaPipelineStages::Sensors),
+        );
+    }
+}
+
+/// A source of gravity, for walking on planets and other non-flat bodies.
+///
+/// Add this component to the entity of the body (e.g. a planet), and add
+/// [`TnuaAffectedByGravitySources`] to the characters that should be pulled by it. Every frame,
+/// [`TnuaGravitySourcesPlugin`] will set the [`TnuaGravity`] of each such character based on the
+/// gravity sources it is inside of.
+///
+/// Since the up direction Tnua uses is the opposite of the gravity, the character will be rotated
+/// so that its feet point toward the body (the bases do that using their `tilt_offset_angvel` and
+/// `tilt_offset_angacl` parameters). For that to work, the character's rotation must **not** be
+/// locked by the physics backend.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TnuaGravitySource {
+    /// The shape of the body, which determines the direction of the gravity.
+    pub shape: TnuaGravitySourceShape,
+    /// The magnitude of the gravity applied to characters inside the source.
+    pub strength: Float,
+    /// Characters further than this distance from the [`shape`](Self::shape) are not affected by
+    /// this source.
+    pub radius: Float,
+    /// When a character is inside multiple gravity sources, only the one with the highest priority
+    /// affects it. If multiple sources share the highest priority, the closest one is used.
+    pub priority: i32,
+}
+
+impl Default for TnuaGravitySource {
+    fn default() -> Self {
+        Self {
+            shape: TnuaGravitySourceShape::Point,
+            strength: 9.81,
+            radius: Float::INFINITY,
+            priority: 0,
+        }
+    }
+}
+
+/// The shape of a [`TnuaGravitySource`].
+#[derive(Clone, Debug, Reflect)]
+pub enum TnuaGravitySourceShape {
+    /// Pull toward the position of the entity. Use this for spherical bodies.
+    Point,
+    /// Pull toward the closest point on a line segment that goes through the position of the
+    /// entity along its local Y axis. Use this for cylinders and capsules.
+    Line {
+        /// The distance from the entity's position to each end of the segment.
+        ///
+        /// This is in world units - the entity's scale does not affect it.
+        half_length: Float,
+    },
+}
+
+impl TnuaGravitySource {
+    /// The gravity this source applies at a point in the world space, or `None` if the point is
+    /// outside the source's [`radius`](Self::radius).
+    ///
+    /// `transform` is the transform of the entity that holds the source.
+    pub fn gravity_at(&self, transform: &GlobalTransform, point: Vector3) -> Option<Vector3> {
+        let offset = self.offset_from_shape(transform, point);
+        if self.radius < offset.length() {
+            return None;
+        }
+        Some(-self.strength * offset.normalize_or_zero())
+    }
+
+    /// The vector from the closest point of the source's shape to a point in the world space.
+    pub fn offset_from_shape(&self, transform: &GlobalTransform, point: Vector3) -> Vector3 {
+        let center = transform.translation().adjust_precision();
+        match self.shape {
+            TnuaGravitySourceShape::Point => point - center,
+            TnuaGravitySourceShape::Line { half_length } => {
+                let axis = transform.up().as_vec3().adjust_precision();
+                let along_axis = (point - center).dot(axis).clamp(-half_length, half_length);
+                point - (center + along_axis * axis)
+            }
+        }
+    }
+}
+
+/// Makes a character's gravity come from [`TnuaGravitySource`]s.
+///
+/// This will add a [`TnuaGravity`] component to the character (starting at the
+/// [`fallback`](Self::fallback), unless the character already has one), which
+/// [`TnuaGravitySourcesPlugin`] will update every frame.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[component(on_add = add_gravity_for_gravity_sources)]
+pub struct TnuaAffectedByGravitySources {
+    /// The gravity to use when the character is not inside any gravity source.
+    ///
+    /// Since [`TnuaGravity`] replaces the physics backend's global gravity, this should usually be
+    /// the same as that global gravity.
+    pub fallback: Vector3,
+}
+
+impl Default for TnuaAffectedByGravitySources {
+    fn default() -> Self {
+        Self {
+            fallback: Vector3::NEG_Y * 9.81,
+        }
+    }
+}
+
+fn add_gravity_for_gravity_sources(mut world: DeferredWorld, ctx: HookContext) {
+    let Some(affected) = world.get::<TnuaAffectedByGravitySources>(ctx.entity) else {
+        return;
+    };
+    let fallback = affected.fallback;
+    world
+        .commands()
+        .entity(ctx.entity)
+        .insert_if_new(TnuaGravity(fallback));
+}
+
+fn update_gravity_from_sources_system(
+    sources_query: Query<(&GlobalTransform, &TnuaGravitySource)>,
+    mut characters_query: Query<(
+        &GlobalTransform,
+        &TnuaAffectedByGravitySources,
+        &mut TnuaGravity,
+    )>,
+) {
+    for (character_transform, affected, mut gravity) in characters_query.iter_mut() {
+        let position = character_transform.translation().adjust_precision();
+        let mut best: Option<(i32, Float, Vector3)> = None;
+        for (source_transform, source) in sources_query.iter() {
+            let Some(source_gravity) = source.gravity_at(source_transform, position) else {
+                continue;
+            };
+            let distance = source
+                .offset_from_shape(source_transform, position)
+                .length();
+            let is_better = match best {
+                None => true,
+                Some((best_priority, best_distance, _)) => {
+                    best_priority < source.priority
+                        || (best_priority == source.priority && distance < best_distance)
+                }
+            };
+            if is_better {
+                best = Some((source.priority, distance, source_gravity));
+            }
+        }
+        gravity.0 = best.map_or(affected.fallback, |(_, _, source_gravity)| source_gravity);
+    }
+}
@@ -9,9 +9,11 @@
 mod air_actions_tracking;
 mod blip_reuse_avoidance;
 mod crouch_enforcer;
+mod gravity_sources;
 mod simple_fall_through_platforms;
 
 pub use air_actions_tracking::*;
 pub use blip_reuse_avoidance::*;
 pub use crouch_enforcer::*;
+pub use gravity_sources::*;
 pub use simple_fall_through_platforms::*;
@@ -3,16 +3,33 @@ use bevy::platform::collections::hash_map::Entry;
 use bevy::platform::collections::HashMap;
 use bevy::prelude::*;
 use bevy::time::Stopwatch;
-use bevy_tnua_physics_integration_layer::math::{AsF32, Float};
+use bevy_tnua_physics_integration_layer::math::{AsF32, Float, Vector3};
 
 use crate::basis_action_traits::{
     BoxableAction, BoxableBasis, DynamicAction, DynamicBasis, TnuaAction, TnuaActionContext,
     TnuaActionInitiationDirective, TnuaActionLifecycleDirective, TnuaActionLifecycleStatus,
     TnuaBasisContext,
 };
+use crate::builtins::{
+    apply_gravity_flips_system, send_ground_pound_impact_events, send_walk_landed_events,
+    TnuaBuiltinChargedJump, TnuaBuiltinChargedJumpState, TnuaBuiltinClimb, TnuaBuiltinClimbState,
+    TnuaBuiltinCrouch, TnuaBuiltinCrouchSlide, TnuaBuiltinCrouchSlideState, TnuaBuiltinCrouchState,
+    TnuaBuiltinDash, TnuaBuiltinDashState, TnuaBuiltinFly, TnuaBuiltinFlyState, TnuaBuiltinGlide,
+    TnuaBuiltinGlideState, TnuaBuiltinGravityFlip, TnuaBuiltinGravityFlipState,
+    TnuaBuiltinGroundPound, TnuaBuiltinGroundPoundImpacted, TnuaBuiltinGroundPoundState,
+    TnuaBuiltinJump, TnuaBuiltinJumpState, TnuaBuiltinJumpToTarget,
+    TnuaBuiltinJumpToTargetSolution, TnuaBuiltinJumpToTargetState, TnuaBuiltinJumpTrajectory,
+    TnuaBuiltinKnockback, TnuaBuiltinKnockbackState, TnuaBuiltinLedgeHang,
+    TnuaBuiltinLedgeHangState, TnuaBuiltinRopeSwing, TnuaBuiltinRopeSwingState, TnuaBuiltinSwim,
+    TnuaBuiltinSwimState, TnuaBuiltinVault, TnuaBuiltinVaultState, TnuaBuiltinWalk,
+    TnuaBuiltinWalkLanded, TnuaBuiltinWalkState, TnuaBuiltinWallJump, TnuaBuiltinWallJumpState,
+    TnuaBuiltinWallRun, TnuaBuiltinWallRunState, TnuaBuiltinWallSlide, TnuaBuiltinWallSlideState,
+};
+use crate::subservient_sensors::TnuaSubservientSensor;
 use crate::{
-    TnuaBasis, TnuaMotor, TnuaPipelineStages, TnuaProximitySensor, TnuaRigidBodyTracker,
-    TnuaSystemSet, TnuaToggle, TnuaUserControlsSystemSet,
+    TnuaBasis, TnuaGhostPlatform, TnuaGhostSensor, TnuaGravity, TnuaMotor, TnuaNotPlatform,
+    TnuaObstacleRadar, TnuaPipelineStages, TnuaProximitySensor, TnuaRigidBodyTracker,
+    TnuaSystemSet, TnuaToggle, TnuaUserControlsSystemSet, TnuaWaterSensor, TnuaWaterVolume,
 };
 
 /// The main for supporting Tnua character controller.
@@ -51,13 +68,79 @@ impl Plugin for TnuaControllerPlugin {
                 .chain()
                 .in_set(TnuaSystemSet),
         );
+        app.register_type::<TnuaToggle>();
+        app.register_type::<TnuaRigidBodyTracker>();
+        app.register_type::<TnuaProximitySensor>();
+        app.register_type::<TnuaMotor>();
+        app.register_type::<TnuaGhostSensor>();
+        app.register_type::<TnuaGhostPlatform>();
+        app.register_type::<TnuaGravity>();
+        app.register_type::<TnuaNotPlatform>();
+        app.register_type::<TnuaWaterSensor>();
+        app.register_type::<TnuaWaterVolume>();
+        app.register_type::<TnuaObstacleRadar>();
+        app.register_type::<TnuaSubservientSensor>();
+        app.register_type::<TnuaBuiltinWalk>();
+        app.register_type::<TnuaBuiltinWalkState>();
+        app.register_type::<TnuaBuiltinSwim>();
+        app.register_type::<TnuaBuiltinSwimState>();
+        app.register_type::<TnuaBuiltinFly>();
+        app.register_type::<TnuaBuiltinFlyState>();
+        app.register_type::<TnuaBuiltinJump>();
+        app.register_type::<TnuaBuiltinJumpState>();
+        app.register_type::<TnuaBuiltinJumpTrajectory>();
+        app.register_type::<TnuaBuiltinDash>();
+        app.register_type::<TnuaBuiltinDashState>();
+        app.register_type::<TnuaBuiltinCrouch>();
+        app.register_type::<TnuaBuiltinCrouchState>();
+        app.register_type::<TnuaBuiltinClimb>();
+        app.register_type::<TnuaBuiltinClimbState>();
+        app.register_type::<TnuaBuiltinWallSlide>();
+        app.register_type::<TnuaBuiltinWallSlideState>();
+        app.register_type::<TnuaBuiltinWallJump>();
+        app.register_type::<TnuaBuiltinWallJumpState>();
+        app.register_type::<TnuaBuiltinWallRun>();
+        app.register_type::<TnuaBuiltinWallRunState>();
+        app.register_type::<TnuaBuiltinKnockback>();
+        app.register_type::<TnuaBuiltinKnockbackState>();
+        app.register_type::<TnuaBuiltinGravityFlip>();
+        app.register_type::<TnuaBuiltinGravityFlipState>();
+        app.register_type::<TnuaBuiltinLedgeHang>();
+        app.register_type::<TnuaBuiltinLedgeHangState>();
+        app.register_type::<TnuaBuiltinVault>();
+        app.register_type::<TnuaBuiltinVaultState>();
+        app.register_type::<TnuaBuiltinRopeSwing>();
+        app.register_type::<TnuaBuiltinRopeSwingState>();
+        app.register_type::<TnuaBuiltinGlide>();
+        app.register_type::<TnuaBuiltinGlideState>();
+        app.register_type::<TnuaBuiltinCrouchSlide>();
+        app.register_type::<TnuaBuiltinCrouchSlideState>();
+        app.register_type::<TnuaBuiltinGroundPound>();
+        app.register_type::<TnuaBuiltinGroundPoundState>();
+        app.register_type::<TnuaBuiltinChargedJump>();
+        app.register_type::<TnuaBuiltinChargedJumpState>();
+        app.register_type::<TnuaBuiltinJumpToTarget>();
+        app.register_type::<TnuaBuiltinJumpToTargetSolution>();
+        app.register_type::<TnuaBuiltinJumpToTargetState>();
+        app.add_event::<TnuaActionEvent>();
+        app.add_event::<TnuaBasisEvent>();
+        app.add_event::<TnuaBuiltinWalkLanded>();
+        app.add_event::<TnuaBuiltinGroundPoundImpacted>();
         app.add_systems(
             self.schedule,
-            apply_controller_system.in_set(TnuaPipelineStages::Logic),
+            (
+                apply_controller_system,
+                send_walk_landed_events,
+                send_ground_pound_impact_events,
+                apply_gravity_flips_system,
+            )
+                .chain()
+                .in_set(TnuaPipelineStages::Logic),
         );
     }
 }
 
+#[derive(Clone)]
 struct FedEntry {
     fed_this_frame: bool,
     rescheduled_in: Option<Timer>,
@@ -84,18 +167,49 @@ struct FedEntry {
 ///   Refer to the documentation of [the implementors of
 ///   `TnuaAction`](crate::TnuaAction#implementors) for more information.
 ///
+///   The "only one action at a time" rule is per [action layer](TnuaActionLayer). The methods of
+///   `TnuaController` itself operate on the [main layer](Self::MAIN_ACTION_LAYER), but more layers
+///   can be added with [`action_layer`](Self::action_layer) for actions that need to run
+///   concurrently with the main one (e.g. an upper-body action that runs alongside a jump)
+///
+///   By default, any contender action that its [`initiation_decision`](TnuaAction::initiation_decision)
+///   allows will cancel the running action. To restrict this from the game code, use
+///   [`cancellation_rules_mut`](Self::cancellation_rules_mut).
+///
 /// Without [`TnuaControllerPlugin`] this component will not do anything.
-#[derive(Component, Default)]
+#[derive(Component)]
 #[require(TnuaMotor, TnuaRigidBodyTracker, TnuaProximitySensor)]
 pub struct TnuaController {
     current_basis: Option<(&'static str, Box<dyn DynamicBasis>)>,
-    actions_being_fed: HashMap<&'static str, FedEntry>,
-    current_action: Option<(&'static str, Box<dyn DynamicAction>)>,
-    contender_action: Option<(&'static str, Box<dyn DynamicAction>, Stopwatch)>,
-    action_flow_status: TnuaActionFlowStatus,
+    action_layers: Vec<(&'static str, TnuaActionLayer)>,
+    cancellation_rules: TnuaActionCancellationRules,
+    basis_footing: Option<BasisFooting>,
+    applied_this_frame: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct BasisFooting {
+    grounded: bool,
+    airborne: bool,
+}
+
+impl Default for TnuaController {
+    fn default() -> Self {
+        Self {
+            current_basis: None,
+            action_layers: vec![(Self::MAIN_ACTION_LAYER, Default::default())],
+            cancellation_rules: Default::default(),
+            basis_footing: None,
+            applied_this_frame: false,
+        }
+    }
 }
 
 impl TnuaController {
+    /// The name of the action layer that [`TnuaController`]'s own action methods (like
+    /// [`action`](Self::action) or [`action_name`](Self::action_name)) operate on.
+    pub const MAIN_ACTION_LAYER: &'static str = "main";
+
     /// Feed a basis - the main movement command - with [its default name](TnuaBasis::NAME).
     pub fn basis<B: TnuaBasis>(&mut self, basis: B) {
         self.named_basis(B::NAME, basis);
@@ -117,6 +231,7 @@ impl TnuaController {
             existing_basis.input = basis;
         } else {
             self.current_basis = Some((name, Box::new(BoxableBasis::new(basis))));
+            self.basis_footing = None;
         }
     }
 
@@ -146,6 +261,14 @@ impl TnuaController {
         Some(self.current_basis.as_ref()?.1.as_ref())
     }
 
+    /// A dynamic accessor to the currently running basis, as mutable.
+    ///
+    /// Useful for inspecting and tuning the basis' input and state via reflection (see
+    /// [`DynamicBasis::input_reflect_mut`] and [`DynamicBasis::state_reflect_mut`])
+    pub fn dynamic_basis_mut(&mut self) -> Option<&mut dyn DynamicBasis> {
+        Some(self.current_basis.as_mut()?.1.as_mut())
+    }
+
     /// The currently running basis, together with its state.
     ///
     /// This is mainly useful for animation. When multiple basis types are used in the game,
@@ -165,6 +288,438 @@ impl TnuaController {
         Some((&boxable_basis.input, &mut boxable_basis.state))
     }
 
+    fn main_action_layer(&self) -> &TnuaActionLayer {
+        &self.action_layers[0].1
+    }
+
+    fn main_action_layer_mut(&mut self) -> &mut TnuaActionLayer {
+        &mut self.action_layers[0].1
+    }
+
+    /// Get an action layer for feeding or querying actions, creating it if it does not exist yet.
+    ///
+    /// Each layer has its own action lifecycle - an action fed into one layer will never cancel
+    /// or delay an action running in another layer. After the basis is applied, the layers are
+    /// applied one after the other - the [main layer](Self::MAIN_ACTION_LAYER) first, and then
+    /// the other layers in the order they were created - so each layer's actions can see and
+    /// alter the [`TnuaMotor`] changes made by the basis and by the layers before it.
+    ///
+    /// Since the order of the layers is determined by the order in which they were created, games
+    /// that care about it should create all their layers when spawning the character:
+    ///
+    /// ```no_run
+    /// # use bevy_tnua::prelude::*;
+    /// let mut controller = TnuaController::default();
+    /// controller.action_layer("upper-body");
+    /// ```
+    pub fn action_layer(&mut self, layer_name: &'static str) -> &mut TnuaActionLayer {
+        let index = if let Some(index) = self
+            .action_layers
+            .iter()
+            .position(|(name, _)| *name == layer_name)
+        {
+            index
+        } else {
+            self.action_layers.push((layer_name, Default::default()));
+            self.action_layers.len() - 1
+        };
+        &mut self.action_layers[index].1
+    }
+
+    /// Get an action layer for querying, if it exists.
+    ///
+    /// Unlike [`action_layer`](Self::action_layer), this method does not create the layer.
+    pub fn get_action_layer(&self, layer_name: &str) -> Option<&TnuaActionLayer> {
+        self.action_layers
+            .iter()
+            .find_map(|(name, layer)| (*name == layer_name).then_some(layer))
+    }
+
+    /// Iterate over all the action layers (including the [main
+    /// one](Self::MAIN_ACTION_LAYER)) in the order they are applied.
+    pub fn iter_action_layers(
+        &self,
+    ) -> impl '_ + Iterator<Item = (&'static str, &TnuaActionLayer)> {
+        self.action_layers
+            .iter()
+            .map(|(name, layer)| (*name, layer))
+    }
+
+    /// The rules that decide which actions can cancel which.
+    pub fn cancellation_rules(&self) -> &TnuaActionCancellationRules {
+        &self.cancellation_rules
+    }
+
+    /// The rules that decide which actions can cancel which, for modifying them.
+    ///
+    /// ```no_run
+    /// # use bevy_tnua::prelude::*;
+    /// # use bevy_tnua::builtins::{TnuaBuiltinDash, TnuaBuiltinJumpState, TnuaBuiltinKnockback};
+    /// let mut controller = TnuaController::default();
+    /// controller
+    ///     .cancellation_rules_mut()
+    ///     // Jumping while being knocked back is ignored:
+    ///     .forbid(TnuaBuiltinKnockback::NAME, TnuaBuiltinJump::NAME)
+    ///     // Dashing during a jump waits for the jump to pass its peak:
+    ///     .allow_when(
+    ///         TnuaBuiltinJump::NAME,
+    ///         TnuaBuiltinDash::NAME,
+    ///         |_: &TnuaBuiltinJump, state| matches!(state, TnuaBuiltinJumpState::FallSection),
+    ///     );
+    /// ```
+    pub fn cancellation_rules_mut(&mut self) -> &mut TnuaActionCancellationRules {
+        &mut self.cancellation_rules
+    }
+
+    /// Feed an action with [its default name](TnuaBasis::NAME).
+    pub fn action<A: TnuaAction>(&mut self, action: A) {
+        self.main_action_layer_mut().action(action);
+    }
+
+    /// Feed an action with a custom name.
+    ///
+    /// This should only be used if the same action type needs to be used with different names to
+    /// allow, for example, different animations. Otherwise prefer to use the default name with
+    /// [`action`](Self::action).
+    pub fn named_action<A: TnuaAction>(&mut self, name: &'static str, action: A) {
+        self.main_action_layer_mut().named_action(name, action);
+    }
+
+    /// Re-feed the same action that is currently active.
+    ///
+    /// This is useful when matching on [`action_name`](Self::action_name) and wanting to continue
+    /// feeding the **exact same** action with the **exact same** input without having to use
+    /// [`concrete_action`](Self::concrete_action).
+    pub fn prolong_action(&mut self) {
+        self.main_action_layer_mut().prolong_action();
+    }
+
+    /// The name of the currently running action.
+    ///
+    /// When using an action with it's default name, prefer to match this against
+    /// [`TnuaAction::NAME`] and not against a string literal.
+    pub fn action_name(&self) -> Option<&'static str> {
+        self.main_action_layer().action_name()
+    }
+
+    /// A dynamic accessor to the currently running action.
+    pub fn dynamic_action(&self) -> Option<&dyn DynamicAction> {
+        self.main_action_layer().dynamic_action()
+    }
+
+    /// A dynamic accessor to the currently running action, as mutable.
+    ///
+    /// Useful for inspecting and tuning the action's input and state via reflection (see
+    /// [`DynamicAction::input_reflect_mut`] and [`DynamicAction::state_reflect_mut`])
+    pub fn dynamic_action_mut(&mut self) -> Option<&mut dyn DynamicAction> {
+        self.main_action_layer_mut().dynamic_action_mut()
+    }
+
+    /// The currently running action, together with its state.
+    ///
+    /// This is mainly useful for animation. When multiple action types are used in the game,
+    /// [`action_name`](Self::action_name) be used to determine the type of the current action
+    /// first, to avoid having to try multiple downcasts.
+    pub fn concrete_action<A: TnuaAction>(&self) -> Option<(&A, &A::State)> {
+        self.main_action_layer().concrete_action()
+    }
+
+    /// The currently running action, together with its state, as mutable.
+    /// Useful if you need to touch the state of a running action to respond to game events.
+    ///
+    /// If the action is replaced, the state will be lost. If you need to keep the state, you should
+    /// store it separately.
+    pub fn concrete_action_mut<A: TnuaAction>(&mut self) -> Option<(&A, &mut A::State)> {
+        self.main_action_layer_mut().concrete_action_mut()
+    }
+
+    /// Indicator for the state and flow of movement actions.
+    ///
+    /// Query this every frame to keep track of the actions. For air actions,
+    /// [`TnuaAirActionsTracker`](crate::control_helpers::TnuaAirActionsTracker) is easier to use
+    /// (and uses this behind the scenes)
+    ///
+    /// The benefits of this over querying [`action_name`](Self::action_name) every frame are:
+    ///
+    /// * `action_flow_status` can indicate when the same action has been fed again immediately
+    ///   after stopping or cancelled into itself.
+    /// * `action_flow_status` shows an [`ActionEnded`](TnuaActionFlowStatus::ActionEnded) when the
+    ///   action is no longer fed, even if the action is still active (termination sequence)
+    ///
+    /// Since only the last transition of the frame is kept, systems that must not miss any
+    /// transition should use [`TnuaActionEvent`] instead.
+    pub fn action_flow_status(&self) -> &TnuaActionFlowStatus {
+        self.main_action_layer().action_flow_status()
+    }
+
+    /// Capture the full state of the controller - the basis and the actions in all the layers,
+    /// together with their states and the controller's bookkeeping of which actions are being fed.
+    ///
+    /// The snapshot can later be [restored](Self::restore) into this controller or into the
+    /// controller of another entity - e.g. for rollback networking or for rewind mechanics.
+    ///
+    /// The [cancellation rules](Self::cancellation_rules) are considered configuration and are not
+    /// part of the snapshot.
+    pub fn snapshot(&self) -> TnuaControllerSnapshot {
+        TnuaControllerSnapshot {
+            current_basis: self
+                .current_basis
+                .as_ref()
+                .map(|(name, basis)| (*name, basis.clone_boxed())),
+            action_layers: self.action_layers.clone(),
+            basis_footing: self.basis_footing,
+        }
+    }
+
+    /// Restore the state of the controller from a [snapshot](Self::snapshot).
+    ///
+    /// Everything that was captured in the snapshot is replaced - including action layers that
+    /// were created after the snapshot was taken, which will be removed. The
+    /// [cancellation rules](Self::cancellation_rules) are kept as they are.
+    pub fn restore(&mut self, snapshot: &TnuaControllerSnapshot) {
+        self.current_basis = snapshot
+            .current_basis
+            .as_ref()
+            .map(|(name, basis)| (*name, basis.clone_boxed()));
+        self.action_layers = snapshot.action_layers.clone();
+        self.basis_footing = snapshot.basis_footing;
+        // Whatever the restored states report did not happen on this frame.
+        self.applied_this_frame = false;
+    }
+
+    /// Checks if the character is currently airborne.
+    ///
+    /// The check is done based on the basis, and is equivalent to getting the controller's
+    /// [`dynamic_basis`](Self::dynamic_basis) and checking its
+    /// [`is_airborne`](TnuaBasis::is_airborne) method.
+    pub fn is_airborne(&self) -> Result<bool, TnuaControllerHasNoBasis> {
+        match self.dynamic_basis() {
+            Some(basis) => Ok(basis.is_airborne()),
+            None => Err(TnuaControllerHasNoBasis),
+        }
+    }
+
+    /// Checks if the controller was applied on the current frame.
+    ///
+    /// The controller is not applied on frames where no time has passed, or when the character's
+    /// [`TnuaToggle`] is [`Disabled`](TnuaToggle::Disabled). The states of the basis and the
+    /// actions are left as they were on these frames - including reports that are only meant to
+    /// be there on a single frame, like [`TnuaBuiltinWalkState::landing_report`] - so code that
+    /// acts on such reports should check this first.
+    pub fn applied_this_frame(&self) -> bool {
+        self.applied_this_frame
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("The Tnua controller does not have any basis set")]
+pub struct TnuaControllerHasNoBasis;
+
+/// The full state of a [`TnuaController`], created with [`TnuaController::snapshot`] and applied
+/// with [`TnuaController::restore`].
+///
+/// The [cancellation rules](TnuaController::cancellation_rules) are deliberately left out. They
+/// are configuration set by the game code rather than simulation state, they don't change during
+/// a rollback, and they hold closures that cannot be cloned.
+pub struct TnuaControllerSnapshot {
+    current_basis: Option<(&'static str, Box<dyn DynamicBasis>)>,
+    action_layers: Vec<(&'static str, TnuaActionLayer)>,
+    basis_footing: Option<BasisFooting>,
+}
+
+impl Clone for TnuaControllerSnapshot {
+    fn clone(&self) -> Self {
+        Self {
+            current_basis: self
+                .current_basis
+                .as_ref()
+                .map(|(name, basis)| (*name, basis.clone_boxed())),
+            action_layers: self.action_layers.clone(),
+            basis_footing: self.basis_footing,
+        }
+    }
+}
+
+impl TnuaControllerSnapshot {
+    /// The name of the basis that was running when the snapshot was taken.
+    pub fn basis_name(&self) -> Option<&'static str> {
+        self.current_basis.as_ref().map(|(name, _)| *name)
+    }
+}
+
+type TnuaCancellationRuleFn =
+    Box<dyn Send + Sync + Fn(&dyn DynamicAction) -> TnuaActionInitiationDirective>;
+
+/// Rules, set by the game code, that restrict which actions can cancel which.
+///
+/// These rules are checked only after the contender action's own
+/// [`initiation_decision`](TnuaAction::initiation_decision) allows it to start, and only when
+/// there is another action running in the same [layer](TnuaActionLayer). They can only make the
+/// controller more restrictive - if they allow the cancellation the contender takes over, but if
+/// they reject or delay it the running action will continue as if the contender returned that
+/// [`TnuaActionInitiationDirective`] itself.
+///
+/// Rules are specified by action names, and explicit rules for a pair of actions (set with
+/// [`forbid`](Self::forbid), [`delay`](Self::delay), [`allow_when`](Self::allow_when) or
+/// [`rule`](Self::rule)) take precedence over [priorities](Self::set_priority).
+///
+/// Get it with [`TnuaController::cancellation_rules_mut`].
+#[derive(Default)]
+pub struct TnuaActionCancellationRules {
+    rules: HashMap<(&'static str, &'static str), TnuaCancellationRuleFn>,
+    priorities: HashMap<&'static str, i32>,
+}
+
+impl TnuaActionCancellationRules {
+    /// Set a rule for what happens when `contender` tries to cancel `running`.
+    ///
+    /// The rule function gets the running action and its state, and its decision is treated as if
+    /// the contender returned it from its [`initiation_decision`](TnuaAction::initiation_decision).
+    ///
+    /// If the running action is not of type `A` (e.g. - if it was fed with a name that belongs to
+    /// a different action type) the rule is ignored and a warning is logged.
+    pub fn rule<A: TnuaAction>(
+        &mut self,
+        running: &'static str,
+        contender: &'static str,
+        rule: impl 'static + Send + Sync + Fn(&A, &A::State) -> TnuaActionInitiationDirective,
+    ) -> &mut Self {
+        self.rules.insert(
+            (running, contender),
+            Box::new(move |action| {
+                if let Some(action) = action.as_any().downcast_ref::<BoxableAction<A>>() {
+                    rule(&action.input, &action.state)
+                } else {
+                    warn!(
+                        "Cancellation rule for {contender} cancelling {running} is ignored - \
+                         {running} is not a {}",
+                        A::NAME
+                    );
+                    TnuaActionInitiationDirective::Allow
+                }
+            }),
+        );
+        self
+    }
+
+    /// Never let `contender` cancel `running`.
+    ///
+    /// If `contender` is fed while `running` is active it'll be
+    /// [rejected](TnuaActionInitiationDirective::Reject), and will have to be released and fed
+    /// again to start.
+    pub fn forbid(&mut self, running: &'static str, contender: &'static str) -> &mut Self {
+        self.rules.insert(
+            (running, contender),
+            Box::new(|_| TnuaActionInitiationDirective::Reject),
+        );
+        self
+    }
+
+    /// Make `contender` wait until `running` is finished.
+    ///
+    /// If `contender` is still fed when `running` finishes, it'll start then.
+    pub fn delay(&mut self, running: &'static str, contender: &'static str) -> &mut Self {
+        self.rules.insert(
+            (running, contender),
+            Box::new(|_| TnuaActionInitiationDirective::Delay),
+        );
+        self
+    }
+
+    /// Let `contender` cancel `running` only when a condition on the running action is met.
+    ///
+    /// While the condition is not met the contender is
+    /// [delayed](TnuaActionInitiationDirective::Delay), so it'll start once the condition becomes
+    /// true (or once `running` finishes) if it is still being fed.
+    pub fn allow_when<A: TnuaAction>(
+        &mut self,
+        running: &'static str,
+        contender: &'static str,
+        condition: impl 'static + Send + Sync + Fn(&A, &A::State) -> bool,
+    ) -> &mut Self {
+        self.rule::<A>(running, contender, move |action, state| {
+            if condition(action, state) {
+                TnuaActionInitiationDirective::Allow
+            } else {
+                TnuaActionInitiationDirective::Delay
+            }
+        })
+    }
+
+    /// Remove the explicit rule for `contender` cancelling `running`, if there is one.
+    pub fn clear_rule(&mut self, running: &'static str, contender: &'static str) -> &mut Self {
+        self.rules.remove(&(running, contender));
+        self
+    }
+
+    /// Set the priority of an action.
+    ///
+    /// When both the running action and the contender have priorities, and there is no explicit
+    /// rule for that pair, a contender with a lower priority than the running action will be
+    /// [delayed](TnuaActionInitiationDirective::Delay) until the running action finishes.
+    /// Contenders with the same or higher priority can cancel the running action.
+    pub fn set_priority(&mut self, action_name: &'static str, priority: i32) -> &mut Self {
+        self.priorities.insert(action_name, priority);
+        self
+    }
+
+    /// The priority of an action, if it was set with [`set_priority`](Self::set_priority).
+    pub fn priority(&self, action_name: &str) -> Option<i32> {
+        self.priorities.get(action_name).copied()
+    }
+
+    fn decide(
+        &self,
+        running_name: &'static str,
+        running_action: &dyn DynamicAction,
+        contender_name: &'static str,
+    ) -> TnuaActionInitiationDirective {
+        if let Some(rule) = self.rules.get(&(running_name, contender_name)) {
+            return rule(running_action);
+        }
+        match (self.priority(running_name), self.priority(contender_name)) {
+            (Some(running_priority), Some(contender_priority))
+                if contender_priority < running_priority =>
+            {
+                TnuaActionInitiationDirective::Delay
+            }
+            _ => TnuaActionInitiationDirective::Allow,
+        }
+    }
+}
+
+/// A single lane of actions inside a [`TnuaController`].
+///
+/// Only one action can be active in a layer at any given moment, but actions in different layers
+/// run independently of each other - each with its own lifecycle and
+/// [`TnuaActionFlowStatus`]. Use [`TnuaController::action_layer`] to get a layer.
+#[derive(Default)]
+pub struct TnuaActionLayer {
+    actions_being_fed: HashMap<&'static str, FedEntry>,
+    current_action: Option<(&'static str, Box<dyn DynamicAction>)>,
+    contender_action: Option<(&'static str, Box<dyn DynamicAction>, Stopwatch)>,
+    action_flow_status: TnuaActionFlowStatus,
+}
+
+impl Clone for TnuaActionLayer {
+    fn clone(&self) -> Self {
+        Self {
+            actions_being_fed: self.actions_being_fed.clone(),
+            current_action: self
+                .current_action
+                .as_ref()
+                .map(|(name, action)| (*name, action.clone_boxed())),
+            contender_action: self.contender_action.as_ref().map(
+                |(name, action, being_fed_for)| {
+                    (*name, action.clone_boxed(), being_fed_for.clone())
+                },
+            ),
+            action_flow_status: self.action_flow_status.clone(),
+        }
+    }
+}
+
+impl TnuaActionLayer {
     /// Feed an action with [its default name](TnuaBasis::NAME).
     pub fn action<A: TnuaAction>(&mut self, action: A) {
         self.named_action(A::NAME, action);
@@ -264,6 +819,11 @@ impl TnuaController {
         Some(self.current_action.as_ref()?.1.as_ref())
     }
 
+    /// A dynamic accessor to the currently running action, as mutable.
+    pub fn dynamic_action_mut(&mut self) -> Option<&mut dyn DynamicAction> {
+        Some(self.current_action.as_mut()?.1.as_mut())
+    }
+
     /// The currently running action, together with its state.
     ///
     /// This is mainly useful for animation. When multiple action types are used in the game,
@@ -286,39 +846,14 @@ impl TnuaController {
         Some((&boxable_action.input, &mut boxable_action.state))
     }
 
-    /// Indicator for the state and flow of movement actions.
+    /// Indicator for the state and flow of the actions in this layer.
     ///
-    /// Query this every frame to keep track of the actions. For air actions,
-    /// [`TnuaAirActionsTracker`](crate::control_helpers::TnuaAirActionsTracker) is easier to use
-    /// (and uses this behind the scenes)
-    ///
-    /// The benefits of this over querying [`action_name`](Self::action_name) every frame are:
-    ///
-    /// * `action_flow_status` can indicate when the same action has been fed again immediately
-    ///   after stopping or cancelled into itself.
-    /// * `action_flow_status` shows an [`ActionEnded`](TnuaActionFlowStatus::ActionEnded) when the
-    ///   action is no longer fed, even if the action is still active (termination sequence)
+    /// See [`TnuaController::action_flow_status`].
     pub fn action_flow_status(&self) -> &TnuaActionFlowStatus {
         &self.action_flow_status
     }
-
-    /// Checks if the character is currently airborne.
-    ///
-    /// The check is done based on the basis, and is equivalent to getting the controller's
-    /// [`dynamic_basis`](Self::dynamic_basis) and checking its
-    /// [`is_airborne`](TnuaBasis::is_airborne) method.
-    pub fn is_airborne(&self) -> Result<bool, TnuaControllerHasNoBasis> {
-        match self.dynamic_basis() {
-            Some(basis) => Ok(basis.is_airborne()),
-            None => Err(TnuaControllerHasNoBasis),
-        }
-    }
 }
 
-#[derive(thiserror::Error, Debug)]
-#[error("The Tnua controller does not have any basis set")]
-pub struct TnuaControllerHasNoBasis;
-
 /// The result of [`TnuaController::action_flow_status()`].
 #[derive(Debug, Default, Clone)]
 pub enum TnuaActionFlowStatus {
@@ -378,22 +913,118 @@ impl TnuaActionFlowStatus {
     }
 }
 
+/// An event sent (and triggered on the character entity, for observers) whenever the action in one
+/// of the controller's [layers](TnuaActionLayer) changes.
+///
+/// Unlike [`TnuaActionFlowStatus`], which only holds the last transition of the frame, every
+/// transition gets its own event - so when an action ends and another starts in the same frame
+/// both will be reported.
+#[derive(Event, Debug, Clone)]
+pub struct TnuaActionEvent {
+    /// The character entity.
+    pub entity: Entity,
+    /// The name of the [action layer](TnuaActionLayer) where the transition happened.
+    pub layer: &'static str,
+    /// What happened.
+    pub kind: TnuaActionEventKind,
+    /// The velocity of the character when the transition happened.
+    pub velocity: Vector3,
+}
+
+/// The transition reported by a [`TnuaActionEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TnuaActionEventKind {
+    /// The action became the active action of the layer.
+    ///
+    /// If it cancelled another action, [`Cancelled`](Self::Cancelled) is sent before this event.
+    Started { action: &'static str },
+
+    /// The action stopped being the active action of the layer, either because it finished or
+    /// because it was [rescheduled](Self::Rescheduled).
+    ///
+    /// Not sent for actions that were [cancelled](Self::Cancelled).
+    Ended { action: &'static str },
+
+    /// The `old` action was cancelled into the `new` action.
+    Cancelled {
+        old: &'static str,
+        new: &'static str,
+    },
+
+    /// The action asked to be restarted, if it is still fed, after the specified duration.
+    Rescheduled {
+        action: &'static str,
+        after_seconds: Float,
+    },
+
+    /// The action was fed but was not allowed to start - either by its own
+    /// [`initiation_decision`](TnuaAction::initiation_decision) or by the controller's
+    /// [cancellation rules](TnuaActionCancellationRules) - and will not start until it is released
+    /// and fed again.
+    Rejected { action: &'static str },
+}
+
+/// An event sent (and triggered on the character entity, for observers) when the basis changes its
+/// relation with the ground.
+///
+/// These are detected from the [`displacement`](TnuaBasis::displacement) and
+/// [`is_airborne`](TnuaBasis::is_airborne) of the basis, so they work with any basis that
+/// implements these methods. No events are sent on the first frame of a new basis.
+#[derive(Event, Debug, Clone)]
+pub struct TnuaBasisEvent {
+    /// The character entity.
+    pub entity: Entity,
+    /// The name of the basis.
+    pub basis: &'static str,
+    /// What happened.
+    pub kind: TnuaBasisEventKind,
+    /// The velocity of the character when the event happened. For
+    /// [`Landed`](TnuaBasisEventKind::Landed), this is the velocity it hit the ground with.
+    pub velocity: Vector3,
+}
+
+/// The change reported by a [`TnuaBasisEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TnuaBasisEventKind {
+    /// The basis no longer holds the character at its desired height.
+    ///
+    /// When the basis has coyote time, this is when it starts.
+    TookOff,
+
+    /// The basis holds the character at its desired height again after it was not.
+    Landed,
+
+    /// The basis started considering the character as airborne. When the basis has coyote time,
+    /// this happens when that coyote time ends (or gets violated by an action).
+    CoyoteTimeExpired,
+}
+
 #[allow(clippy::type_complexity)]
 fn apply_controller_system(
     time: Res<Time>,
     mut query: Query<(
+        Entity,
         &mut TnuaController,
         &TnuaRigidBodyTracker,
         &mut TnuaProximitySensor,
         &mut TnuaMotor,
+        Option<&mut TnuaWaterSensor>,
         Option<&TnuaToggle>,
     )>,
+    mut action_events: EventWriter<TnuaActionEvent>,
+    mut basis_events: EventWriter<TnuaBasisEvent>,
+    mut commands: Commands,
+    mut action_event_kinds: Local<Vec<TnuaActionEventKind>>,
 ) {
     let frame_duration = time.delta().as_secs_f64() as Float;
-    if frame_duration == 0.0 {
-        return;
-    }
-    for (mut controller, tracker, mut sensor, mut motor, tnua_toggle) in query.iter_mut() {
+    for (entity, mut controller, tracker, mut sensor, mut motor, mut water_sensor, tnua_toggle) in
+        query.iter_mut()
+    {
+        // Not a change the game needs to detect.
+        controller.bypass_change_detection().applied_this_frame = false;
+        if frame_duration == 0.0 {
+            continue;
+        }
         match tnua_toggle.copied().unwrap_or_default() {
             TnuaToggle::Disabled => continue,
             TnuaToggle::SenseOnly => {}
@@ -401,223 +1032,354 @@ fn apply_controller_system(
         }
 
         let controller = controller.as_mut();
+        controller.applied_this_frame = true;
+
+        for (_, layer) in controller.action_layers.iter_mut() {
+            layer.advance_flow_status();
+        }
+
+        if let Some((basis_name, basis)) = controller.current_basis.as_mut() {
+            let up_direction = Dir3::new(-tracker.gravity.f32()).unwrap_or(Dir3::Y);
+            let basis = basis.as_mut();
+            basis.apply(
+                TnuaBasisContext {
+                    frame_duration,
+                    tracker,
+                    proximity_sensor: sensor.as_ref(),
+                    water_sensor: water_sensor.as_deref(),
+                    up_direction,
+                },
+                motor.as_mut(),
+            );
+            let mut sensor_cast_range = basis.proximity_sensor_cast_range();
+
+            for (layer_name, layer) in controller.action_layers.iter_mut() {
+                layer.apply(
+                    &mut action_event_kinds,
+                    &controller.cancellation_rules,
+                    &time,
+                    frame_duration,
+                    tracker,
+                    sensor.as_ref(),
+                    water_sensor.as_deref(),
+                    basis,
+                    up_direction,
+                    motor.as_mut(),
+                );
+                sensor_cast_range = sensor_cast_range.max(layer.proximity_sensor_cast_range());
+                for kind in action_event_kinds.drain(..) {
+                    let event = TnuaActionEvent {
+                        entity,
+                        layer: layer_name,
+                        kind,
+                        velocity: tracker.velocity,
+                    };
+                    commands.trigger_targets(event.clone(), entity);
+                    action_events.write(event);
+                }
+            }
 
-        match controller.action_flow_status {
+            let footing = BasisFooting {
+                grounded: basis.displacement().is_some(),
+                airborne: basis.is_airborne(),
+            };
+            if let Some(prev_footing) = controller.basis_footing.replace(footing) {
+                for (happened, kind) in [
+                    (
+                        prev_footing.grounded && !footing.grounded,
+                        TnuaBasisEventKind::TookOff,
+                    ),
+                    (
+                        !prev_footing.airborne && footing.airborne,
+                        TnuaBasisEventKind::CoyoteTimeExpired,
+                    ),
+                    (
+                        !prev_footing.grounded && footing.grounded,
+                        TnuaBasisEventKind::Landed,
+                    ),
+                ] {
+                    if happened {
+                        let event = TnuaBasisEvent {
+                            entity,
+                            basis: basis_name,
+                            kind,
+                            velocity: tracker.velocity,
+                        };
+                        commands.trigger_targets(event.clone(), entity);
+                        basis_events.write(event);
+                    }
+                }
+            }
+
+            sensor.cast_range = sensor_cast_range;
+            sensor.cast_direction = -up_direction;
+            if let Some(water_sensor) = water_sensor.as_mut() {
+                water_sensor.cast_direction = -up_direction;
+            }
+        }
+
+        for (_, layer) in controller.action_layers.iter_mut() {
+            layer.cycle_actions_being_fed(&time);
+        }
+    }
+}
+
+impl TnuaActionLayer {
+    fn advance_flow_status(&mut self) {
+        match self.action_flow_status {
             TnuaActionFlowStatus::NoAction | TnuaActionFlowStatus::ActionOngoing(_) => {}
             TnuaActionFlowStatus::ActionEnded(_) => {
-                controller.action_flow_status = TnuaActionFlowStatus::NoAction;
+                self.action_flow_status = TnuaActionFlowStatus::NoAction;
             }
             TnuaActionFlowStatus::ActionStarted(action_name)
             | TnuaActionFlowStatus::Cancelled {
                 old: _,
                 new: action_name,
             } => {
-                controller.action_flow_status = TnuaActionFlowStatus::ActionOngoing(action_name);
+                self.action_flow_status = TnuaActionFlowStatus::ActionOngoing(action_name);
             }
         }
+    }
 
-        if let Some((_, basis)) = controller.current_basis.as_mut() {
-            let up_direction = Dir3::new(-tracker.gravity.f32()).unwrap_or(Dir3::Y);
-            let basis = basis.as_mut();
-            basis.apply(
-                TnuaBasisContext {
+    #[allow(clippy::too_many_arguments)]
+    fn apply(
+        &mut self,
+        events: &mut Vec<TnuaActionEventKind>,
+        cancellation_rules: &TnuaActionCancellationRules,
+        time: &Time,
+        frame_duration: Float,
+        tracker: &TnuaRigidBodyTracker,
+        proximity_sensor: &TnuaProximitySensor,
+        water_sensor: Option<&TnuaWaterSensor>,
+        basis: &mut dyn DynamicBasis,
+        up_direction: Dir3,
+        motor: &mut TnuaMotor,
+    ) {
+        let has_valid_contender = if let Some((contender_name, contender_action, being_fed_for)) =
+            &mut self.contender_action
+        {
+            let mut initiation_decision = contender_action.initiation_decision(
+                TnuaActionContext {
                     frame_duration,
                     tracker,
-                    proximity_sensor: sensor.as_ref(),
+                    proximity_sensor,
+                    water_sensor,
+                    basis,
                     up_direction,
                 },
-                motor.as_mut(),
+                being_fed_for,
             );
-            let sensor_cast_range_for_basis = basis.proximity_sensor_cast_range();
-
-            // To streamline TnuaActionContext creation
-            let proximity_sensor = sensor.as_ref();
-
-            let has_valid_contender = if let Some((_, contender_action, being_fed_for)) =
-                &mut controller.contender_action
+            being_fed_for.tick(time.delta());
+            if let (TnuaActionInitiationDirective::Allow, Some((running_name, running_action))) =
+                (initiation_decision, &self.current_action)
             {
-                let initiation_decision = contender_action.initiation_decision(
-                    TnuaActionContext {
-                        frame_duration,
-                        tracker,
-                        proximity_sensor,
-                        basis,
-                        up_direction,
-                    },
-                    being_fed_for,
+                initiation_decision = cancellation_rules.decide(
+                    running_name,
+                    running_action.as_ref(),
+                    contender_name,
                 );
-                being_fed_for.tick(time.delta());
-                match initiation_decision {
-                    TnuaActionInitiationDirective::Reject => {
-                        controller.contender_action = None;
-                        false
-                    }
-                    TnuaActionInitiationDirective::Delay => false,
-                    TnuaActionInitiationDirective::Allow => true,
+            }
+            match initiation_decision {
+                TnuaActionInitiationDirective::Reject => {
+                    events.push(TnuaActionEventKind::Rejected {
+                        action: contender_name,
+                    });
+                    self.contender_action = None;
+                    false
                 }
+                TnuaActionInitiationDirective::Delay => false,
+                TnuaActionInitiationDirective::Allow => true,
+            }
+        } else {
+            false
+        };
+
+        if let Some((name, current_action)) = self.current_action.as_mut() {
+            let lifecycle_status = if has_valid_contender {
+                TnuaActionLifecycleStatus::CancelledInto
+            } else if self
+                .actions_being_fed
+                .get(name)
+                .map(|fed_entry| fed_entry.fed_this_frame)
+                .unwrap_or(false)
+            {
+                TnuaActionLifecycleStatus::StillFed
             } else {
-                false
+                TnuaActionLifecycleStatus::NoLongerFed
             };
 
-            if let Some((name, current_action)) = controller.current_action.as_mut() {
-                let lifecycle_status = if has_valid_contender {
-                    TnuaActionLifecycleStatus::CancelledInto
-                } else if controller
-                    .actions_being_fed
-                    .get(name)
-                    .map(|fed_entry| fed_entry.fed_this_frame)
-                    .unwrap_or(false)
-                {
-                    TnuaActionLifecycleStatus::StillFed
-                } else {
-                    TnuaActionLifecycleStatus::NoLongerFed
-                };
-
-                let directive = current_action.apply(
-                    TnuaActionContext {
-                        frame_duration,
-                        tracker,
-                        proximity_sensor,
-                        basis,
-                        up_direction,
-                    },
-                    lifecycle_status,
-                    motor.as_mut(),
-                );
-                if current_action.violates_coyote_time() {
-                    basis.violate_coyote_time();
+            let directive = current_action.apply(
+                TnuaActionContext {
+                    frame_duration,
+                    tracker,
+                    proximity_sensor,
+                    water_sensor,
+                    basis,
+                    up_direction,
+                },
+                lifecycle_status,
+                motor,
+            );
+            if current_action.violates_coyote_time() {
+                basis.violate_coyote_time();
+            }
+            if current_action.suppresses_landing() {
+                basis.suppress_landing();
+            }
+            let reschedule_action = |actions_being_fed: &mut HashMap<&'static str, FedEntry>,
+                                     after_seconds: Float| {
+                if let Some(fed_entry) = actions_being_fed.get_mut(name) {
+                    fed_entry.rescheduled_in =
+                        Some(Timer::from_seconds(after_seconds.f32(), TimerMode::Once));
                 }
-                let reschedule_action =
-                    |actions_being_fed: &mut HashMap<&'static str, FedEntry>,
-                     after_seconds: Float| {
-                        if let Some(fed_entry) = actions_being_fed.get_mut(name) {
-                            fed_entry.rescheduled_in =
-                                Some(Timer::from_seconds(after_seconds.f32(), TimerMode::Once));
-                        }
-                    };
-                match directive {
-                    TnuaActionLifecycleDirective::StillActive => {
-                        if !lifecycle_status.is_active()
-                            && matches!(
-                                controller.action_flow_status,
-                                TnuaActionFlowStatus::ActionOngoing(_)
-                            )
-                        {
-                            controller.action_flow_status = TnuaActionFlowStatus::ActionEnded(name);
-                        }
+            };
+            match directive {
+                TnuaActionLifecycleDirective::StillActive => {
+                    if !lifecycle_status.is_active()
+                        && matches!(
+                            self.action_flow_status,
+                            TnuaActionFlowStatus::ActionOngoing(_)
+                        )
+                    {
+                        self.action_flow_status = TnuaActionFlowStatus::ActionEnded(name);
                     }
-                    TnuaActionLifecycleDirective::Finished
-                    | TnuaActionLifecycleDirective::Reschedule { .. } => {
-                        if let TnuaActionLifecycleDirective::Reschedule { after_seconds } =
-                            directive
+                }
+                TnuaActionLifecycleDirective::Finished
+                | TnuaActionLifecycleDirective::Reschedule { .. } => {
+                    if let TnuaActionLifecycleDirective::Reschedule { after_seconds } = directive {
+                        reschedule_action(&mut self.actions_being_fed, after_seconds);
+                        events.push(TnuaActionEventKind::Rescheduled {
+                            action: name,
+                            after_seconds,
+                        });
+                    }
+                    self.current_action = if has_valid_contender {
+                        let (contender_name, mut contender_action, _) =
+                            self.contender_action.take().expect(
+                                "has_valid_contender can only be true if contender_action is Some",
+                            );
+                        if let Some(contender_fed_entry) =
+                            self.actions_being_fed.get_mut(contender_name)
                         {
-                            reschedule_action(&mut controller.actions_being_fed, after_seconds);
+                            contender_fed_entry.rescheduled_in = None;
                         }
-                        controller.current_action = if has_valid_contender {
-                            let (contender_name, mut contender_action, _) = controller.contender_action.take().expect("has_valid_contender can only be true if contender_action is Some");
-                            if let Some(contender_fed_entry) =
-                                controller.actions_being_fed.get_mut(contender_name)
-                            {
-                                contender_fed_entry.rescheduled_in = None;
-                            }
-                            let contender_directive = contender_action.apply(
-                                TnuaActionContext {
-                                    frame_duration,
-                                    tracker,
-                                    proximity_sensor,
-                                    basis,
-                                    up_direction,
-                                },
-                                TnuaActionLifecycleStatus::CancelledFrom,
-                                motor.as_mut(),
-                            );
-                            if contender_action.violates_coyote_time() {
-                                basis.violate_coyote_time();
-                            }
-                            match contender_directive {
-                                TnuaActionLifecycleDirective::StillActive => {
-                                    if matches!(
-                                        controller.action_flow_status,
-                                        TnuaActionFlowStatus::ActionOngoing(_)
-                                    ) {
-                                        controller.action_flow_status =
-                                            TnuaActionFlowStatus::Cancelled {
-                                                old: name,
-                                                new: contender_name,
-                                            };
-                                    } else {
-                                        controller.action_flow_status =
-                                            TnuaActionFlowStatus::ActionStarted(contender_name);
-                                    }
-                                    Some((contender_name, contender_action))
+                        let contender_directive = contender_action.apply(
+                            TnuaActionContext {
+                                frame_duration,
+                                tracker,
+                                proximity_sensor,
+                                water_sensor,
+                                basis,
+                                up_direction,
+                            },
+                            TnuaActionLifecycleStatus::CancelledFrom,
+                            motor,
+                        );
+                        if contender_action.violates_coyote_time() {
+                            basis.violate_coyote_time();
+                        }
+                        if contender_action.suppresses_landing() {
+                            basis.suppress_landing();
+                        }
+                        events.push(TnuaActionEventKind::Cancelled {
+                            old: name,
+                            new: contender_name,
+                        });
+                        events.push(TnuaActionEventKind::Started {
+                            action: contender_name,
+                        });
+                        match contender_directive {
+                            TnuaActionLifecycleDirective::StillActive => {
+                                if matches!(
+                                    self.action_flow_status,
+                                    TnuaActionFlowStatus::ActionOngoing(_)
+                                ) {
+                                    self.action_flow_status = TnuaActionFlowStatus::Cancelled {
+                                        old: name,
+                                        new: contender_name,
+                                    };
+                                } else {
+                                    self.action_flow_status =
+                                        TnuaActionFlowStatus::ActionStarted(contender_name);
                                 }
-                                TnuaActionLifecycleDirective::Finished => {
-                                    if matches!(
-                                        controller.action_flow_status,
-                                        TnuaActionFlowStatus::ActionOngoing(_)
-                                    ) {
-                                        controller.action_flow_status =
-                                            TnuaActionFlowStatus::ActionEnded(name);
-                                    }
-                                    None
+                                Some((contender_name, contender_action))
+                            }
+                            TnuaActionLifecycleDirective::Finished => {
+                                if matches!(
+                                    self.action_flow_status,
+                                    TnuaActionFlowStatus::ActionOngoing(_)
+                                ) {
+                                    self.action_flow_status =
+                                        TnuaActionFlowStatus::ActionEnded(name);
                                 }
-                                TnuaActionLifecycleDirective::Reschedule { after_seconds } => {
-                                    if matches!(
-                                        controller.action_flow_status,
-                                        TnuaActionFlowStatus::ActionOngoing(_)
-                                    ) {
-                                        controller.action_flow_status =
-                                            TnuaActionFlowStatus::ActionEnded(name);
-                                    }
-                                    reschedule_action(
-                                        &mut controller.actions_being_fed,
-                                        after_seconds,
-                                    );
-                                    None
+                                events.push(TnuaActionEventKind::Ended {
+                                    action: contender_name,
+                                });
+                                None
+                            }
+                            TnuaActionLifecycleDirective::Reschedule { after_seconds } => {
+                                if matches!(
+                                    self.action_flow_status,
+                                    TnuaActionFlowStatus::ActionOngoing(_)
+                                ) {
+                                    self.action_flow_status =
+                                        TnuaActionFlowStatus::ActionEnded(name);
                                 }
+                                reschedule_action(&mut self.actions_being_fed, after_seconds);
+                                events.push(TnuaActionEventKind::Ended {
+                                    action: contender_name,
+                                });
+                                None
                             }
-                        } else {
-                            controller.action_flow_status = TnuaActionFlowStatus::ActionEnded(name);
-                            None
-                        };
-                    }
-                }
-            } else if has_valid_contender {
-                let (contender_name, mut contender_action, _) = controller
-                    .contender_action
-                    .take()
-                    .expect("has_valid_contender can only be true if contender_action is Some");
-                contender_action.apply(
-                    TnuaActionContext {
-                        frame_duration,
-                        tracker,
-                        proximity_sensor,
-                        basis,
-                        up_direction,
-                    },
-                    TnuaActionLifecycleStatus::Initiated,
-                    motor.as_mut(),
-                );
-                if contender_action.violates_coyote_time() {
-                    basis.violate_coyote_time();
+                        }
+                    } else {
+                        self.action_flow_status = TnuaActionFlowStatus::ActionEnded(name);
+                        events.push(TnuaActionEventKind::Ended { action: name });
+                        None
+                    };
                 }
-                controller.action_flow_status = TnuaActionFlowStatus::ActionStarted(contender_name);
-                controller.current_action = Some((contender_name, contender_action));
             }
+        } else if has_valid_contender {
+            let (contender_name, mut contender_action, _) = self
+                .contender_action
+                .take()
+                .expect("has_valid_contender can only be true if contender_action is Some");
+            contender_action.apply(
+                TnuaActionContext {
+                    frame_duration,
+                    tracker,
+                    proximity_sensor,
+                    water_sensor,
+                    basis,
+                    up_direction,
+                },
+                TnuaActionLifecycleStatus::Initiated,
+                motor,
+            );
+            if contender_action.violates_coyote_time() {
+                basis.violate_coyote_time();
+            }
+            if contender_action.suppresses_landing() {
+                basis.suppress_landing();
+            }
+            self.action_flow_status = TnuaActionFlowStatus::ActionStarted(contender_name);
+            events.push(TnuaActionEventKind::Started {
+                action: contender_name,
+            });
+            self.current_action = Some((contender_name, contender_action));
+        }
+    }
 
-            let sensor_case_range_for_action =
-                if let Some((_, current_action)) = &controller.current_action {
-                    current_action.proximity_sensor_cast_range()
-                } else {
-                    0.0
-                };
-
-            sensor.cast_range = sensor_cast_range_for_basis.max(sensor_case_range_for_action);
-            sensor.cast_direction = -up_direction;
+    fn proximity_sensor_cast_range(&self) -> Float {
+        if let Some((_, current_action)) = &self.current_action {
+            current_action.proximity_sensor_cast_range()
+        } else {
+            0.0
         }
+    }
 
-        // Cycle actions_being_fed
-        controller.actions_being_fed.retain(|_, fed_entry| {
+    fn cycle_actions_being_fed(&mut self, time: &Time) {
+        self.actions_being_fed.retain(|_, fed_entry| {
             if fed_entry.fed_this_frame {
                 fed_entry.fed_this_frame = false;
                 if let Some(rescheduled_in) = &mut fed_entry.rescheduled_in {
@@ -629,10 +1391,175 @@ fn apply_controller_system(
             }
         });
 
-        if let Some((contender_name, ..)) = controller.contender_action {
-            if !controller.actions_being_fed.contains_key(contender_name) {
-                controller.contender_action = None;
+        if let Some((contender_name, ..)) = self.contender_action {
+            if !self.actions_being_fed.contains_key(contender_name) {
+                self.contender_action = None;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtins::{TnuaBuiltinCrouch, TnuaBuiltinCrouchState, TnuaBuiltinDash};
+
+    fn decide(
+        rules: &TnuaActionCancellationRules,
+        running: impl TnuaAction,
+        running_name: &'static str,
+        contender_name: &'static str,
+    ) -> TnuaActionInitiationDirective {
+        rules.decide(running_name, &BoxableAction::new(running), contender_name)
+    }
+
+    fn jump() -> TnuaBuiltinJump {
+        TnuaBuiltinJump::default()
+    }
+
+    #[test]
+    fn cancellation_allowed_without_rules() {
+        let rules = TnuaActionCancellationRules::default();
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Allow
+        );
+    }
+
+    #[test]
+    fn cancellation_forbid_and_delay() {
+        let mut rules = TnuaActionCancellationRules::default();
+        rules
+            .forbid(TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME)
+            .delay(TnuaBuiltinJump::NAME, TnuaBuiltinCrouch::NAME);
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Reject
+        );
+        assert_eq!(
+            decide(
+                &rules,
+                jump(),
+                TnuaBuiltinJump::NAME,
+                TnuaBuiltinCrouch::NAME
+            ),
+            TnuaActionInitiationDirective::Delay
+        );
+        // Rules are directional.
+        assert_eq!(
+            decide(
+                &rules,
+                TnuaBuiltinDash::default(),
+                TnuaBuiltinDash::NAME,
+                TnuaBuiltinJump::NAME
+            ),
+            TnuaActionInitiationDirective::Allow
+        );
+
+        rules.clear_rule(TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME);
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Allow
+        );
+    }
+
+    #[test]
+    fn cancellation_allow_when_checks_running_state() {
+        let mut rules = TnuaActionCancellationRules::default();
+        rules.allow_when(
+            TnuaBuiltinCrouch::NAME,
+            TnuaBuiltinDash::NAME,
+            |_: &TnuaBuiltinCrouch, state| matches!(state, TnuaBuiltinCrouchState::Maintaining),
+        );
+        let mut crouch = BoxableAction::new(TnuaBuiltinCrouch::default());
+        assert_eq!(
+            rules.decide(TnuaBuiltinCrouch::NAME, &crouch, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Delay
+        );
+        crouch.state = TnuaBuiltinCrouchState::Maintaining;
+        assert_eq!(
+            rules.decide(TnuaBuiltinCrouch::NAME, &crouch, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Allow
+        );
+    }
+
+    #[test]
+    fn cancellation_rule_with_mismatched_type_is_ignored() {
+        let mut rules = TnuaActionCancellationRules::default();
+        rules.rule(
+            TnuaBuiltinJump::NAME,
+            TnuaBuiltinDash::NAME,
+            |_: &TnuaBuiltinCrouch, _| TnuaActionInitiationDirective::Reject,
+        );
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Allow
+        );
+    }
+
+    #[test]
+    fn cancellation_priorities() {
+        let mut rules = TnuaActionCancellationRules::default();
+        rules
+            .set_priority(TnuaBuiltinJump::NAME, 2)
+            .set_priority(TnuaBuiltinDash::NAME, 1)
+            .set_priority(TnuaBuiltinCrouch::NAME, 2);
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Delay
+        );
+        // Same priority can cancel.
+        assert_eq!(
+            decide(
+                &rules,
+                jump(),
+                TnuaBuiltinJump::NAME,
+                TnuaBuiltinCrouch::NAME
+            ),
+            TnuaActionInitiationDirective::Allow
+        );
+        // Higher priority can cancel.
+        assert_eq!(
+            decide(
+                &rules,
+                TnuaBuiltinDash::default(),
+                TnuaBuiltinDash::NAME,
+                TnuaBuiltinJump::NAME
+            ),
+            TnuaActionInitiationDirective::Allow
+        );
+        // Without a priority for both actions, priorities are not considered.
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, "unprioritized"),
+            TnuaActionInitiationDirective::Allow
+        );
+    }
+
+    #[test]
+    fn cancellation_explicit_rules_take_precedence_over_priorities() {
+        let mut rules = TnuaActionCancellationRules::default();
+        rules
+            .set_priority(TnuaBuiltinJump::NAME, 1)
+            .set_priority(TnuaBuiltinDash::NAME, 2)
+            .set_priority(TnuaBuiltinCrouch::NAME, 0)
+            .forbid(TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME)
+            .allow_when(
+                TnuaBuiltinJump::NAME,
+                TnuaBuiltinCrouch::NAME,
+                |_: &TnuaBuiltinJump, _| true,
+            );
+        assert_eq!(
+            decide(&rules, jump(), TnuaBuiltinJump::NAME, TnuaBuiltinDash::NAME),
+            TnuaActionInitiationDirective::Reject
+        );
+        assert_eq!(
+            decide(
+                &rules,
+                jump(),
+                TnuaBuiltinJump::NAME,
+                TnuaBuiltinCrouch::NAME
+            ),
+            TnuaActionInitiationDirective::Allow
+        );
+    }
+}
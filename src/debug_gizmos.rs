@@ -0,0 +1,278 @@
+//! Gizmos for visualizing what Tnua senses and does.
+//!
+//! Add [`TnuaDebugGizmosPlugin`] to draw them. Each kind of gizmo can be turned on and off with
+//! the [`TnuaDebugGizmos`] config group:
+//!
+//! ```no_run
+//! # use bevy::prelude::*;
+//! # use bevy_tnua::debug_gizmos::TnuaDebugGizmos;
+//! fn hide_radar_gizmos(mut config_store: ResMut<GizmoConfigStore>) {
+//!     let (_, tnua_gizmos) = config_store.config_mut::<TnuaDebugGizmos>();
+//!     tnua_gizmos.obstacle_radar = false;
+//! }
+//! ```
+use bevy::color::palettes::css;
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy::prelude::*;
+
+use crate::builtins::{TnuaBuiltinKnockback, TnuaBuiltinKnockbackState, TnuaBuiltinWalk};
+use crate::controller::TnuaController;
+use crate::math::{AsF32, Float};
+use crate::{
+    TnuaGhostSensor, TnuaMotor, TnuaObstacleRadar, TnuaProximitySensor, TnuaRigidBodyTracker,
+    TnuaSystemSet,
+};
+
+/// Draws gizmos for the sensors, the radar, the walk basis, the motor and the knockback velocity
+/// boundary of Tnua controlled characters.
+///
+/// This plugin should be registered in the same schedule as `TnuaControllerPlugin`.
+pub struct TnuaDebugGizmosPlugin {
+    schedule: InternedScheduleLabel,
+}
+
+impl TnuaDebugGizmosPlugin {
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
+impl Default for TnuaDebugGizmosPlugin {
+    fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl Plugin for TnuaDebugGizmosPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_gizmo_group::<TnuaDebugGizmos>();
+        app.add_systems(
+            self.schedule,
+            (
+                draw_proximity_sensors_system,
+                draw_obstacle_radars_system,
+                draw_walk_basis_system,
+                draw_motors_system,
+                draw_velocity_boundaries_system,
+            )
+                .after(TnuaSystemSet),
+        );
+    }
+}
+
+/// Configuration for the gizmos drawn by [`TnuaDebugGizmosPlugin`].
+///
+/// The general settings of the gizmos (line width, depth bias, etc.) can be configured in the
+/// [`GizmoConfig`] that goes with this group in the [`GizmoConfigStore`].
+#[derive(Reflect, GizmoConfigGroup)]
+pub struct TnuaDebugGizmos {
+    /// Draw the cast of [`TnuaProximitySensor`], and the point and normal of its hit.
+    pub proximity_sensor: bool,
+    /// Draw the hits of [`TnuaGhostSensor`].
+    pub ghost_sensor: bool,
+    /// Draw the cylinder of [`TnuaObstacleRadar`] and lines to its blips.
+    pub obstacle_radar: bool,
+    /// Draw the float height and the cling band of
+    /// [`TnuaBuiltinWalk`].
+    pub walk_basis: bool,
+    /// Draw the velocity changes of [`TnuaMotor`] as arrows.
+    pub motor: bool,
+    /// Draw the [`VelocityBoundary`](crate::util::VelocityBoundary) of an active
+    /// [`TnuaBuiltinKnockback`].
+    pub velocity_boundary: bool,
+    /// The length of the acceleration arrows, per one unit of acceleration.
+    ///
+    /// Boosts are drawn without scaling, but accelerations are usually much bigger so they need to
+    /// be scaled down.
+    pub acceleration_scale: f32,
+}
+
+impl Default for TnuaDebugGizmos {
+    fn default() -> Self {
+        Self {
+            proximity_sensor: true,
+            ghost_sensor: true,
+            obstacle_radar: true,
+            walk_basis: true,
+            motor: true,
+            velocity_boundary: true,
+            acceleration_scale: 0.1,
+        }
+    }
+}
+
+const NORMAL_LENGTH: f32 = 0.5;
+const MARKER_RADIUS: f32 = 0.05;
+
+fn draw_proximity_sensors_system(
+    mut gizmos: Gizmos<TnuaDebugGizmos>,
+    query: Query<(
+        &GlobalTransform,
+        &TnuaProximitySensor,
+        Option<&TnuaGhostSensor>,
+    )>,
+) {
+    let draw_sensor = gizmos.config_ext.proximity_sensor;
+    let draw_ghost_sensor = gizmos.config_ext.ghost_sensor;
+    if !draw_sensor && !draw_ghost_sensor {
+        return;
+    }
+    for (transform, sensor, ghost_sensor) in query.iter() {
+        let origin = transform.transform_point(sensor.cast_origin.f32());
+        let direction = *sensor.cast_direction;
+        let point_at = |proximity: Float| origin + proximity.f32() * direction;
+
+        if draw_sensor {
+            let end = point_at(sensor.cast_range);
+            if let Some(output) = sensor.output.as_ref() {
+                let hit_point = point_at(output.proximity);
+                gizmos.line(origin, hit_point, css::LIME);
+                gizmos.line(hit_point, end, css::GRAY);
+                gizmos.sphere(hit_point, MARKER_RADIUS, css::LIME);
+                gizmos.arrow(
+                    hit_point,
+                    hit_point + NORMAL_LENGTH * *output.normal,
+                    css::YELLOW,
+                );
+            } else {
+                gizmos.line(origin, end, css::GRAY);
+            }
+        }
+
+        if draw_ghost_sensor {
+            for ghost in ghost_sensor
+                .into_iter()
+                .flat_map(|ghost_sensor| ghost_sensor.iter())
+            {
+                let hit_point = point_at(ghost.proximity);
+                gizmos.sphere(hit_point, MARKER_RADIUS, css::VIOLET);
+                gizmos.arrow(
+                    hit_point,
+                    hit_point + NORMAL_LENGTH * *ghost.normal,
+                    css::VIOLET,
+                );
+            }
+        }
+    }
+}
+
+fn draw_obstacle_radars_system(
+    mut gizmos: Gizmos<TnuaDebugGizmos>,
+    query: Query<&TnuaObstacleRadar>,
+    blips_query: Query<&GlobalTransform>,
+) {
+    if !gizmos.config_ext.obstacle_radar {
+        return;
+    }
+    for radar in query.iter() {
+        let position = radar.tracked_position().f32();
+        if !position.is_finite() {
+            // The radar was not updated yet.
+            continue;
+        }
+        gizmos.primitive_3d(
+            &Cylinder::new(radar.radius.f32(), radar.height.f32()),
+            Isometry3d::new(
+                position,
+                Quat::from_rotation_arc(Vec3::Y, *radar.up_direction()),
+            ),
+            css::ORANGE,
+        );
+        for blip in radar.iter_blips() {
+            let Ok(blip_transform) = blips_query.get(blip) else {
+                continue;
+            };
+            gizmos.line(position, blip_transform.translation(), css::ORANGE);
+        }
+    }
+}
+
+fn draw_walk_basis_system(
+    mut gizmos: Gizmos<TnuaDebugGizmos>,
+    query: Query<(&GlobalTransform, &TnuaController, &TnuaProximitySensor)>,
+) {
+    if !gizmos.config_ext.walk_basis {
+        return;
+    }
+    for (transform, controller, sensor) in query.iter() {
+        let Some((walk, _)) = controller.concrete_basis::<TnuaBuiltinWalk>() else {
+            continue;
+        };
+        let Some(output) = sensor.output.as_ref() else {
+            continue;
+        };
+        let origin = transform.transform_point(sensor.cast_origin.f32());
+        let up = -*sensor.cast_direction;
+        let hit_point = origin - output.proximity.f32() * up;
+        let float_point = hit_point + walk.float_height.f32() * up;
+        let cling_point = float_point + walk.cling_distance.f32() * up;
+        let ring_rotation = Quat::from_rotation_arc(Vec3::Z, up);
+
+        gizmos.line(hit_point, float_point, css::AQUA);
+        gizmos.line(float_point, cling_point, css::TEAL);
+        gizmos.circle(Isometry3d::new(float_point, ring_rotation), 0.5, css::AQUA);
+        gizmos.circle(Isometry3d::new(cling_point, ring_rotation), 0.5, css::TEAL);
+        // The current offset from the float height, which the spring tries to close.
+        gizmos.line(origin, float_point, css::WHITE);
+    }
+}
+
+fn draw_motors_system(
+    mut gizmos: Gizmos<TnuaDebugGizmos>,
+    query: Query<(&TnuaRigidBodyTracker, &TnuaMotor)>,
+) {
+    if !gizmos.config_ext.motor {
+        return;
+    }
+    let acceleration_scale = gizmos.config_ext.acceleration_scale;
+    for (tracker, motor) in query.iter() {
+        let position = tracker.translation.f32();
+        for (vector, color) in [
+            (motor.lin.boost.f32(), css::RED),
+            (
+                acceleration_scale * motor.lin.acceleration.f32(),
+                css::TOMATO,
+            ),
+            (motor.ang.boost.f32(), css::BLUE),
+            (
+                acceleration_scale * motor.ang.acceleration.f32(),
+                css::DODGER_BLUE,
+            ),
+        ] {
+            if vector.is_finite() && vector != Vec3::ZERO {
+                gizmos.arrow(position, position + vector, color);
+            }
+        }
+    }
+}
+
+fn draw_velocity_boundaries_system(
+    mut gizmos: Gizmos<TnuaDebugGizmos>,
+    query: Query<(&TnuaRigidBodyTracker, &TnuaController)>,
+) {
+    if !gizmos.config_ext.velocity_boundary {
+        return;
+    }
+    for (tracker, controller) in query.iter() {
+        let position = tracker.translation.f32();
+        for (_, layer) in controller.iter_action_layers() {
+            let Some((_, TnuaBuiltinKnockbackState::Pushback { boundary })) =
+                layer.concrete_action::<TnuaBuiltinKnockback>()
+            else {
+                continue;
+            };
+            // The boundary is on the velocity, so it is drawn where the character would be in one
+            // second if it moved at the boundary's velocity.
+            let direction = *boundary.direction;
+            let boundary_point = position + boundary.frontier().f32() * direction;
+            gizmos.arrow(position, boundary_point, css::MAGENTA);
+            gizmos.rect(
+                Isometry3d::new(boundary_point, Quat::from_rotation_arc(Vec3::Z, direction)),
+                Vec2::ONE,
+                css::MAGENTA,
+            );
+        }
+    }
+}
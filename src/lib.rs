@@ -11,6 +11,8 @@
 //! * For Rapier 3D, add the [bevy-tnua-rapier3d](https://crates.io/crates/bevy-tnua-rapier3d) crate.
 //! * For Avian 2D, add the [bevy-tnua-avian2d](https://crates.io/crates/bevy-tnua-avian2d) crate.
 //! * For Avian 3D, add the [bevy-tnua-avian3d](https://crates.io/crates/bevy-tnua-avian3d) crate.
+//! * For testing without a physics engine, use the
+//!   [bevy-tnua-headless](https://crates.io/crates/bevy-tnua-headless) crate.
 //! * Third party integration crates. Such crates should depend on
 //!   [bevy-tnua-physics-integration-layer](https://crates.io/crates/bevy-tnua-physics-integration-layer)
 //!   and not the main bevy-tnua crate.
@@ -126,11 +128,18 @@
 //!
 //! [`TnuaController`](crate::prelude::TnuaController) can also be used to retreive data that can
 //! be used to decide which animation to play. A useful helper for that is [`TnuaAnimatingState`].
+//!
+//! ## Debugging
+//!
+//! With the `debug_gizmos` feature, add `TnuaDebugGizmosPlugin` (from the `debug_gizmos` module)
+//! to draw what the sensors detect and what the motor does.
 mod animating_helper;
 mod basis_action_traits;
 pub mod builtins;
 pub mod control_helpers;
 pub mod controller;
+#[cfg(feature = "debug_gizmos")]
+pub mod debug_gizmos;
 pub mod radar_lens;
 pub mod util;
 pub use animating_helper::{TnuaAnimatingState, TnuaAnimatingStateDirective};
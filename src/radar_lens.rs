@@ -183,6 +183,177 @@ impl<X: TnuaSpatialExt> TnuaRadarBlipLens<'_, X> {
             TnuaBlipSpatialRelation::Aeside(planar_direction)
         }
     }
+
+    /// Check if the obstacle has a top edge the character can grab and hang from.
+    ///
+    /// The edge must be above the character's position, but no higher than `reach`, and there
+    /// must be at least `clearance` of free space above it (checked against all the blips of the
+    /// radar). The top surface of the ledge is probed `clearance / 4` into the obstacle, so narrow
+    /// walls may not be detected as ledges.
+    ///
+    /// This only checks the geometry. The character should only grab the ledge while falling -
+    /// [`TnuaBuiltinLedgeHang`](crate::builtins::TnuaBuiltinLedgeHang) checks that on its own.
+    pub fn find_grabbable_ledge(
+        &self,
+        up: Dir3,
+        reach: Float,
+        clearance: Float,
+    ) -> Option<TnuaBlipLedge> {
+        let up_vector = up.adjust_precision();
+        let closest_point = self.closest_point().outside()?;
+        let position = self.radar().tracked_position();
+        let wall_direction =
+            Dir3::new((closest_point - position).reject_from(up_vector).f32()).ok()?;
+        let wall_vector = wall_direction.adjust_precision();
+
+        let extent = self.probe_extent_from_closest_point(up, reach);
+        if 0.9 * reach <= extent {
+            // The wall continues above the reach
+            return None;
+        }
+
+        // Find the exact height of the top surface by casting down on it from above.
+        let inset = 0.25 * clearance;
+        let probe_origin =
+            closest_point + extent * up_vector + inset * wall_vector + clearance * up_vector;
+        let (time_of_impact, top_normal) = self.radar_lens.ext.cast_ray(
+            probe_origin,
+            -up_vector,
+            2.0 * clearance,
+            &self.collider_data,
+        )?;
+        if time_of_impact <= 0.0 || top_normal.dot(up_vector) < 0.7 {
+            // Either the probe started inside the obstacle, or the top is not flat enough.
+            return None;
+        }
+        let top_height = (probe_origin - time_of_impact * up_vector - closest_point).dot(up_vector);
+        let edge_point = closest_point + top_height * up_vector;
+
+        let edge_height = (edge_point - position).dot(up_vector);
+        if edge_height < 0.0 || reach < edge_height {
+            return None;
+        }
+
+        for fraction in [0.5, 1.0] {
+            let point = edge_point + inset * wall_vector + fraction * clearance * up_vector;
+            let blocked = self.radar_lens.iter_blips().any(|blip| {
+                blip.is_interactable() && blip.closest_point_from(point, false).inside().is_some()
+            });
+            if blocked {
+                return None;
+            }
+        }
+
+        Some(TnuaBlipLedge {
+            edge_point,
+            wall_direction,
+            edge_direction: Dir3::new(up.cross(*wall_direction)).ok()?,
+        })
+    }
+
+    /// Measure the obstacle for vaulting or mantling over it.
+    ///
+    /// The obstacle's top must be no higher than `max_height` above the character's position (the
+    /// center of its collider - so an obstacle that is lower than that center will have a
+    /// negative [`height`](TnuaBlipVaultable::height)). The game code should decide, based on
+    /// that height, whether the obstacle is high enough to vault over instead of just stepping
+    /// on it.
+    ///
+    /// Obstacles that are deeper than `max_depth` are still returned (for mantling onto them),
+    /// but with their [`depth`](TnuaBlipVaultable::depth) set to `None`.
+    pub fn find_vaultable_obstacle(
+        &self,
+        up: Dir3,
+        max_height: Float,
+        max_depth: Float,
+    ) -> Option<TnuaBlipVaultable> {
+        let up_vector = up.adjust_precision();
+        let closest_point = self.closest_point().outside()?;
+        let position = self.radar().tracked_position();
+        let direction = Dir3::new((closest_point - position).reject_from(up_vector).f32()).ok()?;
+        let direction_vector = direction.adjust_precision();
+
+        let extent = self.probe_extent_from_closest_point(up, max_height);
+        if 0.9 * max_height <= extent {
+            // The obstacle continues above the maximum height
+            return None;
+        }
+        let top_point = closest_point + extent.max(0.0) * up_vector;
+
+        // Probe from above the far side, so that the closest point will be on the far edge if the
+        // obstacle is thin enough or on the top surface if it is not.
+        let probe_point = top_point + max_depth * (direction_vector + up_vector);
+        let far_point = self.closest_point_from(probe_point, false).get();
+        let depth = (far_point - top_point).dot(direction_vector).max(0.0);
+
+        Some(TnuaBlipVaultable {
+            top_point,
+            direction,
+            height: (top_point - position).dot(up_vector),
+            depth: (depth < 0.9 * max_depth).then_some(depth),
+        })
+    }
+
+    /// Check if the obstacle is a wall the character can run on.
+    ///
+    /// The wall must be upright (its normal must be close to perpendicular to `up`) and flat
+    /// enough at the `probe_offsets` - see [`flat_wall_score`](Self::flat_wall_score). The
+    /// returned normal is horizontal, even if the wall is a bit slanted.
+    pub fn find_runnable_wall(
+        &self,
+        up: Dir3,
+        probe_offsets: &[Float],
+        min_flat_wall_score: Float,
+    ) -> Option<TnuaBlipWall> {
+        let contact_point = self.closest_point().outside()?;
+        let normal = self.normal_from_closest_point();
+        if 0.3 < normal.dot(up.adjust_precision()).abs() {
+            return None;
+        }
+        let normal = Dir3::new(normal.reject_from(up.adjust_precision()).f32()).ok()?;
+        if self.flat_wall_score(up, probe_offsets) < min_flat_wall_score {
+            return None;
+        }
+        Some(TnuaBlipWall {
+            contact_point,
+            normal,
+        })
+    }
+}
+
+/// A ledge found by [`TnuaRadarBlipLens::find_grabbable_ledge`].
+#[derive(Debug, Clone, Copy)]
+pub struct TnuaBlipLedge {
+    /// The point on the top edge of the obstacle that is closest to the character.
+    pub edge_point: Vector3,
+    /// The horizontal direction from the character toward the obstacle.
+    pub wall_direction: Dir3,
+    /// The direction along the edge (perpendicular to both the up direction and the
+    /// `wall_direction`)
+    pub edge_direction: Dir3,
+}
+
+/// An obstacle found by [`TnuaRadarBlipLens::find_vaultable_obstacle`].
+#[derive(Debug, Clone, Copy)]
+pub struct TnuaBlipVaultable {
+    /// The point on the top edge of the obstacle that is closest to the character.
+    pub top_point: Vector3,
+    /// The horizontal direction from the character toward the obstacle.
+    pub direction: Dir3,
+    /// The height of the [`top_point`](Self::top_point) above the character's position.
+    pub height: Float,
+    /// The distance from the [`top_point`](Self::top_point) to the far side of the obstacle, or
+    /// `None` if the obstacle is too deep to vault over.
+    pub depth: Option<Float>,
+}
+
+/// A wall found by [`TnuaRadarBlipLens::find_runnable_wall`].
+#[derive(Debug, Clone, Copy)]
+pub struct TnuaBlipWall {
+    /// The point on the wall that is closest to the character.
+    pub contact_point: Vector3,
+    /// The horizontal direction pointing out of the wall.
+    pub normal: Dir3,
 }
 
 #[derive(Debug)]
@@ -3,7 +3,7 @@ mod velocity_boundary;
 
 use bevy::prelude::*;
 use bevy_tnua_physics_integration_layer::{
-    data_for_backends::TnuaVelChange,
+    data_for_backends::{TnuaRigidBodyTracker, TnuaVelChange},
     math::{AdjustPrecision, Float, Quaternion, Vector2, Vector3},
 };
 pub use command_impl_helpers::MotionHelper;
@@ -161,6 +161,229 @@ impl SegmentedJumpDurationCalculator {
     }
 }
 
+/// A prediction of the vertical motion of a jump, where different gravity is applied in different
+/// segments of the jump.
+///
+/// This is the counterpart of [`SegmentedJumpInitialVelocityCalculator`] - instead of calculating
+/// the velocity required for a jump, it calculates the height and velocity over time of a jump with
+/// a known initial velocity. Like [`SegmentedJumpDurationCalculator`], segments are added from the
+/// bottom of the jump (the takeoff) upward, and each segment lasts until the upward velocity drops
+/// to its `velocity_threshold`. Unlike the other calculators, it can also predict the part of the
+/// jump after the peak, and segments can be cut short at a certain time (e.g. when the player
+/// releases the jump button).
+///
+/// ```
+/// # use bevy_tnua::util::SegmentedJumpTrajectory;
+/// # use bevy_tnua::math::Float;
+/// # const GRAVITY: Float = 9.81;
+/// let mut trajectory = SegmentedJumpTrajectory::new(10.0);
+/// trajectory
+///     // Until the upward velocity drops to 2.0, use an extra gravity of 30.0
+///     .add_segment(GRAVITY + 30.0, 2.0)
+///     // Until the peak, use regular gravity
+///     .add_segment(GRAVITY, 0.0)
+///     // When falling down, use an extra gravity of 20.0
+///     .add_final_segment(GRAVITY + 20.0);
+/// let (peak_time, peak_height) = trajectory.peak().expect("the jump should have a peak");
+/// assert!(trajectory.velocity_at(peak_time).abs() < 0.001);
+/// let landing_time = trajectory
+///     .time_to_descend_to(0.0)
+///     .expect("the jump should land back at its takeoff height");
+/// assert!(peak_time < landing_time);
+/// ```
+///
+/// Note that:
+///
+/// * Heights are relative to the takeoff point, and times are relative to the takeoff.
+/// * After the last segment (if [`add_final_segment`](Self::add_final_segment) was not called)
+///   the velocity is assumed to stay constant.
+#[derive(Debug, Clone, Reflect)]
+pub struct SegmentedJumpTrajectory {
+    segments: Vec<SegmentedJumpTrajectorySegment>,
+    time: Float,
+    height: Float,
+    velocity: Float,
+}
+
+#[derive(Debug, Clone, Reflect)]
+struct SegmentedJumpTrajectorySegment {
+    start_time: Float,
+    duration: Float,
+    start_height: Float,
+    start_velocity: Float,
+    gravity: Float,
+}
+
+impl SegmentedJumpTrajectorySegment {
+    fn height_after(&self, time: Float) -> Float {
+        self.start_height + self.start_velocity * time - 0.5 * self.gravity * time.powi(2)
+    }
+
+    fn velocity_after(&self, time: Float) -> Float {
+        self.start_velocity - self.gravity * time
+    }
+}
+
+impl SegmentedJumpTrajectory {
+    /// Create a `SegmentedJumpTrajectory` for a jump that takes off with the specified upward
+    /// velocity.
+    pub fn new(initial_velocity: Float) -> Self {
+        Self {
+            segments: Vec::new(),
+            time: 0.0,
+            height: 0.0,
+            velocity: initial_velocity,
+        }
+    }
+
+    /// Continue the jump under the given gravity until the upward velocity drops to
+    /// `velocity_threshold`.
+    ///
+    /// If the upward velocity is already at or below the threshold, nothing will be changed.
+    pub fn add_segment(&mut self, gravity: Float, velocity_threshold: Float) -> &mut Self {
+        self.add_segment_until(gravity, velocity_threshold, Float::INFINITY)
+    }
+
+    /// Continue the jump under the given gravity until the upward velocity drops to
+    /// `velocity_threshold` or until `time_limit` (measured from the takeoff) - whichever comes
+    /// first.
+    ///
+    /// If the upward velocity is already at or below the threshold, or if the time limit has
+    /// already passed, nothing will be changed.
+    pub fn add_segment_until(
+        &mut self,
+        gravity: Float,
+        velocity_threshold: Float,
+        time_limit: Float,
+    ) -> &mut Self {
+        if self.velocity <= velocity_threshold || time_limit <= self.time {
+            return self;
+        }
+        let duration_to_threshold = if 0.0 < gravity {
+            (self.velocity - velocity_threshold) / gravity
+        } else {
+            Float::INFINITY
+        };
+        self.push_segment(gravity, duration_to_threshold.min(time_limit - self.time));
+        self
+    }
+
+    /// Continue the jump under the given gravity indefinitely.
+    ///
+    /// Segments added after this one will be ignored.
+    pub fn add_final_segment(&mut self, gravity: Float) -> &mut Self {
+        if self.time.is_finite() {
+            self.push_segment(gravity, Float::INFINITY);
+        }
+        self
+    }
+
+    fn push_segment(&mut self, gravity: Float, duration: Float) {
+        let segment = SegmentedJumpTrajectorySegment {
+            start_time: self.time,
+            duration,
+            start_height: self.height,
+            start_velocity: self.velocity,
+            gravity,
+        };
+        if duration.is_finite() {
+            self.height = segment.height_after(duration);
+            self.velocity = segment.velocity_after(duration);
+        }
+        self.time += duration;
+        self.segments.push(segment);
+    }
+
+    /// The segment active at the given time, and the time passed since it started.
+    fn segment_at(&self, time: Float) -> Option<(&SegmentedJumpTrajectorySegment, Float)> {
+        let segment = self
+            .segments
+            .iter()
+            .take_while(|segment| segment.start_time <= time)
+            .last()?;
+        Some((segment, time - segment.start_time))
+    }
+
+    /// The height, relative to the takeoff point, at the given time since the takeoff.
+    pub fn height_at(&self, time: Float) -> Float {
+        match self.segment_at(time) {
+            Some((segment, time)) if time <= segment.duration => segment.height_after(time),
+            // After the last segment
+            Some(_) => self.height + self.velocity * (time - self.time),
+            None => self.velocity * time,
+        }
+    }
+
+    /// The upward velocity at the given time since the takeoff.
+    pub fn velocity_at(&self, time: Float) -> Float {
+        match self.segment_at(time) {
+            Some((segment, time)) if time <= segment.duration => segment.velocity_after(time),
+            _ => self.velocity,
+        }
+    }
+
+    /// The time since the takeoff and the height of the peak of the jump.
+    ///
+    /// Returns `None` if the jump never stops going up.
+    pub fn peak(&self) -> Option<(Float, Float)> {
+        for segment in self.segments.iter() {
+            if segment.start_velocity <= 0.0 {
+                return Some((segment.start_time, segment.start_height));
+            }
+            if 0.0 < segment.gravity {
+                let time_to_peak = segment.start_velocity / segment.gravity;
+                if time_to_peak <= segment.duration {
+                    return Some((
+                        segment.start_time + time_to_peak,
+                        segment.height_after(time_to_peak),
+                    ));
+                }
+            }
+        }
+        if self.velocity <= 0.0 {
+            Some((self.time, self.height))
+        } else {
+            None
+        }
+    }
+
+    /// The time since the takeoff when the jump, on its way down, reaches the given height
+    /// (relative to the takeoff point).
+    ///
+    /// Returns `None` if the jump never gets down to that height - or if it never gets up to it in
+    /// the first place.
+    pub fn time_to_descend_to(&self, height: Float) -> Option<Float> {
+        let (peak_time, peak_height) = self.peak()?;
+        if peak_height < height {
+            return None;
+        }
+        for segment in self.segments.iter() {
+            if segment.start_time + segment.duration < peak_time {
+                continue;
+            }
+            let height_diff = segment.start_height - height;
+            let time = if 0.0 < segment.gravity {
+                // The later root of `height_after(time) == height`
+                let discriminant =
+                    segment.start_velocity.powi(2) + 2.0 * segment.gravity * height_diff;
+                (segment.start_velocity + discriminant.max(0.0).sqrt()) / segment.gravity
+            } else if segment.start_velocity < 0.0 {
+                height_diff / -segment.start_velocity
+            } else {
+                continue;
+            };
+            if time <= segment.duration {
+                return Some(segment.start_time + time.max(0.0));
+            }
+        }
+        if self.velocity < 0.0 && height <= self.height {
+            Some(self.time + (self.height - height) / -self.velocity)
+        } else {
+            None
+        }
+    }
+}
+
 /// Calculate the rotation around `around_axis` required to rotate the character from
 /// `current_forward` to `desired_forward`.
 pub fn rotation_arc_around_axis(
@@ -212,3 +435,59 @@ pub fn calc_angular_velchange_to_force_forward(
 
     TnuaVelChange::boost(torque_to_turn * up_direction.adjust_precision())
 }
+
+/// Calculate the angular velocity change required to keep the character upright and turn it so
+/// that its negative Z will face `desired_forward`.
+///
+/// This is how the builtin bases handle the character's rotation. If `desired_forward` is `None`,
+/// only the tilt is fixed.
+pub fn calc_angular_velchange_to_stay_upright(
+    tracker: &TnuaRigidBodyTracker,
+    up_direction: Dir3,
+    desired_forward: Option<Dir3>,
+    frame_duration: Float,
+    tilt_offset_angvel: Float,
+    tilt_offset_angacl: Float,
+    turning_angvel: Float,
+) -> TnuaVelChange {
+    // Tilt
+
+    let torque_to_fix_tilt = {
+        let tilted_up = tracker.rotation.mul_vec3(Vector3::Y);
+
+        let rotation_required_to_fix_tilt =
+            Quaternion::from_rotation_arc(tilted_up, up_direction.adjust_precision());
+
+        let desired_angvel = (rotation_required_to_fix_tilt.xyz() / frame_duration)
+            .clamp_length_max(tilt_offset_angvel);
+        let angular_velocity_diff = desired_angvel - tracker.angvel;
+        angular_velocity_diff.clamp_length_max(frame_duration * tilt_offset_angacl)
+    };
+
+    // Turning
+
+    let desired_angvel = if let Some(desired_forward) = desired_forward {
+        let current_forward = tracker.rotation.mul_vec3(Vector3::NEG_Z);
+        let rotation_along_up_axis = rotation_arc_around_axis(
+            up_direction,
+            current_forward,
+            desired_forward.adjust_precision(),
+        )
+        .unwrap_or(0.0);
+        (rotation_along_up_axis / frame_duration).clamp(-turning_angvel, turning_angvel)
+    } else {
+        0.0
+    };
+
+    // NOTE: This is the regular axis system so we used the configured up.
+    let existing_angvel = tracker.angvel.dot(up_direction.adjust_precision());
+
+    // This is the torque. Should it be clamped by an acceleration? From experimenting with
+    // this I think it's meaningless and only causes bugs.
+    let torque_to_turn = desired_angvel - existing_angvel;
+
+    let existing_turn_torque = torque_to_fix_tilt.dot(up_direction.adjust_precision());
+    let torque_to_turn = torque_to_turn - existing_turn_torque;
+
+    TnuaVelChange::boost(torque_to_fix_tilt + torque_to_turn * up_direction.adjust_precision())
+}
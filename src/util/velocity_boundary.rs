@@ -5,7 +5,8 @@ use bevy::prelude::*;
 
 /// An indication that a character was knocked back and "struggles" to get back to its original
 /// velocity.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VelocityBoundary {
     base: Float,
     original_frontier: Float,
@@ -65,6 +66,11 @@ impl VelocityBoundary {
         self.no_push_timer.finished() || self.frontier <= self.base
     }
 
+    /// The velocity, along [`direction`](Self::direction), that the boundary currently stands at.
+    pub fn frontier(&self) -> Float {
+        self.frontier
+    }
+
     /// Calculate how a boost needs to be adjusted according to the boundary.
     ///
     /// Note that the returned value is the boost limit only on the axis of the returned direction.
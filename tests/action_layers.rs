@@ -0,0 +1,326 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use bevy_tnua::builtins::TnuaBuiltinCrouch;
+use bevy_tnua::controller::TnuaActionFlowStatus;
+use bevy_tnua::math::{Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::util::MotionHelper;
+use bevy_tnua::{
+    TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor, TnuaVelChange,
+};
+use bevy_tnua_headless::TnuaHeadlessRigidBody;
+
+use common::{Frame, Hold};
+
+/// An action that overrides the motor's linear velocity change with one that moves the character
+/// horizontally at the given velocity - discarding whatever the layers before it did.
+#[derive(Clone, Reflect)]
+struct MoveAt(Vector3);
+
+impl TnuaAction for MoveAt {
+    const NAME: &'static str = "MoveAt";
+    type State = ();
+    const VIOLATES_COYOTE_TIME: bool = false;
+
+    fn apply(
+        &self,
+        _state: &mut Self::State,
+        ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if !lifecycle_status.is_active() {
+            return TnuaActionLifecycleDirective::Finished;
+        }
+        motor.lin =
+            ctx.negate_gravity() + TnuaVelChange::boost(self.0 - ctx.tracker.velocity.with_y(0.0));
+        TnuaActionLifecycleDirective::StillActive
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        TnuaActionInitiationDirective::Allow
+    }
+}
+
+/// An action that asks to be restarted after [`Pulse::INTERVAL`] on the frame it starts.
+#[derive(Clone, Reflect)]
+struct Pulse;
+
+impl Pulse {
+    const INTERVAL: Float = 0.2;
+}
+
+impl TnuaAction for Pulse {
+    const NAME: &'static str = "Pulse";
+    type State = ();
+    const VIOLATES_COYOTE_TIME: bool = false;
+
+    fn apply(
+        &self,
+        _state: &mut Self::State,
+        _ctx: TnuaActionContext,
+        _lifecycle_status: TnuaActionLifecycleStatus,
+        _motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        TnuaActionLifecycleDirective::Reschedule {
+            after_seconds: Self::INTERVAL,
+        }
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        TnuaActionInitiationDirective::Allow
+    }
+}
+
+const UPPER: &str = "upper";
+const LOWER: &str = "lower";
+
+type Control = fn(usize, &mut TnuaController);
+
+#[derive(Resource)]
+struct ControlFn(Control);
+
+fn control(frame: Res<Frame>, control_fn: Res<ControlFn>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        (control_fn.0)(frame.0, &mut controller);
+    }
+}
+
+fn setup(layers: &[&'static str], control_fn: Control) -> (App, Entity) {
+    let mut app = common::app();
+    app.insert_resource(ControlFn(control_fn));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    let mut controller = app
+        .world_mut()
+        .get_mut::<TnuaController>(character)
+        .unwrap();
+    for layer in layers {
+        controller.action_layer(layer);
+    }
+    (app, character)
+}
+
+/// The name of the action running in each layer.
+fn layer_actions(app: &App, character: Entity) -> Vec<(&'static str, Option<&'static str>)> {
+    common::controller(app, character)
+        .iter_action_layers()
+        .map(|(name, layer)| (name, layer.action_name()))
+        .collect()
+}
+
+fn flow_status(app: &App, character: Entity, layer: &str) -> TnuaActionFlowStatus {
+    common::controller(app, character)
+        .get_action_layer(layer)
+        .unwrap()
+        .action_flow_status()
+        .clone()
+}
+
+fn run(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+#[test]
+fn actions_on_different_layers_run_at_the_same_time() {
+    let (mut app, character) = setup(&[UPPER], |frame, controller| {
+        if 5 <= frame {
+            controller.action(TnuaBuiltinJump::default());
+            controller.action_layer(UPPER).action(TnuaBuiltinCrouch {
+                float_offset: -0.3,
+                ..Default::default()
+            });
+        }
+    });
+    run(&mut app, 5);
+    assert_eq!(
+        layer_actions(&app, character),
+        [
+            (
+                TnuaController::MAIN_ACTION_LAYER,
+                Some(TnuaBuiltinJump::NAME)
+            ),
+            (UPPER, Some(TnuaBuiltinCrouch::NAME)),
+        ]
+    );
+    assert!(matches!(
+        flow_status(&app, character, TnuaController::MAIN_ACTION_LAYER),
+        TnuaActionFlowStatus::ActionStarted(TnuaBuiltinJump::NAME)
+    ));
+    assert!(matches!(
+        flow_status(&app, character, UPPER),
+        TnuaActionFlowStatus::ActionStarted(TnuaBuiltinCrouch::NAME)
+    ));
+}
+
+/// Run [`MoveAt`] on both layers, each with a different velocity, and return the velocity the
+/// character ended up with.
+fn velocity_with_layers_created_in_order(layers: &[&'static str]) -> Vector3 {
+    let (mut app, character) = setup(layers, |frame, controller| {
+        if 5 <= frame {
+            controller
+                .action_layer(LOWER)
+                .action(MoveAt(Vector3::X * 2.0));
+            controller
+                .action_layer(UPPER)
+                .action(MoveAt(Vector3::Z * 2.0));
+        }
+    });
+    run(&mut app, 20);
+    app.world()
+        .get::<TnuaHeadlessRigidBody>(character)
+        .unwrap()
+        .linvel
+        .with_y(0.0)
+}
+
+#[test]
+fn layers_apply_in_creation_order() {
+    // The layer created last is applied last, so its motor changes win.
+    let velocity = velocity_with_layers_created_in_order(&[LOWER, UPPER]);
+    assert!(velocity.distance(Vector3::Z * 2.0) < 0.01, "{velocity}");
+    let velocity = velocity_with_layers_created_in_order(&[UPPER, LOWER]);
+    assert!(velocity.distance(Vector3::X * 2.0) < 0.01, "{velocity}");
+}
+
+#[test]
+fn action_finishing_on_one_layer_leaves_other_layer_running() {
+    let (mut app, character) = setup(&[UPPER], |frame, controller| {
+        if (5..10).contains(&frame) {
+            controller.action(Hold);
+        }
+        if 5 <= frame {
+            controller.action_layer(UPPER).action(Hold);
+        }
+    });
+    run(&mut app, 10);
+    assert!(matches!(
+        flow_status(&app, character, TnuaController::MAIN_ACTION_LAYER),
+        TnuaActionFlowStatus::ActionEnded(Hold::NAME)
+    ));
+    assert!(matches!(
+        flow_status(&app, character, UPPER),
+        TnuaActionFlowStatus::ActionOngoing(Hold::NAME)
+    ));
+    run(&mut app, 5);
+    assert_eq!(
+        layer_actions(&app, character),
+        [
+            (TnuaController::MAIN_ACTION_LAYER, None),
+            (UPPER, Some(Hold::NAME)),
+        ]
+    );
+}
+
+#[test]
+fn action_cancelled_on_one_layer_leaves_other_layer_running() {
+    let (mut app, character) = setup(&[UPPER], |frame, controller| {
+        if 5 <= frame {
+            controller.action(Hold);
+        }
+        if (5..10).contains(&frame) {
+            controller.action_layer(UPPER).named_action("first", Hold);
+        }
+        if 9 <= frame {
+            controller.action_layer(UPPER).named_action("second", Hold);
+        }
+    });
+    run(&mut app, 9);
+    assert!(matches!(
+        flow_status(&app, character, UPPER),
+        TnuaActionFlowStatus::Cancelled {
+            old: "first",
+            new: "second"
+        }
+    ));
+    assert!(matches!(
+        flow_status(&app, character, TnuaController::MAIN_ACTION_LAYER),
+        TnuaActionFlowStatus::ActionOngoing(Hold::NAME)
+    ));
+    run(&mut app, 5);
+    assert_eq!(
+        layer_actions(&app, character),
+        [
+            (TnuaController::MAIN_ACTION_LAYER, Some(Hold::NAME)),
+            (UPPER, Some("second")),
+        ]
+    );
+}
+
+#[test]
+fn contenders_do_not_cross_layers() {
+    let (mut app, character) = setup(&[UPPER], |frame, controller| {
+        if 5 <= frame {
+            controller.action(Hold);
+        }
+        if 10 <= frame {
+            // On the main layer this would have cancelled `Hold`.
+            controller
+                .action_layer(UPPER)
+                .named_action("contender", Hold);
+        }
+    });
+    run(&mut app, 15);
+    assert_eq!(
+        layer_actions(&app, character),
+        [
+            (TnuaController::MAIN_ACTION_LAYER, Some(Hold::NAME)),
+            (UPPER, Some("contender")),
+        ]
+    );
+}
+
+#[test]
+fn reschedule_is_kept_per_layer() {
+    let (mut app, character) = setup(&[UPPER], |frame, controller| {
+        if 5 <= frame {
+            controller.action(Hold);
+            controller.action_layer(UPPER).action(Pulse);
+        }
+    });
+    let mut pulse_frames = Vec::new();
+    for _ in 0..60 {
+        app.update();
+        let frame = app.world().resource::<Frame>().0;
+        if matches!(
+            flow_status(&app, character, UPPER),
+            TnuaActionFlowStatus::ActionEnded(Pulse::NAME)
+        ) {
+            pulse_frames.push(frame);
+        }
+        if 5 <= frame {
+            assert_eq!(
+                common::controller(&app, character).action_name(),
+                Some(Hold::NAME),
+                "main layer action was disturbed on frame {frame}"
+            );
+        }
+    }
+    // Pulse ends on the same frame it starts, and restarts once the interval has passed - which
+    // takes a couple of extra frames for the reschedule timer to tick and for the restarted action
+    // to get through the contender slot.
+    let interval_frames = (Pulse::INTERVAL * common::FRAMES_PER_SECOND as Float).round() as usize;
+    assert!(3 <= pulse_frames.len(), "{pulse_frames:?}");
+    for pair in pulse_frames.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!(
+            (interval_frames..=interval_frames + 2).contains(&gap),
+            "{pulse_frames:?}"
+        );
+    }
+}
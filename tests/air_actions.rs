@@ -0,0 +1,130 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+use bevy_tnua::control_helpers::TnuaSimpleAirActionsCounter;
+use bevy_tnua::math::Vector3;
+use bevy_tnua::prelude::*;
+use bevy_tnua::{
+    TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor,
+};
+
+use common::Frame;
+
+/// An action that refreshes the air actions budget, and stays active for as long as it is fed.
+#[derive(Clone, Reflect)]
+struct Refresh;
+
+impl TnuaAction for Refresh {
+    const NAME: &'static str = "Refresh";
+    type State = ();
+    const VIOLATES_COYOTE_TIME: bool = true;
+
+    fn apply(
+        &self,
+        _state: &mut Self::State,
+        _ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        _motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if lifecycle_status.is_active() {
+            TnuaActionLifecycleDirective::StillActive
+        } else {
+            TnuaActionLifecycleDirective::Finished
+        }
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        TnuaActionInitiationDirective::Allow
+    }
+
+    fn refreshes_air_actions(&self, _state: &Self::State) -> bool {
+        true
+    }
+}
+
+const AIR_JUMP: &str = "air jump";
+const REFRESH_LAYER: &str = "refresh";
+const REFRESH_FRAME: usize = 20;
+
+#[derive(Resource)]
+struct RefreshOnLayer(&'static str);
+
+fn control(
+    frame: Res<Frame>,
+    refresh_on_layer: Res<RefreshOnLayer>,
+    mut query: Query<&mut TnuaController>,
+) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        if (1..=5).contains(&frame.0) {
+            controller.action(TnuaBuiltinJump {
+                height: 3.0,
+                ..Default::default()
+            });
+        }
+        if (10..=12).contains(&frame.0) {
+            controller.named_action(
+                AIR_JUMP,
+                TnuaBuiltinJump {
+                    height: 1.0,
+                    allow_in_air: true,
+                    ..Default::default()
+                },
+            );
+        }
+        if (REFRESH_FRAME..REFRESH_FRAME + 5).contains(&frame.0) {
+            controller.action_layer(refresh_on_layer.0).action(Refresh);
+        }
+    }
+}
+
+/// Jump, air jump, and then refresh the air actions on the given layer.
+///
+/// Returns the counter's count right before the refresh and right after it, and the air number
+/// the refreshing action got.
+fn refresh_in_air(refresh_on_layer: &'static str) -> (Option<usize>, Option<usize>, usize) {
+    let mut app = common::app();
+    app.insert_resource(RefreshOnLayer(refresh_on_layer));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    let mut counter = TnuaSimpleAirActionsCounter::default();
+    let mut count_before = None;
+    for _ in 0..REFRESH_FRAME {
+        app.update();
+        counter.update(common::controller(&app, character));
+        if app.world().resource::<Frame>().0 == REFRESH_FRAME - 1 {
+            count_before = counter.get_count_mut().copied();
+        }
+    }
+    assert!(common::controller(&app, character).is_airborne().unwrap());
+    (
+        count_before,
+        counter.get_count_mut().copied(),
+        counter.air_count_for(Refresh::NAME),
+    )
+}
+
+#[test]
+fn refreshing_action_counts_like_jump_from_ground() {
+    let (count_before, count_after, refresh_air_number) =
+        refresh_in_air(TnuaController::MAIN_ACTION_LAYER);
+    // The jump from the ground and the air jump.
+    assert_eq!(count_before, Some(2));
+    assert_eq!(refresh_air_number, 0);
+    // Just like right after jumping from the ground - so the next air jump is the first one.
+    assert_eq!(count_after, Some(1));
+}
+
+#[test]
+fn refreshing_action_on_another_layer_resets_the_count() {
+    let (count_before, count_after, _) = refresh_in_air(REFRESH_LAYER);
+    assert_eq!(count_before, Some(2));
+    assert_eq!(count_after, Some(1));
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::TnuaBuiltinChargedJump;
+use bevy_tnua::control_helpers::TnuaSimpleAirActionsCounter;
+use bevy_tnua::math::{Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua_headless::TnuaHeadlessRigidBody;
+
+use common::Frame;
+
+/// The charge is released on the frame after this one.
+const LAST_CHARGE_FRAME: usize = 60;
+
+#[derive(Resource)]
+struct ChargedJump(TnuaBuiltinChargedJump);
+
+fn control(frame: Res<Frame>, jump: Res<ChargedJump>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::X * 4.0));
+        if frame.0 <= LAST_CHARGE_FRAME {
+            controller.action(jump.0.clone());
+        }
+    }
+}
+
+/// Charge while walking off a ledge, and release the charge in the air.
+///
+/// Returns the frame the action finished on and the highest upward speed after the release.
+fn release_after_walking_off_ledge(jump: TnuaBuiltinChargedJump) -> (Option<usize>, Float) {
+    let mut app = common::app();
+    app.insert_resource(ChargedJump(jump));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    // A ledge at 2.0 - reached at about 0.4 seconds into the charge.
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(0.0, -0.5, 0.0),
+        Vector3::new(2.0, 0.5, 2.0),
+    );
+    // Something to land on, far below.
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(0.0, -100.5, 0.0),
+        Vector3::new(100.0, 0.5, 100.0),
+    );
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+
+    let mut finished_on = None;
+    let mut max_upward_speed = Float::NEG_INFINITY;
+    for _ in 0..LAST_CHARGE_FRAME + 30 {
+        app.update();
+        let frame = app.world().resource::<Frame>().0;
+        if frame <= LAST_CHARGE_FRAME {
+            // The sensor has no output on the first frame, so the action can only start on the
+            // second one.
+            if frame == 1 {
+                continue;
+            }
+            assert_eq!(
+                common::controller(&app, character).action_name(),
+                Some(TnuaBuiltinChargedJump::NAME),
+            );
+            continue;
+        }
+        let linvel = app
+            .world()
+            .get::<TnuaHeadlessRigidBody>(character)
+            .unwrap()
+            .linvel;
+        max_upward_speed = max_upward_speed.max(linvel.y);
+        if finished_on.is_none() && common::controller(&app, character).action_name().is_none() {
+            finished_on = Some(frame);
+        }
+    }
+    assert!(
+        common::position(&app, character).y < -1.0,
+        "character did not walk off the ledge"
+    );
+    (finished_on, max_upward_speed)
+}
+
+#[test]
+fn release_in_the_air_finishes_without_jumping() {
+    let (finished_on, max_upward_speed) =
+        release_after_walking_off_ledge(TnuaBuiltinChargedJump::default());
+    assert_eq!(finished_on, Some(LAST_CHARGE_FRAME + 1));
+    assert!(max_upward_speed <= 0.0, "jumped at {max_upward_speed}");
+}
+
+#[test]
+fn release_in_the_air_jumps_when_allowed_in_air() {
+    let (_, max_upward_speed) = release_after_walking_off_ledge(TnuaBuiltinChargedJump {
+        jump: TnuaBuiltinJump {
+            allow_in_air: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    assert!(0.0 < max_upward_speed, "did not jump");
+}
+
+#[test]
+fn charging_is_not_an_air_action() {
+    let mut app = common::app();
+    app.insert_resource(ChargedJump(TnuaBuiltinChargedJump::default()));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    let mut counter = TnuaSimpleAirActionsCounter::default();
+    let mut max_height = Float::NEG_INFINITY;
+    for _ in 0..LAST_CHARGE_FRAME + 180 {
+        app.update();
+        let controller = common::controller(&app, character);
+        counter.update(controller);
+        let frame = app.world().resource::<Frame>().0;
+        if frame <= LAST_CHARGE_FRAME {
+            // Charging keeps the character on the ground, with the coyote time intact.
+            assert!(!controller.is_airborne().unwrap(), "airborne on {frame}");
+            assert_eq!(counter.get_count_mut(), None, "counted on {frame}");
+        } else if let Some(count) = counter.get_count_mut() {
+            // The jump itself, once released, is counted like a jump from the ground.
+            assert_eq!(*count, 1, "on {frame}");
+            max_height = max_height.max(common::position(&app, character).y);
+        }
+    }
+    assert!(
+        common::FLOAT_HEIGHT as Float + 1.0 < max_height,
+        "only reached {max_height}"
+    );
+    assert_eq!(counter.get_count_mut(), None, "did not land");
+}
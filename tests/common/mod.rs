@@ -0,0 +1,134 @@
+//! Setup shared by the tests that run Tnua on the headless backend.
+
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::{Stopwatch, TimeUpdateStrategy};
+use bevy_tnua::math::{AdjustPrecision, AsF32, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{
+    TnuaActionContext, TnuaActionInitiationDirective, TnuaActionLifecycleDirective,
+    TnuaActionLifecycleStatus, TnuaMotor,
+};
+use bevy_tnua_headless::{TnuaHeadlessCollider, TnuaHeadlessPlugin, TnuaHeadlessRigidBody};
+
+pub const FRAMES_PER_SECOND: u32 = 60;
+pub const CHARACTER_RADIUS: f32 = 0.5;
+pub const FLOAT_HEIGHT: f32 = 1.0;
+
+/// The number of the current frame, counted from 1 - incremented right before the user controls
+/// run.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct Frame(pub usize);
+
+fn advance_frame_counter(mut frame: ResMut<Frame>) {
+    frame.0 += 1;
+}
+
+/// An app that runs Tnua on the headless backend with a fixed frame duration.
+///
+/// The control systems should be added in [`TnuaUserControlsSystemSet`].
+pub fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(
+        Duration::from_secs(1) / FRAMES_PER_SECOND,
+    ));
+    app.add_plugins((
+        TnuaHeadlessPlugin::default(),
+        TnuaControllerPlugin::default(),
+    ));
+    app.init_resource::<Frame>();
+    app.add_systems(
+        Update,
+        advance_frame_counter.before(TnuaUserControlsSystemSet),
+    );
+    app
+}
+
+pub fn spawn_static_cuboid(app: &mut App, position: Vector3, half_extents: Vector3) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_translation(position.f32()),
+            TnuaHeadlessCollider::Cuboid { half_extents },
+        ))
+        .id()
+}
+
+/// A wide floor whose top is at zero height.
+pub fn spawn_floor(app: &mut App) -> Entity {
+    spawn_static_cuboid(
+        app,
+        Vector3::new(0.0, -0.5, 0.0),
+        Vector3::new(100.0, 0.5, 100.0),
+    )
+}
+
+/// A spherical character, standing at its float height above the given point.
+pub fn spawn_character(app: &mut App, ground_point: Vector3) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_translation(ground_point.f32() + FLOAT_HEIGHT * Vec3::Y),
+            TnuaHeadlessRigidBody::default(),
+            TnuaHeadlessCollider::Sphere {
+                radius: CHARACTER_RADIUS.adjust_precision(),
+            },
+            TnuaController::default(),
+        ))
+        .id()
+}
+
+pub fn position(app: &App, entity: Entity) -> Vector3 {
+    app.world()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation
+        .adjust_precision()
+}
+
+pub fn controller(app: &App, entity: Entity) -> &TnuaController {
+    app.world().get::<TnuaController>(entity).unwrap()
+}
+
+pub fn walk(desired_velocity: Vector3) -> TnuaBuiltinWalk {
+    TnuaBuiltinWalk {
+        float_height: FLOAT_HEIGHT.adjust_precision(),
+        desired_velocity,
+        ..Default::default()
+    }
+}
+
+/// An action that does nothing, and stays active for as long as it is fed.
+#[derive(Clone, Reflect)]
+pub struct Hold;
+
+impl TnuaAction for Hold {
+    const NAME: &'static str = "Hold";
+    type State = ();
+    const VIOLATES_COYOTE_TIME: bool = false;
+
+    fn apply(
+        &self,
+        _state: &mut Self::State,
+        _ctx: TnuaActionContext,
+        lifecycle_status: TnuaActionLifecycleStatus,
+        _motor: &mut TnuaMotor,
+    ) -> TnuaActionLifecycleDirective {
+        if lifecycle_status.is_active() {
+            TnuaActionLifecycleDirective::StillActive
+        } else {
+            TnuaActionLifecycleDirective::Finished
+        }
+    }
+
+    fn initiation_decision(
+        &self,
+        _ctx: TnuaActionContext,
+        _being_fed_for: &Stopwatch,
+    ) -> TnuaActionInitiationDirective {
+        TnuaActionInitiationDirective::Allow
+    }
+}
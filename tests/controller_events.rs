@@ -0,0 +1,222 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::controller::{
+    TnuaActionEvent, TnuaActionEventKind, TnuaBasisEvent, TnuaBasisEventKind,
+};
+use bevy_tnua::math::Vector3;
+use bevy_tnua::prelude::*;
+use bevy_tnua::TnuaBasis;
+
+use common::{Frame, Hold};
+
+/// A transition reported by the controller, as seen by an observer or an event reader.
+#[derive(Debug, Clone, PartialEq)]
+enum Transition {
+    Action(&'static str, TnuaActionEventKind),
+    Basis(TnuaBasisEventKind),
+}
+
+/// Transitions along with the frames they were reported on.
+type Log = Vec<(usize, Transition)>;
+
+#[derive(Resource, Default)]
+struct Observed(Log);
+
+type Control = fn(usize, &mut TnuaController);
+
+#[derive(Resource)]
+struct ControlFn(Control);
+
+fn control(frame: Res<Frame>, control_fn: Res<ControlFn>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        (control_fn.0)(frame.0, &mut controller);
+    }
+}
+
+/// Run the simulation, and return the transitions both from the observers and from the events.
+fn run(control_fn: Control, frames: usize) -> (Log, Log) {
+    let mut app = common::app();
+    app.insert_resource(ControlFn(control_fn));
+    app.init_resource::<Observed>();
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    app.world_mut()
+        .entity_mut(character)
+        .observe(
+            |trigger: Trigger<TnuaActionEvent>,
+             frame: Res<Frame>,
+             mut observed: ResMut<Observed>| {
+                let event = trigger.event();
+                observed
+                    .0
+                    .push((frame.0, Transition::Action(event.layer, event.kind.clone())));
+            },
+        )
+        .observe(
+            |trigger: Trigger<TnuaBasisEvent>,
+             frame: Res<Frame>,
+             mut observed: ResMut<Observed>| {
+                observed
+                    .0
+                    .push((frame.0, Transition::Basis(trigger.event().kind)));
+            },
+        );
+
+    let mut from_events = Vec::new();
+    for _ in 0..frames {
+        app.update();
+        let frame = app.world().resource::<Frame>().0;
+        for event in app
+            .world_mut()
+            .resource_mut::<Events<TnuaActionEvent>>()
+            .drain()
+        {
+            assert_eq!(event.entity, character);
+            from_events.push((frame, Transition::Action(event.layer, event.kind)));
+        }
+        for event in app
+            .world_mut()
+            .resource_mut::<Events<TnuaBasisEvent>>()
+            .drain()
+        {
+            assert_eq!(event.entity, character);
+            assert_eq!(event.basis, TnuaBuiltinWalk::NAME);
+            from_events.push((frame, Transition::Basis(event.kind)));
+        }
+    }
+    let observed = std::mem::take(&mut app.world_mut().resource_mut::<Observed>().0);
+    (observed, from_events)
+}
+
+fn jump() -> TnuaBuiltinJump {
+    TnuaBuiltinJump {
+        height: 2.0,
+        ..Default::default()
+    }
+}
+
+/// The transitions without the frames they happened on.
+fn transitions(observed: &[(usize, Transition)]) -> Vec<Transition> {
+    observed
+        .iter()
+        .map(|(_, transition)| transition.clone())
+        .collect()
+}
+
+fn frame_of(observed: &[(usize, Transition)], transition: &Transition) -> usize {
+    observed
+        .iter()
+        .find(|(_, t)| t == transition)
+        .unwrap_or_else(|| panic!("{transition:?} was not reported"))
+        .0
+}
+
+#[test]
+fn jump_reports_events_in_order() {
+    let (observed, from_events) = run(
+        |frame, controller| {
+            if (10..20).contains(&frame) {
+                controller.action(jump());
+            }
+        },
+        120,
+    );
+    assert_eq!(observed, from_events);
+
+    let started = Transition::Action(
+        TnuaController::MAIN_ACTION_LAYER,
+        TnuaActionEventKind::Started {
+            action: TnuaBuiltinJump::NAME,
+        },
+    );
+    let ended = Transition::Action(
+        TnuaController::MAIN_ACTION_LAYER,
+        TnuaActionEventKind::Ended {
+            action: TnuaBuiltinJump::NAME,
+        },
+    );
+    assert_eq!(
+        transitions(&observed),
+        [
+            // Settling on the floor after spawning.
+            Transition::Basis(TnuaBasisEventKind::Landed),
+            started.clone(),
+            Transition::Basis(TnuaBasisEventKind::TookOff),
+            Transition::Basis(TnuaBasisEventKind::CoyoteTimeExpired),
+            ended.clone(),
+            Transition::Basis(TnuaBasisEventKind::Landed),
+        ]
+    );
+    assert_eq!(frame_of(&observed, &started), 10);
+    // The jump finishes on the frame it lands, and the action is reported before the basis.
+    let landed = observed.last().unwrap().0;
+    assert_eq!(frame_of(&observed, &ended), landed);
+}
+
+#[test]
+fn cancelled_action_reports_events_in_order() {
+    let (observed, from_events) = run(
+        |frame, controller| {
+            if (10..20).contains(&frame) {
+                controller.action(jump());
+            }
+            if (15..25).contains(&frame) {
+                controller.action(Hold);
+            }
+        },
+        120,
+    );
+    assert_eq!(observed, from_events);
+
+    let action_transitions = observed
+        .iter()
+        .filter(|(_, transition)| matches!(transition, Transition::Action(..)))
+        .cloned()
+        .collect::<Vec<_>>();
+    let action = |kind| Transition::Action(TnuaController::MAIN_ACTION_LAYER, kind);
+    assert_eq!(
+        action_transitions,
+        [
+            (
+                10,
+                action(TnuaActionEventKind::Started {
+                    action: TnuaBuiltinJump::NAME
+                })
+            ),
+            (
+                15,
+                action(TnuaActionEventKind::Cancelled {
+                    old: TnuaBuiltinJump::NAME,
+                    new: Hold::NAME,
+                })
+            ),
+            (
+                15,
+                action(TnuaActionEventKind::Started { action: Hold::NAME })
+            ),
+            (
+                25,
+                action(TnuaActionEventKind::Ended { action: Hold::NAME })
+            ),
+        ]
+    );
+    // The basis still goes through the whole airborne cycle, even though the jump was cut short.
+    assert_eq!(
+        observed
+            .iter()
+            .filter_map(|(_, transition)| match transition {
+                Transition::Basis(kind) => Some(*kind),
+                Transition::Action(..) => None,
+            })
+            .collect::<Vec<_>>(),
+        [
+            TnuaBasisEventKind::Landed,
+            TnuaBasisEventKind::TookOff,
+            TnuaBasisEventKind::CoyoteTimeExpired,
+            TnuaBasisEventKind::Landed,
+        ]
+    );
+}
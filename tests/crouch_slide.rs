@@ -0,0 +1,155 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinCrouchSlide, TnuaBuiltinCrouchSlideState};
+use bevy_tnua::control_helpers::{TnuaCrouchEnforcer, TnuaCrouchEnforcerPlugin};
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua_headless::TnuaHeadlessRigidBody;
+
+use common::Frame;
+
+const RUN_SPEED: Float = 8.0;
+
+/// The first frame the slide is fed on. The character runs until then.
+const SLIDE_FRAME: usize = 30;
+
+/// The first frame the slide is no longer fed on - long after it slowed down.
+const RELEASE_FRAME: usize = SLIDE_FRAME + 150;
+
+fn crouch_slide() -> TnuaBuiltinCrouchSlide {
+    TnuaBuiltinCrouchSlide {
+        float_offset: -0.5,
+        ..Default::default()
+    }
+}
+
+fn control(
+    frame: Res<Frame>,
+    mut query: Query<(&mut TnuaController, Option<&mut TnuaCrouchEnforcer>)>,
+) {
+    for (mut controller, crouch_enforcer) in query.iter_mut() {
+        if frame.0 < SLIDE_FRAME {
+            controller.basis(common::walk(RUN_SPEED * Vector3::X));
+            continue;
+        }
+        controller.basis(common::walk(Vector3::ZERO));
+        if RELEASE_FRAME <= frame.0 {
+            continue;
+        }
+        if let Some(mut crouch_enforcer) = crouch_enforcer {
+            controller.action(crouch_enforcer.enforcing(crouch_slide()));
+        } else {
+            controller.action(crouch_slide());
+        }
+    }
+}
+
+fn setup() -> (App, Entity) {
+    let mut app = common::app();
+    app.add_plugins(TnuaCrouchEnforcerPlugin::default());
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    (app, character)
+}
+
+fn horizontal_speed(app: &App, character: Entity) -> Float {
+    app.world()
+        .get::<TnuaHeadlessRigidBody>(character)
+        .unwrap()
+        .linvel
+        .with_y(0.0)
+        .length()
+}
+
+fn slide_state(app: &App, character: Entity) -> Option<TnuaBuiltinCrouchSlideState> {
+    let (_, state) =
+        common::controller(app, character).concrete_action::<TnuaBuiltinCrouchSlide>()?;
+    Some(state.clone())
+}
+
+#[test]
+fn slide_ends_below_stop_speed() {
+    let (mut app, character) = setup();
+    let slide = crouch_slide();
+    let mut sliding_frames = 0;
+    loop {
+        app.update();
+        let frame = app.world().resource::<Frame>().0;
+        assert!(frame < RELEASE_FRAME, "slide did not end");
+        match slide_state(&app, character) {
+            Some(TnuaBuiltinCrouchSlideState::Sliding { .. }) => {
+                sliding_frames += 1;
+                if 10 < sliding_frames {
+                    let height = common::position(&app, character).y;
+                    let crouched_height =
+                        common::FLOAT_HEIGHT.adjust_precision() + slide.float_offset;
+                    assert!((height - crouched_height).abs() < 0.05, "{height}");
+                }
+            }
+            Some(_) if 0 < sliding_frames => {
+                let speed = horizontal_speed(&app, character);
+                assert!(speed < slide.stop_speed, "{speed}");
+                break;
+            }
+            _ => assert_eq!(sliding_frames, 0, "slide ended without stopping"),
+        }
+    }
+    // The speed decays by the friction, from the running speed down to the stop speed.
+    let expected_frames =
+        (RUN_SPEED - slide.stop_speed) / slide.friction * common::FRAMES_PER_SECOND as Float;
+    assert!(
+        (sliding_frames as Float - expected_frames).abs() < 3.0,
+        "slid for {sliding_frames} frames instead of {expected_frames}"
+    );
+
+    // Without `end_in_crouch` the character stands up right away, even though the slide is still
+    // fed.
+    for _ in 0..30 {
+        app.update();
+    }
+    assert!(app.world().resource::<Frame>().0 < RELEASE_FRAME);
+    assert_eq!(common::controller(&app, character).action_name(), None);
+    let height = common::position(&app, character).y;
+    assert!(
+        (height - common::FLOAT_HEIGHT.adjust_precision()).abs() < 0.05,
+        "{height}"
+    );
+}
+
+#[test]
+fn slide_stays_crouched_under_crouch_enforcer() {
+    let (mut app, character) = setup();
+    // Too low to stand under, but high enough to slide under.
+    let ceiling_bottom = 1.3;
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(10.0, ceiling_bottom + 0.5, 0.0),
+        Vector3::new(8.0, 0.5, 5.0),
+    );
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaCrouchEnforcer::new(
+            common::CHARACTER_RADIUS.adjust_precision() * Vector3::Y,
+            |_| {},
+        ));
+
+    for _ in 0..RELEASE_FRAME + 60 {
+        app.update();
+    }
+    let position = common::position(&app, character);
+    assert!(2.0 < position.x && position.x < 18.0, "{position}");
+    // Stopped sliding, and kept crouching even though the slide is no longer fed.
+    assert!(
+        matches!(
+            slide_state(&app, character),
+            Some(TnuaBuiltinCrouchSlideState::Crouching)
+        ),
+        "{:?}",
+        slide_state(&app, character)
+    );
+    let crouched_height = common::FLOAT_HEIGHT.adjust_precision() + crouch_slide().float_offset;
+    assert!((position.y - crouched_height).abs() < 0.05, "{position}");
+    assert!(horizontal_speed(&app, character) < 0.01);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "debug_gizmos")]
+
+mod common;
+
+use bevy::asset::AssetPlugin;
+use bevy::gizmos::GizmoPlugin;
+use bevy::prelude::*;
+use bevy::render::render_resource::Shader;
+use bevy_tnua::builtins::TnuaBuiltinKnockback;
+use bevy_tnua::debug_gizmos::{TnuaDebugGizmos, TnuaDebugGizmosPlugin};
+use bevy_tnua::math::Vector3;
+use bevy_tnua::prelude::*;
+use bevy_tnua::{TnuaGhostSensor, TnuaObstacleRadar};
+
+use common::Frame;
+
+fn control(frame: Res<Frame>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::X));
+        if (10..20).contains(&frame.0) {
+            controller.action(TnuaBuiltinJump {
+                height: 2.0,
+                ..Default::default()
+            });
+        }
+        if (60..70).contains(&frame.0) {
+            controller.action(TnuaBuiltinKnockback {
+                shove: Vector3::new(-5.0, 0.0, 3.0),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// Run a character through the things the gizmos draw, with the given gizmos configuration.
+fn run(configure: impl FnOnce(&mut TnuaDebugGizmos)) {
+    let mut app = common::app();
+    app.add_plugins(AssetPlugin::default());
+    // Normally registered by the render plugin, which cannot run headless.
+    app.init_asset::<Shader>();
+    app.add_plugins((GizmoPlugin, TnuaDebugGizmosPlugin::default()));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(2.0, 1.0, 0.0),
+        Vector3::new(0.5, 1.0, 0.5),
+    );
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    app.world_mut()
+        .entity_mut(character)
+        .insert((TnuaObstacleRadar::new(1.5, 2.0), TnuaGhostSensor::default()));
+
+    let mut config_store = app.world_mut().resource_mut::<GizmoConfigStore>();
+    let (_, tnua_gizmos) = config_store.config_mut::<TnuaDebugGizmos>();
+    configure(tnua_gizmos);
+
+    for _ in 0..120 {
+        app.update();
+    }
+    assert!(common::controller(&app, character).basis_name().is_some());
+}
+
+#[test]
+fn draws_all_gizmos() {
+    run(|tnua_gizmos| {
+        assert!(tnua_gizmos.proximity_sensor);
+        assert!(tnua_gizmos.ghost_sensor);
+        assert!(tnua_gizmos.obstacle_radar);
+        assert!(tnua_gizmos.walk_basis);
+        assert!(tnua_gizmos.motor);
+        assert!(tnua_gizmos.velocity_boundary);
+    });
+}
+
+#[test]
+fn draws_with_all_gizmos_disabled() {
+    run(|tnua_gizmos| {
+        tnua_gizmos.proximity_sensor = false;
+        tnua_gizmos.ghost_sensor = false;
+        tnua_gizmos.obstacle_radar = false;
+        tnua_gizmos.walk_basis = false;
+        tnua_gizmos.motor = false;
+        tnua_gizmos.velocity_boundary = false;
+    });
+}
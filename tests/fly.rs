@@ -0,0 +1,98 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::TnuaBuiltinFly;
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua_headless::TnuaHeadlessRigidBody;
+
+/// Where the character starts - high enough above the floor for the sensors to ignore it.
+const START: Vector3 = Vector3::new(0.0, 10.0, 0.0);
+
+#[derive(Resource)]
+struct Fly(TnuaBuiltinFly);
+
+fn control(fly: Res<Fly>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(fly.0.clone());
+    }
+}
+
+/// Fly for the given number of frames, and return the character's position on each frame.
+fn simulate(fly: TnuaBuiltinFly, gravity_scale: Float, frames: usize) -> Vec<Vector3> {
+    let mut app = common::app();
+    app.insert_resource(Fly(fly));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(
+        &mut app,
+        START - common::FLOAT_HEIGHT.adjust_precision() * Vector3::Y,
+    );
+    app.world_mut()
+        .get_mut::<TnuaHeadlessRigidBody>(character)
+        .unwrap()
+        .gravity_scale = gravity_scale;
+    (0..frames)
+        .map(|_| {
+            app.update();
+            common::position(&app, character)
+        })
+        .collect()
+}
+
+#[test]
+fn hovers_in_place_against_gravity() {
+    for gravity_scale in [1.0, 2.5] {
+        let positions = simulate(TnuaBuiltinFly::default(), gravity_scale, 120);
+        for position in positions {
+            assert!(
+                position.distance(START) < 0.01,
+                "drifted to {position} with gravity scale {gravity_scale}"
+            );
+        }
+    }
+}
+
+#[test]
+fn flies_at_desired_velocity() {
+    let desired_velocity = Vector3::new(3.0, 2.0, -1.0);
+    let frames = 120;
+    let positions = simulate(
+        TnuaBuiltinFly {
+            desired_velocity,
+            ..Default::default()
+        },
+        1.0,
+        frames,
+    );
+    // Measure over the last second, once the character is up to speed.
+    let last_second = &positions[frames - common::FRAMES_PER_SECOND as usize - 1..];
+    let velocity = *last_second.last().unwrap() - last_second[0];
+    assert!(velocity.distance(desired_velocity) < 0.01, "{velocity}");
+}
+
+#[test]
+fn hover_bob_stays_around_the_hover_height() {
+    let fly = TnuaBuiltinFly {
+        hover_bob_amplitude: 0.3,
+        ..Default::default()
+    };
+    let frames = (3.0 * fly.hover_bob_period * common::FRAMES_PER_SECOND as Float) as usize;
+    let positions = simulate(fly.clone(), 1.0, frames);
+    let heights = positions.iter().map(|position| position.y - START.y);
+    let (lowest, highest) = heights.fold((Float::INFINITY, Float::NEG_INFINITY), |(lo, hi), h| {
+        (lo.min(h), hi.max(h))
+    });
+    // The character starts at the middle of the bob, and may lag a bit behind it.
+    assert!(
+        fly.hover_bob_amplitude * 0.8 < highest && highest < fly.hover_bob_amplitude * 1.2,
+        "{highest}"
+    );
+    assert!(
+        -fly.hover_bob_amplitude * 1.2 < lowest && lowest < -fly.hover_bob_amplitude * 0.8,
+        "{lowest}"
+    );
+    for position in positions {
+        assert!(position.with_y(0.0).length() < 0.01, "{position}");
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinGlide, TnuaBuiltinGlideState};
+use bevy_tnua::math::{Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua_headless::TnuaHeadlessRigidBody;
+
+const WALK_VELOCITY: Vector3 = Vector3::new(3.0, 0.0, 0.0);
+
+fn control(mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(WALK_VELOCITY));
+        if controller.is_airborne().unwrap_or(false) {
+            controller.action(TnuaBuiltinGlide::default());
+        }
+    }
+}
+
+#[test]
+fn glide_caps_fall_speed_until_landing() {
+    let mut app = common::app();
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::new(0.0, 20.0, 0.0));
+    let glide = TnuaBuiltinGlide::default();
+
+    let mut previous_fall_speed = Float::NEG_INFINITY;
+    let mut gliding_frames = 0;
+    let mut capped = false;
+    let mut landed = false;
+    for frame in 0..900 {
+        app.update();
+        let velocity = app
+            .world()
+            .get::<TnuaHeadlessRigidBody>(character)
+            .unwrap()
+            .linvel;
+        let fall_speed = -velocity.y;
+        let controller = common::controller(&app, character);
+        let Some((_, state)) = controller.concrete_action::<TnuaBuiltinGlide>() else {
+            if 0 < gliding_frames {
+                // The glide only ends by landing.
+                assert!(!controller.is_airborne().unwrap(), "frame {frame}");
+                landed = true;
+                break;
+            }
+            continue;
+        };
+        gliding_frames += 1;
+        let TnuaBuiltinGlideState::Gliding {
+            fall_speed: state_fall_speed,
+            ..
+        } = state
+        else {
+            panic!("glide state was not set on frame {frame}");
+        };
+        assert!(
+            (fall_speed - state_fall_speed).abs() < 0.01,
+            "{fall_speed} != {state_fall_speed} on frame {frame}"
+        );
+        if capped {
+            assert!(
+                fall_speed <= glide.max_fall_speed + 0.01,
+                "fell at {fall_speed} on frame {frame}"
+            );
+        } else if 1 < gliding_frames {
+            // Braking down to the cap.
+            assert!(
+                fall_speed < previous_fall_speed,
+                "fall speed rose from {previous_fall_speed} to {fall_speed} on frame {frame}"
+            );
+        }
+        capped |= fall_speed <= glide.max_fall_speed + 0.01;
+        previous_fall_speed = fall_speed;
+
+        // The fall speed removed by the cap is converted into forward speed.
+        assert!(WALK_VELOCITY.x < velocity.x, "{velocity} on frame {frame}");
+        assert!(velocity.z.abs() < 0.01, "{velocity} on frame {frame}");
+    }
+    assert!(capped, "fall speed was never capped");
+    assert!(landed, "glide did not land");
+    // Gliding down at the capped speed takes a lot longer than falling.
+    let min_gliding_frames =
+        (15.0 / glide.max_fall_speed * common::FRAMES_PER_SECOND as Float) as usize;
+    assert!(
+        min_gliding_frames < gliding_frames,
+        "only glided for {gliding_frames} frames"
+    );
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinGravityFlip, TnuaBuiltinGravityFlipState};
+use bevy_tnua::math::Vector3;
+use bevy_tnua::prelude::*;
+use bevy_tnua::TnuaGravity;
+
+use common::{Frame, Hold};
+
+const FLIP_FRAME: usize = 10;
+const GRAVITY: Vector3 = Vector3::new(0.0, -9.81, 0.0);
+
+type Control = fn(usize, &mut TnuaController);
+
+#[derive(Resource)]
+struct ControlFn(Control);
+
+fn control(frame: Res<Frame>, control_fn: Res<ControlFn>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        (control_fn.0)(frame.0, &mut controller);
+    }
+}
+
+fn setup(control_fn: Control) -> (App, Entity) {
+    let mut app = common::app();
+    app.insert_resource(ControlFn(control_fn));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    (app, character)
+}
+
+/// Tap the flip for a single frame.
+fn tap_flip(frame: usize, controller: &mut TnuaController) {
+    if frame == FLIP_FRAME {
+        controller.action(TnuaBuiltinGravityFlip::default());
+    }
+}
+
+fn gravity(app: &App, character: Entity) -> Option<Vector3> {
+    Some(app.world().get::<TnuaGravity>(character)?.0)
+}
+
+fn assert_gravity_flipped(app: &App, character: Entity) {
+    let gravity = gravity(app, character).expect("gravity was not changed");
+    assert!(gravity.distance(-GRAVITY) < 1e-4, "gravity is {gravity}");
+}
+
+fn run(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+fn is_flipping(app: &App, character: Entity) -> bool {
+    common::controller(app, character)
+        .concrete_action::<TnuaBuiltinGravityFlip>()
+        .is_some_and(|(_, state)| matches!(state, TnuaBuiltinGravityFlipState::Flipping { .. }))
+}
+
+#[test]
+fn flip_inverts_gravity() {
+    let (mut app, character) = setup(tap_flip);
+    run(&mut app, FLIP_FRAME + 2);
+    assert!(is_flipping(&app, character));
+    run(&mut app, 60);
+    assert!(!is_flipping(&app, character));
+    assert_gravity_flipped(&app, character);
+}
+
+#[test]
+fn flip_is_not_left_midway_when_cancelled() {
+    let (mut app, character) = setup(|frame, controller| {
+        tap_flip(frame, controller);
+        if FLIP_FRAME + 2 <= frame {
+            controller.action(Hold);
+        }
+    });
+    run(&mut app, FLIP_FRAME + 5);
+    assert!(is_flipping(&app, character));
+    run(&mut app, 60);
+    assert_eq!(
+        common::controller(&app, character).action_name(),
+        Some(Hold::NAME)
+    );
+    assert_gravity_flipped(&app, character);
+}
+
+#[test]
+fn flip_resumes_from_snapshot() {
+    let (mut app, character) = setup(tap_flip);
+    run(&mut app, FLIP_FRAME + 5);
+    assert!(is_flipping(&app, character));
+    let snapshot = common::controller(&app, character).snapshot();
+
+    // Restore into a character in another world, which never had its gravity changed.
+    let (mut other_app, other_character) = setup(|_, _| {});
+    run(&mut other_app, FLIP_FRAME + 5);
+    assert_eq!(gravity(&other_app, other_character), None);
+    other_app
+        .world_mut()
+        .get_mut::<TnuaController>(other_character)
+        .unwrap()
+        .restore(&snapshot);
+    run(&mut other_app, 60);
+    assert!(!is_flipping(&other_app, other_character));
+    assert_gravity_flipped(&other_app, other_character);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::control_helpers::{
+    TnuaAffectedByGravitySources, TnuaGravitySource, TnuaGravitySourceShape,
+    TnuaGravitySourcesPlugin,
+};
+use bevy_tnua::math::{AsF32, Float, Vector3};
+use bevy_tnua::TnuaGravity;
+
+fn assert_close(actual: Vector3, expected: Vector3) {
+    assert!(
+        actual.distance(expected) < 1e-4,
+        "{actual} instead of {expected}"
+    );
+}
+
+#[test]
+fn point_source_pulls_toward_its_center() {
+    let source = TnuaGravitySource {
+        strength: 5.0,
+        radius: 10.0,
+        ..Default::default()
+    };
+    let transform = GlobalTransform::from_translation(Vec3::new(1.0, 2.0, 3.0));
+    assert_close(
+        source
+            .gravity_at(&transform, Vector3::new(1.0, 2.0, 7.0))
+            .unwrap(),
+        Vector3::NEG_Z * 5.0,
+    );
+    assert_eq!(
+        source.gravity_at(&transform, Vector3::new(1.0, 2.0, 14.0)),
+        None
+    );
+}
+
+#[test]
+fn line_source_pulls_toward_its_segment() {
+    let source = TnuaGravitySource {
+        shape: TnuaGravitySourceShape::Line { half_length: 2.0 },
+        strength: 5.0,
+        ..Default::default()
+    };
+    // The segment goes along the X axis, from -2.0 to 2.0.
+    let transform = GlobalTransform::from(Transform::from_rotation(Quat::from_rotation_z(
+        -std::f32::consts::FRAC_PI_2,
+    )));
+    assert_close(
+        source
+            .gravity_at(&transform, Vector3::new(1.5, 3.0, 0.0))
+            .unwrap(),
+        Vector3::NEG_Y * 5.0,
+    );
+    // Past the end of the segment, it pulls toward that end.
+    assert_close(
+        source
+            .gravity_at(&transform, Vector3::new(5.0, 0.0, 0.0))
+            .unwrap(),
+        Vector3::NEG_X * 5.0,
+    );
+}
+
+fn app() -> App {
+    let mut app = common::app();
+    app.add_plugins(TnuaGravitySourcesPlugin::default());
+    app
+}
+
+fn spawn_source(app: &mut App, position: Vector3, source: TnuaGravitySource) {
+    app.world_mut()
+        .spawn((Transform::from_translation(position.f32()), source));
+}
+
+fn spawn_affected_character(app: &mut App, position: Vector3, fallback: Vector3) -> Entity {
+    app.world_mut()
+        .spawn((
+            Transform::from_translation(position.f32()),
+            TnuaAffectedByGravitySources { fallback },
+        ))
+        .id()
+}
+
+fn gravity(app: &App, entity: Entity) -> Vector3 {
+    app.world().get::<TnuaGravity>(entity).unwrap().0
+}
+
+#[test]
+fn gravity_starts_at_the_fallback() {
+    let mut app = app();
+    let fallback = Vector3::NEG_Z * 3.0;
+    let character = spawn_affected_character(&mut app, Vector3::ZERO, fallback);
+    app.world_mut().flush();
+    assert_eq!(gravity(&app, character), fallback);
+
+    // An existing gravity is kept until the plugin updates it.
+    let existing = Vector3::X;
+    let character = app
+        .world_mut()
+        .spawn((
+            TnuaGravity(existing),
+            TnuaAffectedByGravitySources { fallback },
+        ))
+        .id();
+    app.world_mut().flush();
+    assert_eq!(gravity(&app, character), existing);
+}
+
+#[test]
+fn fallback_is_used_outside_all_sources() {
+    let mut app = app();
+    spawn_source(
+        &mut app,
+        Vector3::ZERO,
+        TnuaGravitySource {
+            radius: 5.0,
+            ..Default::default()
+        },
+    );
+    let fallback = Vector3::NEG_Z * 3.0;
+    let inside = spawn_affected_character(&mut app, Vector3::Y * 4.0, fallback);
+    let outside = spawn_affected_character(&mut app, Vector3::Y * 6.0, fallback);
+    app.update();
+    assert_close(gravity(&app, inside), Vector3::NEG_Y * 9.81);
+    assert_eq!(gravity(&app, outside), fallback);
+}
+
+#[test]
+fn closest_source_with_highest_priority_wins() {
+    let mut app = app();
+    let source = |strength: Float, priority: i32| TnuaGravitySource {
+        strength,
+        priority,
+        ..Default::default()
+    };
+    spawn_source(&mut app, Vector3::X * 10.0, source(1.0, 0));
+    spawn_source(&mut app, Vector3::X * -3.0, source(2.0, 0));
+    spawn_source(&mut app, Vector3::Y * 20.0, source(3.0, 1));
+    let character = spawn_affected_character(&mut app, Vector3::ZERO, Vector3::ZERO);
+    app.update();
+    // The far source has the higher priority.
+    assert_close(gravity(&app, character), Vector3::Y * 3.0);
+
+    // Among the sources that share the highest priority, the closest one wins.
+    spawn_source(&mut app, Vector3::X * -25.0, source(4.0, 1));
+    spawn_source(&mut app, Vector3::Z * 15.0, source(5.0, 1));
+    // The global transforms of the new sources are only propagated at the end of the frame.
+    app.update();
+    app.update();
+    assert_close(gravity(&app, character), Vector3::Z * 5.0);
+}
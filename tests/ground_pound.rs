@@ -0,0 +1,135 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_tnua::builtins::{
+    TnuaBuiltinGroundPound, TnuaBuiltinGroundPoundImpacted, TnuaBuiltinGroundPoundState,
+};
+use bevy_tnua::control_helpers::TnuaSimpleAirActionsCounter;
+use bevy_tnua::math::Vector3;
+use bevy_tnua::prelude::*;
+use bevy_tnua::TnuaToggle;
+
+use common::Frame;
+
+const POUND_LAYER: &str = "pound";
+
+#[derive(Resource)]
+struct PoundOnLayer(Option<&'static str>);
+
+fn control(
+    frame: Res<Frame>,
+    pound_on_layer: Res<PoundOnLayer>,
+    mut query: Query<&mut TnuaController>,
+) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        if (1..=5).contains(&frame.0) {
+            controller.action(TnuaBuiltinJump {
+                height: 3.0,
+                ..Default::default()
+            });
+        }
+        if (15..=20).contains(&frame.0) {
+            if let Some(layer) = pound_on_layer.0 {
+                controller
+                    .action_layer(layer)
+                    .action(TnuaBuiltinGroundPound::default());
+            } else {
+                controller.action(TnuaBuiltinGroundPound::default());
+            }
+        }
+    }
+}
+
+fn setup(pound_on_layer: Option<&'static str>) -> (App, Entity) {
+    let mut app = common::app();
+    app.insert_resource(PoundOnLayer(pound_on_layer));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    (app, character)
+}
+
+fn drain_impacts(app: &mut App) -> Vec<TnuaBuiltinGroundPoundImpacted> {
+    app.world_mut()
+        .resource_mut::<Events<TnuaBuiltinGroundPoundImpacted>>()
+        .drain()
+        .collect()
+}
+
+#[test]
+fn ground_pound_uses_one_air_action() {
+    let (mut app, character) = setup(None);
+    let mut counter = TnuaSimpleAirActionsCounter::default();
+    let mut pound_frames = 0;
+    let mut recovered = false;
+    for _ in 0..120 {
+        app.update();
+        let controller = common::controller(&app, character);
+        counter.update(controller);
+        if let Some((_, state)) = controller.concrete_action::<TnuaBuiltinGroundPound>() {
+            if pound_frames == 0 {
+                // Started after the jump from the ground - so it is the first air action.
+                assert_eq!(counter.air_count_for(TnuaBuiltinGroundPound::NAME), 1);
+            }
+            pound_frames += 1;
+            if !matches!(state, TnuaBuiltinGroundPoundState::Recovering { .. }) {
+                // The jump and the ground pound - and nothing else, even after the ground pound
+                // is no longer fed.
+                assert_eq!(counter.air_count_for(TnuaBuiltinJump::NAME), 2);
+            }
+        } else if 0 < pound_frames {
+            recovered = true;
+            assert_eq!(counter.air_count_for(TnuaBuiltinJump::NAME), 0);
+        }
+    }
+    assert!(0 < pound_frames, "ground pound did not start");
+    assert!(recovered, "ground pound did not finish");
+}
+
+#[test]
+fn ground_pound_impact_is_reported_once() {
+    for pound_on_layer in [None, Some(POUND_LAYER)] {
+        let (mut app, character) = setup(pound_on_layer);
+        let mut impacts = Vec::new();
+        for _ in 0..120 {
+            app.update();
+            let new_impacts = drain_impacts(&mut app);
+            if !new_impacts.is_empty() {
+                // Frames where no time passes should not report the impact again.
+                app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+                for _ in 0..3 {
+                    app.update();
+                    assert!(drain_impacts(&mut app).is_empty());
+                }
+                app.insert_resource(TimeUpdateStrategy::ManualDuration(
+                    Duration::from_secs(1) / common::FRAMES_PER_SECOND,
+                ));
+                // Neither should frames where the controller is disabled.
+                app.world_mut()
+                    .entity_mut(character)
+                    .insert(TnuaToggle::Disabled);
+                for _ in 0..3 {
+                    app.update();
+                    assert!(drain_impacts(&mut app).is_empty());
+                }
+                app.world_mut()
+                    .entity_mut(character)
+                    .insert(TnuaToggle::Enabled);
+            }
+            impacts.extend(new_impacts);
+        }
+        assert_eq!(impacts.len(), 1, "impacts with pound on {pound_on_layer:?}");
+        let impact = &impacts[0];
+        assert_eq!(impact.entity, character);
+        assert!(
+            impact.impact.point.y.abs() < 0.1,
+            "impact at {}",
+            impact.impact.point
+        );
+        assert!(0.0 < impact.impact.speed);
+    }
+}
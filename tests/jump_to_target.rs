@@ -0,0 +1,199 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinJumpToTarget, TnuaBuiltinJumpToTargetUnreachable};
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua_headless::TnuaHeadlessGravity;
+
+use common::Frame;
+
+const GRAVITY: Float = 9.81;
+
+fn origin() -> Vector3 {
+    Vector3::new(0.0, common::FLOAT_HEIGHT.adjust_precision(), 0.0)
+}
+
+fn jump_to(target: Vector3) -> TnuaBuiltinJumpToTarget {
+    TnuaBuiltinJumpToTarget {
+        target,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn solve_without_target() {
+    assert_eq!(
+        TnuaBuiltinJumpToTarget::default()
+            .solve(origin(), Dir3::Y, GRAVITY)
+            .unwrap_err(),
+        TnuaBuiltinJumpToTargetUnreachable::NoTarget,
+    );
+}
+
+#[test]
+fn solve_without_gravity() {
+    let action = jump_to(Vector3::new(3.0, 1.0, 0.0));
+    for gravity in [0.0, -GRAVITY] {
+        assert_eq!(
+            action.solve(origin(), Dir3::Y, gravity).unwrap_err(),
+            TnuaBuiltinJumpToTargetUnreachable::NoGravity,
+        );
+    }
+}
+
+#[test]
+fn solve_too_high() {
+    let action = jump_to(Vector3::new(1.0, 10.0, 0.0));
+    let Err(TnuaBuiltinJumpToTargetUnreachable::TooHigh {
+        required_height,
+        max_height,
+    }) = action.solve(origin(), Dir3::Y, GRAVITY)
+    else {
+        panic!("target should be too high");
+    };
+    assert_eq!(required_height, 9.0 + action.apex_clearance);
+    assert_eq!(max_height, action.max_height);
+}
+
+#[test]
+fn solve_too_far() {
+    let action = jump_to(Vector3::new(100.0, 1.0, 0.0));
+    let Err(TnuaBuiltinJumpToTargetUnreachable::TooFar {
+        required_speed,
+        max_speed,
+    }) = action.solve(origin(), Dir3::Y, GRAVITY)
+    else {
+        panic!("target should be too far");
+    };
+    assert!(max_speed < required_speed);
+    assert_eq!(max_speed, action.max_horizontal_speed);
+}
+
+#[test]
+fn solve_lower_target() {
+    let action = jump_to(Vector3::new(4.0, -2.0, 0.0));
+    let solution = action.solve(origin(), Dir3::Y, GRAVITY).unwrap();
+    // The apex is measured from the takeoff point, since it is higher than the target.
+    assert_eq!(solution.height, action.apex_clearance);
+    // Falling below the takeoff point takes longer than going up.
+    let jump = TnuaBuiltinJump {
+        height: solution.height,
+        ..action.jump.clone()
+    };
+    assert!(2.0 * jump.duration_to_peak(GRAVITY) < solution.duration);
+    assert_eq!(solution.horizontal_velocity.normalize(), Vector3::X);
+}
+
+#[test]
+fn solve_ignores_negative_apex_clearance() {
+    for target in [Vector3::new(4.0, -2.0, 0.0), Vector3::new(4.0, 3.0, 0.0)] {
+        let action = TnuaBuiltinJumpToTarget {
+            apex_clearance: -1.0,
+            ..jump_to(target)
+        };
+        let solution = action.solve(origin(), Dir3::Y, GRAVITY).unwrap();
+        assert_eq!(solution.height, (target - origin()).y.max(0.0));
+        assert!(solution.duration.is_finite(), "{solution:?}");
+        assert!(0.0 < solution.duration, "{solution:?}");
+        assert!(solution.horizontal_velocity.is_finite(), "{solution:?}");
+    }
+}
+
+#[test]
+fn solve_follows_up_direction() {
+    let action = jump_to(Vector3::new(3.0, 0.0, 2.0));
+    let solution = action.solve(Vector3::ZERO, Dir3::NEG_X, GRAVITY).unwrap();
+    // With the up direction flipped to -X, the target is 3.0 below the takeoff point.
+    assert_eq!(solution.height, action.apex_clearance);
+    assert_eq!(solution.horizontal_velocity.x, 0.0);
+}
+
+#[derive(Resource)]
+struct Target(Vector3);
+
+fn control(frame: Res<Frame>, target: Res<Target>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        if (10..20).contains(&frame.0) {
+            controller.action(jump_to(target.0));
+        }
+    }
+}
+
+/// Jump from the top of a box with the given height to the target, and return where the
+/// character was when it came down to the target's height - or `None` if the jump never started.
+fn simulate(start_box_height: Float, target: Vector3, gravity: Vector3) -> Option<Vector3> {
+    let mut app = common::app();
+    app.insert_resource(Target(target));
+    app.insert_resource(TnuaHeadlessGravity(gravity));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let start = Vector3::new(0.0, start_box_height, 0.0);
+    if 0.0 < start_box_height {
+        common::spawn_static_cuboid(
+            &mut app,
+            start / 2.0,
+            Vector3::new(1.0, start_box_height / 2.0, 1.0),
+        );
+    }
+    // The box to land on, if the target is above the floor.
+    let target_ground = target - common::FLOAT_HEIGHT.adjust_precision() * Vector3::Y;
+    if 0.01 < target_ground.y {
+        common::spawn_static_cuboid(
+            &mut app,
+            Vector3::new(target_ground.x, target_ground.y / 2.0, target_ground.z),
+            Vector3::new(1.0, target_ground.y / 2.0, 1.0),
+        );
+    }
+    let character = common::spawn_character(&mut app, start);
+    let mut started = false;
+    let mut previous_height = Float::NEG_INFINITY;
+    for _ in 0..180 {
+        app.update();
+        let position = common::position(&app, character);
+        if started && position.y <= target.y && position.y < previous_height {
+            return Some(position);
+        }
+        previous_height = position.y;
+        started |= common::controller(&app, character).action_name()
+            == Some(TnuaBuiltinJumpToTarget::NAME);
+    }
+    assert!(!started, "jump did not come down to the target");
+    None
+}
+
+fn assert_landed_on(position: Vector3, target: Vector3) {
+    // The character only reaches the target's height somewhere between two frames, and travels
+    // about 0.15 horizontally in each frame of these jumps.
+    assert!(
+        position.distance(target) < 0.3,
+        "landed at {position} instead of {target}"
+    );
+}
+
+#[test]
+fn jump_lands_on_higher_target() {
+    let target = Vector3::new(6.0, 3.0, 2.0);
+    let landed_at = simulate(0.0, target, Vector3::NEG_Y * GRAVITY).expect("jump did not start");
+    assert_landed_on(landed_at, target);
+}
+
+#[test]
+fn jump_lands_on_lower_target() {
+    let target = Vector3::new(-4.0, 1.0, 3.0);
+    let landed_at = simulate(2.0, target, Vector3::NEG_Y * GRAVITY).expect("jump did not start");
+    assert_landed_on(landed_at, target);
+}
+
+#[test]
+fn unreachable_target_is_rejected() {
+    let target = Vector3::new(100.0, 1.0, 0.0);
+    assert_eq!(simulate(0.0, target, Vector3::NEG_Y * GRAVITY), None);
+}
+
+#[test]
+fn jump_is_rejected_without_gravity() {
+    let target = Vector3::new(3.0, 1.0, 0.0);
+    assert_eq!(simulate(0.0, target, Vector3::ZERO), None);
+}
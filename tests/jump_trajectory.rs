@@ -0,0 +1,179 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinJumpTrajectory, TnuaBuiltinWalk};
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+
+use common::Frame;
+
+const GRAVITY: Float = 9.81;
+
+/// The first frame the jump is fed on.
+const TAKEOFF_FRAME: usize = 30;
+
+fn jump() -> TnuaBuiltinJump {
+    TnuaBuiltinJump {
+        height: 3.0,
+        ..Default::default()
+    }
+}
+
+fn trajectory() -> TnuaBuiltinJumpTrajectory {
+    TnuaBuiltinJumpTrajectory {
+        origin: Vector3::ZERO,
+        up: Dir3::Y,
+        horizontal_velocity: Vector3::X,
+        vertical: jump().predict_vertical_trajectory(GRAVITY, None),
+    }
+}
+
+#[test]
+fn sample_includes_duration() {
+    let samples = trajectory().sample(0.3, 1.0).collect::<Vec<_>>();
+    let times = samples.iter().map(|(time, _)| *time).collect::<Vec<_>>();
+    assert_eq!(times.len(), 5);
+    assert_eq!(times[0], 0.0);
+    assert_eq!(times[4], 1.0);
+    assert_eq!(samples[4].1.x, 1.0);
+}
+
+#[test]
+fn sample_rejects_invalid_arguments() {
+    let trajectory = trajectory();
+    for (time_step, duration) in [
+        (0.1, Float::INFINITY),
+        (0.1, Float::NAN),
+        (0.1, -1.0),
+        (0.0, 1.0),
+        (-0.1, 1.0),
+        (Float::NAN, 1.0),
+    ] {
+        assert_eq!(
+            trajectory.sample(time_step, duration).count(),
+            0,
+            "sampled every {time_step} for {duration}"
+        );
+    }
+}
+
+#[test]
+fn sample_limits_tiny_time_step() {
+    let trajectory = trajectory();
+    let samples = trajectory.sample(Float::MIN_POSITIVE, 1.0);
+    assert_eq!(samples.count(), TnuaBuiltinJumpTrajectory::MAX_SAMPLES + 1);
+}
+
+/// For how many frames the jump is fed - or `None` to feed it until the end.
+#[derive(Resource)]
+struct FedFrames(Option<usize>);
+
+fn control(frame: Res<Frame>, fed_frames: Res<FedFrames>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::X * 4.0));
+        let fed_so_far = frame.0.checked_sub(TAKEOFF_FRAME);
+        if fed_so_far.is_some_and(|fed_so_far| fed_frames.0.is_none_or(|fed| fed_so_far < fed)) {
+            controller.action(jump());
+        }
+    }
+}
+
+struct Jump {
+    apex_time: Float,
+    apex: Vector3,
+    landing_time: Float,
+}
+
+/// Predict the jump right before it starts, and simulate it.
+fn predict_and_simulate(fed_frames: Option<usize>) -> (Jump, Jump) {
+    let mut app = common::app();
+    app.insert_resource(FedFrames(fed_frames));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    let frame_duration = 1.0 / common::FRAMES_PER_SECOND as Float;
+    let release_after = fed_frames.map(|fed_frames| fed_frames as Float * frame_duration);
+
+    for _ in 1..TAKEOFF_FRAME {
+        app.update();
+    }
+    let (_, walk_state) = common::controller(&app, character)
+        .concrete_basis::<TnuaBuiltinWalk>()
+        .unwrap();
+    let trajectory = jump().predict_trajectory(
+        walk_state,
+        common::position(&app, character),
+        Dir3::Y,
+        GRAVITY,
+        release_after,
+    );
+    let (apex_time, apex) = trajectory.apex().unwrap();
+    let landing_height = -trajectory.origin.y + common::FLOAT_HEIGHT.adjust_precision();
+    let predicted = Jump {
+        apex_time,
+        apex,
+        landing_time: trajectory.time_to_land_at(landing_height).unwrap(),
+    };
+
+    let mut apex_time = 0.0;
+    let mut apex = Vector3::NEG_INFINITY;
+    let mut landing_time = None;
+    for step in 1..120 {
+        app.update();
+        let time = step as Float * frame_duration;
+        let position = common::position(&app, character);
+        if apex.y < position.y {
+            apex_time = time;
+            apex = position;
+        } else if position.y <= common::FLOAT_HEIGHT.adjust_precision() {
+            landing_time = Some(time);
+            break;
+        }
+    }
+    let simulated = Jump {
+        apex_time,
+        apex,
+        landing_time: landing_time.expect("character did not land"),
+    };
+    (predicted, simulated)
+}
+
+fn assert_prediction_matches(predicted: Jump, simulated: Jump) {
+    // Up to two frames off, since the simulation only samples the jump once per frame.
+    let time_tolerance = 2.0 / common::FRAMES_PER_SECOND as Float;
+    assert!(
+        (predicted.apex_time - simulated.apex_time).abs() < time_tolerance,
+        "predicted apex at {}, simulated at {}",
+        predicted.apex_time,
+        simulated.apex_time,
+    );
+    assert!(
+        predicted.apex.distance(simulated.apex) < 0.3,
+        "predicted apex at {}, simulated at {}",
+        predicted.apex,
+        simulated.apex,
+    );
+    assert!(
+        (predicted.landing_time - simulated.landing_time).abs() < time_tolerance,
+        "predicted landing at {}, simulated at {}",
+        predicted.landing_time,
+        simulated.landing_time,
+    );
+}
+
+#[test]
+fn prediction_matches_full_hold_jump() {
+    let (predicted, simulated) = predict_and_simulate(None);
+    assert_prediction_matches(predicted, simulated);
+}
+
+#[test]
+fn prediction_matches_early_release_jump() {
+    let (predicted, simulated) = predict_and_simulate(Some(6));
+    let full_apex_height = common::FLOAT_HEIGHT.adjust_precision() + jump().height;
+    assert!(
+        simulated.apex.y < full_apex_height - 0.5,
+        "jump was not shortened"
+    );
+    assert_prediction_matches(predicted, simulated);
+}
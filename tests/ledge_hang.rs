@@ -0,0 +1,147 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinLedgeHang, TnuaBuiltinLedgeHangState};
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::radar_lens::TnuaRadarLens;
+use bevy_tnua::TnuaObstacleRadar;
+use bevy_tnua_headless::TnuaSpatialExtHeadless;
+
+use common::Frame;
+
+/// The first frame the jump toward the ledge is fed on.
+const JUMP_FRAME: usize = 10;
+
+/// The height of the ledge's top surface.
+const LEDGE_HEIGHT: Float = 3.0;
+
+/// The frame to start climbing over the edge on, or `None` to keep hanging.
+#[derive(Resource)]
+struct ClimbFrame(Option<usize>);
+
+fn control(
+    frame: Res<Frame>,
+    climb_frame: Res<ClimbFrame>,
+    mut query: Query<(&mut TnuaController, &TnuaObstacleRadar)>,
+    spatial_ext: TnuaSpatialExtHeadless,
+) {
+    for (mut controller, obstacle_radar) in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+
+        let radar_lens = TnuaRadarLens::new(obstacle_radar, &spatial_ext);
+        let ledge = radar_lens.iter_blips().find_map(|blip| {
+            let ledge = blip.find_grabbable_ledge(Dir3::Y, 1.5, 1.0)?;
+            Some((blip.entity(), ledge))
+        });
+
+        if let (Some((ledge_entity, ledge)), Ok(true)) = (ledge, controller.is_airborne()) {
+            controller.action(TnuaBuiltinLedgeHang {
+                ledge_entity: Some(ledge_entity),
+                edge_point: ledge.edge_point,
+                wall_direction: ledge.wall_direction,
+                climb_up: climb_frame
+                    .0
+                    .is_some_and(|climb_frame| climb_frame <= frame.0),
+                ..Default::default()
+            });
+        } else if (JUMP_FRAME..JUMP_FRAME + 10).contains(&frame.0) {
+            controller.action(TnuaBuiltinJump {
+                height: 3.0,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// A character that jumps up next to the wall of a ledge, and falls back past its edge.
+fn setup(climb_frame: Option<usize>) -> (App, Entity, Entity) {
+    let mut app = common::app();
+    app.insert_resource(ClimbFrame(climb_frame));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    // Its wall faces the character at x=1.0.
+    let ledge = common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(3.0, 0.5 * LEDGE_HEIGHT, 0.0),
+        Vector3::new(2.0, 0.5 * LEDGE_HEIGHT, 10.0),
+    );
+    let character = common::spawn_character(&mut app, Vector3::new(0.4, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaObstacleRadar::new(1.5, 2.0));
+    (app, character, ledge)
+}
+
+fn hang_position() -> Vector3 {
+    let action = TnuaBuiltinLedgeHang::default();
+    let edge_point = Vector3::new(1.0, LEDGE_HEIGHT, 0.0);
+    edge_point - action.hang_height * Vector3::Y - action.hang_distance * Vector3::X
+}
+
+#[test]
+fn ledge_hang_starts_from_radar_detection() {
+    let (mut app, character, ledge) = setup(None);
+    let mut grabbed_at = None;
+    let mut previous_height = Float::NEG_INFINITY;
+    for _ in 0..120 {
+        app.update();
+        let frame = app.world().resource::<Frame>().0;
+        let height = common::position(&app, character).y;
+        let controller = common::controller(&app, character);
+        if let Some((action, _)) = controller.concrete_action::<TnuaBuiltinLedgeHang>() {
+            assert_eq!(action.ledge_entity, Some(ledge));
+            assert_eq!(action.wall_direction, Dir3::X);
+            assert!(
+                action
+                    .edge_point
+                    .distance(Vector3::new(1.0, LEDGE_HEIGHT, 0.0))
+                    < 0.01,
+                "{}",
+                action.edge_point
+            );
+            if grabbed_at.is_none() {
+                // Only grabbed while falling from the jump.
+                assert!(height < previous_height, "grabbed while rising");
+                grabbed_at = Some(frame);
+            }
+        } else {
+            assert_eq!(grabbed_at, None, "let go of the ledge on frame {frame}");
+        }
+        previous_height = height;
+    }
+    assert!(grabbed_at.is_some(), "ledge was not grabbed");
+
+    let (_, state) = common::controller(&app, character)
+        .concrete_action::<TnuaBuiltinLedgeHang>()
+        .unwrap();
+    assert!(matches!(state, TnuaBuiltinLedgeHangState::Hanging { .. }));
+    let position = common::position(&app, character);
+    assert!(position.distance(hang_position()) < 0.05, "{position}");
+}
+
+#[test]
+fn climbing_up_ends_on_top_of_the_ledge() {
+    let climb_frame = 100;
+    let (mut app, character, _) = setup(Some(climb_frame));
+    for _ in 0..climb_frame {
+        app.update();
+    }
+    assert_eq!(
+        common::controller(&app, character).action_name(),
+        Some(TnuaBuiltinLedgeHang::NAME)
+    );
+    for _ in 0..120 {
+        app.update();
+    }
+    let controller = common::controller(&app, character);
+    assert_eq!(controller.action_name(), None);
+    assert!(!controller.is_airborne().unwrap());
+    let position = common::position(&app, character);
+    let action = TnuaBuiltinLedgeHang::default();
+    assert!(1.0 + action.climb_up_forward <= position.x, "{position}");
+    assert!(
+        (position.y - LEDGE_HEIGHT - common::FLOAT_HEIGHT.adjust_precision()).abs() < 0.1,
+        "{position}"
+    );
+}
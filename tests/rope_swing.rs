@@ -0,0 +1,117 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinRopeSwing, TnuaBuiltinRopeSwingState};
+use bevy_tnua::math::{float_consts, AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+
+/// The rope's anchor - high enough above the floor for the character to never reach it.
+const ANCHOR: Vector3 = Vector3::new(0.0, 20.0, 0.0);
+
+const ROPE_LENGTH: Float = 4.0;
+
+/// The angle of the rope from the down direction, when the character starts swinging.
+const START_ANGLE: Float = float_consts::FRAC_PI_4;
+
+#[derive(Resource)]
+struct RopeSwing(TnuaBuiltinRopeSwing);
+
+fn control(rope_swing: Res<RopeSwing>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+        controller.action(rope_swing.0.clone());
+    }
+}
+
+fn rope_swing() -> TnuaBuiltinRopeSwing {
+    TnuaBuiltinRopeSwing {
+        anchor: ANCHOR,
+        rope_length: ROPE_LENGTH,
+        ..Default::default()
+    }
+}
+
+/// Swing from a point 45 degrees below the anchor, with the rope already stretched, and return the
+/// character's position and rope state on each frame.
+///
+/// Starting on a taut rope, because while the rope is slack the basis' extra free fall gravity
+/// adds energy that would make the swing go higher than where it started.
+fn simulate(
+    rope_swing: TnuaBuiltinRopeSwing,
+    frames: usize,
+) -> Vec<(Vector3, TnuaBuiltinRopeSwingState)> {
+    let mut app = common::app();
+    app.insert_resource(RopeSwing(rope_swing));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let start = ANCHOR + ROPE_LENGTH * Vector3::new(START_ANGLE.sin(), -START_ANGLE.cos(), 0.0);
+    let character = common::spawn_character(
+        &mut app,
+        start - common::FLOAT_HEIGHT.adjust_precision() * Vector3::Y,
+    );
+    // The first update has no time delta, so the action only starts on the second one.
+    app.update();
+    (0..frames)
+        .map(|_| {
+            app.update();
+            let (_, state) = common::controller(&app, character)
+                .concrete_action::<TnuaBuiltinRopeSwing>()
+                .expect("rope swing is not running");
+            (common::position(&app, character), state.clone())
+        })
+        .collect()
+}
+
+#[test]
+fn rope_stays_taut_at_its_length() {
+    let frames = simulate(rope_swing(), 180);
+    for (i, (position, state)) in frames.iter().enumerate() {
+        let TnuaBuiltinRopeSwingState::Swinging { taut, .. } = state else {
+            panic!("rope swing state was not set on frame {i}");
+        };
+        // Gravity keeps pulling the character away from the anchor for the entire swing.
+        assert!(*taut, "rope went slack on frame {i}");
+        let distance = position.distance(ANCHOR);
+        assert!(
+            (distance - ROPE_LENGTH).abs() < 0.01,
+            "rope length changed to {distance} on frame {i}"
+        );
+    }
+
+    // Swings like a pendulum - down through the bottom and up to the same height on the other
+    // side.
+    let lowest = frames
+        .iter()
+        .map(|(position, _)| position.y)
+        .fold(Float::INFINITY, Float::min);
+    assert!((lowest - (ANCHOR.y - ROPE_LENGTH)).abs() < 0.01, "{lowest}");
+    let (furthest, _) = frames
+        .iter()
+        .min_by(|(a, _), (b, _)| a.x.total_cmp(&b.x))
+        .unwrap();
+    let mirrored_start =
+        ANCHOR + ROPE_LENGTH * Vector3::new(-START_ANGLE.sin(), -START_ANGLE.cos(), 0.0);
+    assert!(furthest.distance(mirrored_start) < 0.1, "{furthest}");
+}
+
+#[test]
+fn reeling_in_shortens_the_rope() {
+    let reel_in_speed = 1.0;
+    let frames = simulate(
+        TnuaBuiltinRopeSwing {
+            reel_in_speed,
+            ..rope_swing()
+        },
+        common::FRAMES_PER_SECOND as usize,
+    );
+    let (position, state) = frames.last().unwrap();
+    let TnuaBuiltinRopeSwingState::Swinging { rope_length, .. } = state else {
+        panic!("rope swing state was not set");
+    };
+    assert!(
+        (rope_length - (ROPE_LENGTH - reel_in_speed)).abs() < 0.01,
+        "{rope_length}"
+    );
+    let distance = position.distance(ANCHOR);
+    assert!(distance <= rope_length + 0.01, "{distance}");
+}
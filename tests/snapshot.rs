@@ -0,0 +1,125 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::TnuaBuiltinCrouch;
+use bevy_tnua::controller::TnuaControllerSnapshot;
+use bevy_tnua::math::Vector3;
+use bevy_tnua::prelude::*;
+use bevy_tnua_headless::TnuaHeadlessRigidBody;
+
+use common::Frame;
+
+const UPPER_LAYER: &str = "upper";
+
+fn control(frame: Res<Frame>, mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::X * 3.0));
+        if (5..40).contains(&frame.0) {
+            controller.action(TnuaBuiltinJump {
+                height: 3.0,
+                ..Default::default()
+            });
+        }
+        if 2 <= frame.0 {
+            controller
+                .action_layer(UPPER_LAYER)
+                .action(TnuaBuiltinCrouch {
+                    float_offset: -0.3,
+                    ..Default::default()
+                });
+        }
+    }
+}
+
+/// Everything observable about the character in a single frame.
+#[derive(Debug, PartialEq)]
+struct Observation {
+    translation: Vec3,
+    linvel: Vector3,
+    basis_state: String,
+    layers: Vec<(&'static str, Option<&'static str>, String)>,
+}
+
+fn observe(app: &App, character: Entity) -> Observation {
+    let controller = common::controller(app, character);
+    Observation {
+        translation: app.world().get::<Transform>(character).unwrap().translation,
+        linvel: app
+            .world()
+            .get::<TnuaHeadlessRigidBody>(character)
+            .unwrap()
+            .linvel,
+        basis_state: format!("{:?}", controller.dynamic_basis().unwrap().state_reflect()),
+        layers: controller
+            .iter_action_layers()
+            .map(|(name, layer)| {
+                (
+                    name,
+                    layer.action_name(),
+                    format!("{:?}", layer.dynamic_action().map(|a| a.state_reflect())),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn run(app: &mut App, character: Entity, frames: usize) -> Vec<Observation> {
+    (0..frames)
+        .map(|_| {
+            app.update();
+            observe(app, character)
+        })
+        .collect()
+}
+
+#[test]
+fn restored_snapshot_reproduces_the_simulation() {
+    let mut app = common::app();
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+
+    run(&mut app, character, 20);
+
+    let controller = common::controller(&app, character);
+    assert_eq!(controller.action_name(), Some(TnuaBuiltinJump::NAME));
+    assert_eq!(
+        controller
+            .get_action_layer(UPPER_LAYER)
+            .and_then(|layer| layer.action_name()),
+        Some(TnuaBuiltinCrouch::NAME)
+    );
+    let snapshot: TnuaControllerSnapshot = controller.snapshot();
+    let transform = *app.world().get::<Transform>(character).unwrap();
+    let rigid_body = app
+        .world()
+        .get::<TnuaHeadlessRigidBody>(character)
+        .unwrap()
+        .clone();
+    let frame = *app.world().resource::<Frame>();
+
+    let original = run(&mut app, character, 40);
+
+    app.world_mut()
+        .get_mut::<TnuaController>(character)
+        .unwrap()
+        .restore(&snapshot);
+    *app.world_mut().get_mut::<Transform>(character).unwrap() = transform;
+    *app.world_mut()
+        .get_mut::<TnuaHeadlessRigidBody>(character)
+        .unwrap() = rigid_body;
+    *app.world_mut().resource_mut::<Frame>() = frame;
+
+    let replayed = run(&mut app, character, 40);
+
+    assert_eq!(original, replayed);
+}
+
+#[test]
+fn restore_removes_layers_created_after_the_snapshot() {
+    let mut controller = TnuaController::default();
+    let snapshot = controller.snapshot();
+    controller.action_layer(UPPER_LAYER);
+    controller.restore(&snapshot);
+    assert!(controller.get_action_layer(UPPER_LAYER).is_none());
+}
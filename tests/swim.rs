@@ -0,0 +1,144 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinSwim, TnuaBuiltinSwimMode};
+use bevy_tnua::math::{AdjustPrecision, AsF32, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::{TnuaWaterSensor, TnuaWaterVolume};
+use bevy_tnua_headless::TnuaHeadlessCollider;
+
+use common::Frame;
+
+/// Where the pool's water ends, along the X axis.
+const SHORE: Float = 5.0;
+/// Where the shore steps down a bit, along the X axis.
+const STEP: Float = SHORE + 10.0;
+const STEP_HEIGHT: Float = 0.2;
+
+type DesiredVelocity = fn(usize) -> Vector3;
+
+#[derive(Resource)]
+struct DesiredVelocityFn(DesiredVelocity);
+
+fn control(
+    frame: Res<Frame>,
+    desired_velocity: Res<DesiredVelocityFn>,
+    mut query: Query<&mut TnuaController>,
+) {
+    for mut controller in query.iter_mut() {
+        controller.basis(TnuaBuiltinSwim {
+            desired_velocity: (desired_velocity.0)(frame.0),
+            float_height: common::FLOAT_HEIGHT.adjust_precision(),
+            ..Default::default()
+        });
+    }
+}
+
+/// A pool whose water surface is at 1.0, next to a shore whose top is at zero height - with the
+/// shore stepping down a bit further away from the water.
+fn setup(desired_velocity: DesiredVelocity, ground_point: Vector3) -> (App, Entity) {
+    let mut app = common::app();
+    app.insert_resource(DesiredVelocityFn(desired_velocity));
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new((SHORE + STEP) / 2.0, -0.5, 0.0),
+        Vector3::new((STEP - SHORE) / 2.0, 0.5, 50.0),
+    );
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(STEP + 50.0, -0.5 - STEP_HEIGHT, 0.0),
+        Vector3::new(50.0, 0.5, 50.0),
+    );
+    // The pool's floor.
+    common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(SHORE - 50.0, -3.5, 0.0),
+        Vector3::new(50.0, 0.5, 50.0),
+    );
+    app.world_mut().spawn((
+        Transform::from_translation(Vector3::new(SHORE - 50.0, -1.0, 0.0).f32()),
+        TnuaHeadlessCollider::Cuboid {
+            half_extents: Vector3::new(50.0, 2.0, 50.0),
+        },
+        TnuaWaterVolume,
+    ));
+    let character = common::spawn_character(&mut app, ground_point);
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaWaterSensor::default());
+    (app, character)
+}
+
+/// Run the simulation and return the swim modes the character went through.
+fn modes(app: &mut App, character: Entity, frames: usize) -> Vec<TnuaBuiltinSwimMode> {
+    let mut modes = Vec::new();
+    for _ in 0..frames {
+        app.update();
+        // The ground sensor only gets the cast range the basis asks for on the second frame.
+        if app.world().resource::<Frame>().0 <= 2 {
+            continue;
+        }
+        let (_, state) = common::controller(app, character)
+            .concrete_basis::<TnuaBuiltinSwim>()
+            .unwrap();
+        if modes.last() != Some(&state.mode) {
+            modes.push(state.mode);
+        }
+    }
+    modes
+}
+
+#[test]
+fn swimming_out_of_the_water() {
+    let (mut app, character) = setup(|_| Vector3::X * 2.0, Vector3::new(-5.0, -0.5, 0.0));
+    assert_eq!(
+        modes(&mut app, character, 480),
+        [TnuaBuiltinSwimMode::Surface, TnuaBuiltinSwimMode::OnGround]
+    );
+    let position = common::position(&app, character);
+    assert!(SHORE < position.x && position.x < STEP, "{position}");
+    assert!(
+        (position.y - common::FLOAT_HEIGHT.adjust_precision()).abs() < 0.05,
+        "{position}"
+    );
+}
+
+#[test]
+fn diving_and_resurfacing() {
+    let (mut app, character) = setup(
+        |frame| {
+            if (30..60).contains(&frame) {
+                Vector3::NEG_Y * 2.0
+            } else if (90..150).contains(&frame) {
+                Vector3::Y * 2.0
+            } else {
+                Vector3::ZERO
+            }
+        },
+        Vector3::new(-20.0, -0.5, 0.0),
+    );
+    assert_eq!(
+        modes(&mut app, character, 240),
+        [
+            TnuaBuiltinSwimMode::Surface,
+            TnuaBuiltinSwimMode::Underwater,
+            TnuaBuiltinSwimMode::Surface,
+        ]
+    );
+}
+
+#[test]
+fn stepping_down_stays_on_the_ground() {
+    let (mut app, character) = setup(|_| Vector3::X * 2.0, Vector3::new(STEP - 2.0, 0.0, 0.0));
+    assert_eq!(
+        modes(&mut app, character, 180),
+        [TnuaBuiltinSwimMode::OnGround]
+    );
+    let position = common::position(&app, character);
+    assert!(STEP + 1.0 < position.x, "{position}");
+    assert!(
+        (position.y - (common::FLOAT_HEIGHT.adjust_precision() - STEP_HEIGHT)).abs() < 0.05,
+        "{position}"
+    );
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinVault, TnuaBuiltinVaultState};
+use bevy_tnua::math::{AdjustPrecision, Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::radar_lens::TnuaRadarLens;
+use bevy_tnua::TnuaObstacleRadar;
+use bevy_tnua_headless::TnuaSpatialExtHeadless;
+
+const WALK_VELOCITY: Vector3 = Vector3::new(3.0, 0.0, 0.0);
+
+/// The low wall to vault over.
+const OBSTACLE_POSITION: Vector3 = Vector3::new(2.1, 0.75, 0.0);
+const OBSTACLE_HALF_EXTENTS: Vector3 = Vector3::new(0.1, 0.75, 5.0);
+
+fn control(
+    mut query: Query<(&mut TnuaController, &TnuaObstacleRadar)>,
+    spatial_ext: TnuaSpatialExtHeadless,
+) {
+    for (mut controller, obstacle_radar) in query.iter_mut() {
+        controller.basis(common::walk(WALK_VELOCITY));
+
+        let radar_lens = TnuaRadarLens::new(obstacle_radar, &spatial_ext);
+        for blip in radar_lens.iter_blips() {
+            let Some(obstacle) = blip.find_vaultable_obstacle(Dir3::Y, 1.5, 1.0) else {
+                continue;
+            };
+            let Some(depth) = obstacle.depth else {
+                continue;
+            };
+            let distance = (obstacle.top_point - obstacle_radar.tracked_position())
+                .with_y(0.0)
+                .length();
+            if obstacle.height <= 0.0 || obstacle.direction.dot(Vec3::X) < 0.5 || 1.0 < distance {
+                continue;
+            }
+            controller.action(TnuaBuiltinVault {
+                obstacle_entity: Some(blip.entity()),
+                top_point: obstacle.top_point,
+                direction: obstacle.direction,
+                over_distance: depth + common::CHARACTER_RADIUS.adjust_precision() + 0.3,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// The distance from the character's center to the obstacle.
+fn distance_to_obstacle(position: Vector3) -> Float {
+    let closest = position.clamp(
+        OBSTACLE_POSITION - OBSTACLE_HALF_EXTENTS,
+        OBSTACLE_POSITION + OBSTACLE_HALF_EXTENTS,
+    );
+    position.distance(closest)
+}
+
+#[test]
+fn vault_starts_from_radar_detection() {
+    let mut app = common::app();
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let obstacle = common::spawn_static_cuboid(&mut app, OBSTACLE_POSITION, OBSTACLE_HALF_EXTENTS);
+    let character = common::spawn_character(&mut app, Vector3::ZERO);
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaObstacleRadar::new(1.5, 2.0));
+
+    let mut phases = Vec::new();
+    for _ in 0..120 {
+        app.update();
+        let position = common::position(&app, character);
+        // There is no collision response on the headless backend, so only the vault keeps the
+        // character from passing through the obstacle.
+        assert!(
+            common::CHARACTER_RADIUS.adjust_precision() < distance_to_obstacle(position),
+            "character went into the obstacle at {position}"
+        );
+        let controller = common::controller(&app, character);
+        if let Some((action, state)) = controller.concrete_action::<TnuaBuiltinVault>() {
+            assert_eq!(action.obstacle_entity, Some(obstacle));
+            assert_eq!(action.direction, Dir3::X);
+            // The near edge of the obstacle's top.
+            let top_point = Vector3::new(
+                OBSTACLE_POSITION.x - OBSTACLE_HALF_EXTENTS.x,
+                OBSTACLE_POSITION.y + OBSTACLE_HALF_EXTENTS.y,
+                0.0,
+            );
+            assert!(
+                action.top_point.distance(top_point) < 0.01,
+                "{}",
+                action.top_point
+            );
+            let phase = match state {
+                TnuaBuiltinVaultState::NoVault => "none",
+                TnuaBuiltinVaultState::Rising { .. } => "rising",
+                TnuaBuiltinVaultState::GoingOver { .. } => "going over",
+            };
+            if phases.last() != Some(&phase) {
+                phases.push(phase);
+            }
+        }
+    }
+    assert_eq!(phases, ["rising", "going over"]);
+
+    // Landed past the obstacle, and kept walking.
+    let position = common::position(&app, character);
+    assert!(
+        (OBSTACLE_POSITION + OBSTACLE_HALF_EXTENTS).x < position.x,
+        "{position}"
+    );
+    let controller = common::controller(&app, character);
+    assert_eq!(controller.action_name(), None);
+    assert!(!controller.is_airborne().unwrap());
+}
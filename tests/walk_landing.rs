@@ -0,0 +1,92 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_tnua::builtins::TnuaBuiltinWalkLanded;
+use bevy_tnua::math::{Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::TnuaToggle;
+
+const DROP_HEIGHT: Float = 3.0;
+
+fn control(mut query: Query<&mut TnuaController>) {
+    for mut controller in query.iter_mut() {
+        controller.basis(common::walk(Vector3::ZERO));
+    }
+}
+
+fn drain_landings(app: &mut App) -> Vec<TnuaBuiltinWalkLanded> {
+    app.world_mut()
+        .resource_mut::<Events<TnuaBuiltinWalkLanded>>()
+        .drain()
+        .collect()
+}
+
+/// Drop the character, and run `after_landing` right after the frame it landed on.
+///
+/// Returns all the landings reported.
+fn drop_character(after_landing: impl Fn(&mut App, Entity)) -> Vec<TnuaBuiltinWalkLanded> {
+    let mut app = common::app();
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    let character = common::spawn_character(&mut app, Vector3::Y * DROP_HEIGHT);
+    let mut landings = Vec::new();
+    for _ in 0..120 {
+        app.update();
+        let new_landings = drain_landings(&mut app);
+        if !new_landings.is_empty() {
+            after_landing(&mut app, character);
+        }
+        landings.extend(new_landings);
+    }
+    landings
+}
+
+fn assert_landed_once(landings: &[TnuaBuiltinWalkLanded]) {
+    assert_eq!(landings.len(), 1, "{landings:?}");
+    let report = &landings[0].report;
+    // The character sinks a bit below its float height before the basis catches it.
+    assert!(
+        (DROP_HEIGHT..DROP_HEIGHT + 0.5).contains(&report.fall_height),
+        "fell from {}",
+        report.fall_height
+    );
+    assert!(report.impact_velocity < 0.0);
+}
+
+#[test]
+fn landing_is_reported_once() {
+    assert_landed_once(&drop_character(|_, _| {}));
+}
+
+#[test]
+fn landing_is_not_reported_again_on_zero_duration_frames() {
+    assert_landed_once(&drop_character(|app, _| {
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+        for _ in 0..3 {
+            app.update();
+            assert!(drain_landings(app).is_empty());
+        }
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(
+            Duration::from_secs(1) / common::FRAMES_PER_SECOND,
+        ));
+    }));
+}
+
+#[test]
+fn landing_is_not_reported_again_while_disabled() {
+    assert_landed_once(&drop_character(|app, character| {
+        app.world_mut()
+            .entity_mut(character)
+            .insert(TnuaToggle::Disabled);
+        for _ in 0..3 {
+            app.update();
+            assert!(drain_landings(app).is_empty());
+        }
+        app.world_mut()
+            .entity_mut(character)
+            .insert(TnuaToggle::Enabled);
+    }));
+}
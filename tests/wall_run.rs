@@ -0,0 +1,158 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_tnua::builtins::{TnuaBuiltinWallRun, TnuaBuiltinWallRunState};
+use bevy_tnua::math::{Float, Vector3};
+use bevy_tnua::prelude::*;
+use bevy_tnua::radar_lens::TnuaRadarLens;
+use bevy_tnua::TnuaObstacleRadar;
+use bevy_tnua_headless::{TnuaHeadlessRigidBody, TnuaSpatialExtHeadless};
+
+use common::Frame;
+
+/// The first frame the jump toward the wall run is fed on.
+const JUMP_FRAME: usize = 20;
+
+/// The speed the character walks along the wall at before jumping.
+const WALK_SPEED: Float = 6.0;
+
+fn wall_run() -> TnuaBuiltinWallRun {
+    TnuaBuiltinWallRun::default()
+}
+
+fn control(
+    frame: Res<Frame>,
+    mut query: Query<(&mut TnuaController, &TnuaObstacleRadar)>,
+    spatial_ext: TnuaSpatialExtHeadless,
+) {
+    for (mut controller, obstacle_radar) in query.iter_mut() {
+        controller.basis(common::walk(WALK_SPEED * Vector3::NEG_Z));
+
+        let radar_lens = TnuaRadarLens::new(obstacle_radar, &spatial_ext);
+        let wall = radar_lens.iter_blips().find_map(|blip| {
+            let wall = blip.find_runnable_wall(Dir3::Y, &[-0.5, 0.5], 0.9)?;
+            Some((blip.entity(), wall))
+        });
+
+        if let (Some((wall_entity, wall)), Ok(true)) = (wall, controller.is_airborne()) {
+            controller.action(TnuaBuiltinWallRun {
+                wall_entity: Some(wall_entity),
+                contact_point_with_wall: wall.contact_point,
+                normal: wall.normal,
+                ..wall_run()
+            });
+        } else if (JUMP_FRAME..JUMP_FRAME + 10).contains(&frame.0) {
+            controller.action(TnuaBuiltinJump {
+                height: 3.0,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+struct RunFrame {
+    position: Vector3,
+    velocity: Vector3,
+    state: TnuaBuiltinWallRunState,
+}
+
+/// Walk along a wall on the character's right, jump, and return every frame of the run that follows.
+fn simulate() -> Vec<RunFrame> {
+    let mut app = common::app();
+    app.add_systems(Update, control.in_set(TnuaUserControlsSystemSet));
+    common::spawn_floor(&mut app);
+    // Its face is at x=1.5, a bit further than the character's radius.
+    let wall = common::spawn_static_cuboid(
+        &mut app,
+        Vector3::new(2.0, 3.0, 0.0),
+        Vector3::new(0.5, 3.0, 50.0),
+    );
+    let character = common::spawn_character(&mut app, Vector3::new(0.7, 0.0, 0.0));
+    app.world_mut()
+        .entity_mut(character)
+        .insert(TnuaObstacleRadar::new(1.5, 2.0));
+
+    let mut run_frames = Vec::new();
+    for _ in 0..180 {
+        app.update();
+        let controller = common::controller(&app, character);
+        if let Some((action, state)) = controller.concrete_action::<TnuaBuiltinWallRun>() {
+            assert_eq!(action.wall_entity, Some(wall));
+            assert_eq!(action.normal, Dir3::NEG_X);
+            run_frames.push(RunFrame {
+                position: common::position(&app, character),
+                velocity: app
+                    .world()
+                    .get::<TnuaHeadlessRigidBody>(character)
+                    .unwrap()
+                    .linvel,
+                state: state.clone(),
+            });
+        }
+    }
+    run_frames
+}
+
+#[test]
+fn wall_run_starts_from_radar_detection() {
+    let run_frames = simulate();
+    assert!(!run_frames.is_empty(), "wall run did not start");
+    let TnuaBuiltinWallRunState::Running {
+        wall_side,
+        run_direction,
+        ..
+    } = run_frames.last().unwrap().state
+    else {
+        panic!("wall run state was not set");
+    };
+    assert_eq!(wall_side, 1.0);
+    assert!(
+        run_direction.distance(Vec3::NEG_Z) < 0.01,
+        "{run_direction:?}"
+    );
+}
+
+#[test]
+fn wall_run_holds_height_and_runs_along_the_wall() {
+    let run_frames = simulate();
+    let action = wall_run();
+    let frame_duration = 1.0 / common::FRAMES_PER_SECOND as Float;
+    // The last frame reached before the run times out.
+    let max_frames = (action.max_duration / frame_duration).ceil() as usize;
+    assert!(
+        (max_frames - 2..=max_frames).contains(&run_frames.len()),
+        "ran for {} frames",
+        run_frames.len()
+    );
+
+    let start_height = run_frames[0].position.y;
+    for (i, run_frame) in run_frames.iter().enumerate() {
+        let drop = start_height - run_frame.position.y;
+        assert!(
+            (-0.01..=action.height_drop + 0.01).contains(&drop),
+            "dropped {drop} on frame {i} of the run"
+        );
+        // Stays clear of the wall without flying away from it.
+        assert!(
+            (0.7 - 0.01..=1.0).contains(&run_frame.position.x),
+            "{}",
+            run_frame.position
+        );
+    }
+
+    // Accelerates from the walk speed to the run speed.
+    let final_velocity = run_frames.last().unwrap().velocity;
+    assert!(
+        (final_velocity.z + action.run_speed).abs() < 0.1,
+        "{final_velocity}"
+    );
+    assert!(
+        final_velocity.y.abs() < action.height_drop / action.max_duration * 3.0,
+        "{final_velocity}"
+    );
+    assert!(
+        run_frames[0].velocity.z.abs() < action.run_speed,
+        "{}",
+        run_frames[0].velocity
+    );
+}